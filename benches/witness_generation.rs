@@ -0,0 +1,55 @@
+//! Benchmark of the out-of-circuit witness generation of `poly_divide_by_cyclo` for N = 4096
+//!
+//! Compare `cargo bench --bench witness_generation` with `cargo bench --bench witness_generation --features parallel`
+
+use std::time::Instant;
+use zk_fhe::chips::utils::{div_by_cyclo_witness, div_by_cyclo_witness_batch};
+
+const N: usize = 4096;
+const DEG_DVD: usize = 2 * N - 2;
+const Q: u64 = 18014398509309953;
+// Number of independent divisions, e.g. the products of the two public key polynomials by u in BFV encryption
+const BATCH: usize = 8;
+
+fn main() {
+    // Deterministic pseudo-random dividends with coefficients in the range [0, Q - 1]
+    let mut state = 0x2545f4914f6cdd1d_u64;
+    let dividends = (0..BATCH)
+        .map(|_| {
+            (0..=DEG_DVD)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state % Q
+                })
+                .collect::<Vec<u64>>()
+        })
+        .collect::<Vec<Vec<u64>>>();
+
+    let mut divisor = vec![0; N + 1];
+    divisor[0] = 1;
+    divisor[N] = 1;
+
+    let start = Instant::now();
+    let serial = dividends
+        .iter()
+        .map(|dividend| div_by_cyclo_witness::<DEG_DVD, N, Q>(dividend, &divisor))
+        .collect::<Vec<(Vec<u64>, Vec<u64>)>>();
+    let serial_time = start.elapsed();
+
+    let start = Instant::now();
+    let batch = div_by_cyclo_witness_batch::<DEG_DVD, N, Q>(&dividends, &divisor);
+    let batch_time = start.elapsed();
+
+    assert_eq!(batch, serial);
+
+    println!(
+        "{} divisions by x^{} + 1: serial {:?}, div_by_cyclo_witness_batch {:?} (parallel feature: {})",
+        BATCH,
+        N,
+        serial_time,
+        batch_time,
+        cfg!(feature = "parallel")
+    );
+}
@@ -16,7 +16,8 @@ use zk_fhe::chips::poly_distribution::{
     check_poly_from_distribution_chi_error, check_poly_from_distribution_chi_key,
 };
 use zk_fhe::chips::poly_operations::{
-    poly_add, poly_divide_by_cyclo, poly_mul_equal_deg, poly_reduce, poly_scalar_mul,
+    cyclotomic_divisor, poly_add, poly_divide_by_cyclo, poly_mul_equal_deg, poly_reduce,
+    poly_scalar_mul,
 };
 
 /// Circuit inputs for BFV encryption operations
@@ -140,26 +141,12 @@ fn bfv_encryption_circuit<F: ScalarField>(
     let range = RangeChip::default(lookup_bits);
 
     // Assign the cyclotomic polynomial to the circuit -> x^DEG + 1
-    // Performing the assignemnt for the index 0, using a for loop from 1 to DEG - 1, and performing the assignment for the index DEG enforces that:
+    // The `cyclotomic_divisor` chip loads the coefficients as constants, which enforces that:
     // - the degree of the polynomial is DEG
     // - the leading coefficient is 1
     // - the constant term is 1
     // - all the other coefficients are 0
-    let mut cyclo = vec![];
-
-    let leading_coefficient = F::from(1);
-    let leading_coefficient_assigned_value = ctx.load_witness(leading_coefficient);
-    cyclo.push(leading_coefficient_assigned_value);
-
-    for _i in 1..DEG {
-        let cyclo_val = F::from(0);
-        let cyclo_assigned_value = ctx.load_witness(cyclo_val);
-        cyclo.push(cyclo_assigned_value);
-    }
-
-    let constant_term = F::from(1);
-    let constant_term_assigned_value = ctx.load_witness(constant_term);
-    cyclo.push(constant_term_assigned_value);
+    let cyclo = cyclotomic_divisor::<DEG, F>(ctx);
 
     assert!(cyclo.len() - 1 == DEG);
 
@@ -224,7 +211,7 @@ fn bfv_encryption_circuit<F: ScalarField>(
     // Q needs to be chosen such that (Q-1) * (Q-1) * DEG < p where p is the prime field of the circuit in order to avoid overflow during the polynomial multiplication.
     // (Q-1) * (Q-1) * DEG < p according to the assumption of the circuit.
 
-    let pk0_u = poly_mul_equal_deg::<{ DEG - 1 }, F>(ctx, pk0.clone(), u.clone(), &range.gate());
+    let pk0_u = poly_mul_equal_deg::<{ DEG - 1 }, F>(ctx, &pk0, &u, range.gate());
 
     // pk0_u is a polynomial of degree (DEG - 1) * 2 = 2*DEG - 2
     // pk0_u has coefficients in the [0, (Q-1) * (Q-1) * DEG] range
@@ -336,7 +323,7 @@ fn bfv_encryption_circuit<F: ScalarField>(
     // Note that this condition is a subset of the condition (Q-1) * (Q-1) * DEG < p which is an assumption of the circuit.
 
     let pk0_u_trimmed_plus_m_delta =
-        poly_add::<{ DEG - 1 }, F>(ctx, pk0_u_trimmed, m_delta, range.gate());
+        poly_add::<{ DEG - 1 }, F>(ctx, &pk0_u_trimmed, &m_delta, range.gate());
 
     // Reduce the coefficients of `m_delta` by modulo `Q`
     // Coefficients of pk0_u_trimmed_plus_m_delta are in the [0, 2Q - 2] range
@@ -373,7 +360,7 @@ fn bfv_encryption_circuit<F: ScalarField>(
     // If the condition (Q-1) + (Q-1) < p is satisfied there is no risk of overflow during the polynomial addition.
     // Note that this condition is a subset of the condition (Q-1) * (Q-1) * DEG < p which is an assumption of the circuit.
 
-    let c0 = poly_add::<{ DEG - 1 }, F>(ctx, pk0_u_trimmed_plus_m_delta, e0, range.gate());
+    let c0 = poly_add::<{ DEG - 1 }, F>(ctx, &pk0_u_trimmed_plus_m_delta, &e0, range.gate());
 
     // The coefficients of c0 are in the range [0, 2Q - 2] according to the polynomial addition constraint set above.
     // Therefore the coefficients of c0 are known to have <= `num_bits_3` bits, therefore they satisfy the assumption of the `poly_reduce` chip
@@ -408,7 +395,7 @@ fn bfv_encryption_circuit<F: ScalarField>(
     // Q needs to be chosen such that (Q-1) * (Q-1) * DEG < p where p is the prime field of the circuit in order to avoid overflow during the polynomial multiplication.
     // (Q-1) * (Q-1) * DEG < p according to the assumption of the circuit.
 
-    let pk1_u = poly_mul_equal_deg::<{ DEG - 1 }, F>(ctx, pk1.clone(), u, range.gate());
+    let pk1_u = poly_mul_equal_deg::<{ DEG - 1 }, F>(ctx, &pk1, &u, range.gate());
 
     // pk1_u is a polynomial of degree (DEG - 1) * 2 = 2*DEG - 2
     // pk1_u has coefficients in the [0, (Q-1) * (Q-1) * DEG] range
@@ -479,7 +466,7 @@ fn bfv_encryption_circuit<F: ScalarField>(
 
     // Perform the polynomial addition between pk1_u_trimmed and e1.
 
-    let c1 = poly_add::<{ DEG - 1 }, F>(ctx, pk1_u_trimmed, e1, range.gate());
+    let c1 = poly_add::<{ DEG - 1 }, F>(ctx, &pk1_u_trimmed, &e1, range.gate());
 
     // The coefficients of c1 are in the range [0, 2Q - 2] according to the polynomial addition constraint set above.
     // Therefore the coefficients of c1 are known to have <= `num_bits_3` bits, therefore they satisfy the assumption of the `poly_reduce` chip
@@ -528,4 +515,7 @@ fn main() {
     let args = Cli::parse();
 
     run(bfv_encryption_circuit, args);
+
+    #[cfg(feature = "stats")]
+    println!("{}", zk_fhe::chips::stats::report());
 }
@@ -0,0 +1,51 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zk_fhe::chips::utils::div_euclid;
+
+// Same shape as the division performed by `poly_divide_by_cyclo` in the bfv example:
+// a product of two polynomials of degree N - 1 divided by the cyclotomic polynomial x^N + 1
+const N: usize = 8;
+const DEG_DVD: usize = 2 * N - 2;
+const DEG_DVS: usize = N;
+
+// 2^63 - 25 is prime, large enough to exercise intermediate values that overflow a single u64
+const Q: u64 = 9223372036854775783;
+
+fuzz_target!(|coeffs: [u64; DEG_DVD + 1]| {
+    // Precondition: the coefficients of the dividend are in the range [0, Q - 1]
+    let dividend = coeffs.iter().map(|&x| x % Q).collect::<Vec<u64>>();
+
+    // Precondition: the divisor is the cyclotomic polynomial x^N + 1
+    let mut divisor = vec![0u64; DEG_DVS + 1];
+    divisor[0] = 1;
+    divisor[DEG_DVS] = 1;
+
+    let (quotient, remainder) = div_euclid::<DEG_DVD, DEG_DVS, Q>(&dividend, &divisor);
+
+    // The quotient has degree DEG_DVD - DEG_DVS and the remainder has degree strictly less than DEG_DVS
+    assert_eq!(quotient.len() - 1, DEG_DVD - DEG_DVS);
+    assert!(remainder.len() <= DEG_DVS);
+
+    // The coefficients of the quotient and of the remainder are in the range [0, Q - 1]
+    assert!(quotient.iter().all(|&x| x < Q));
+    assert!(remainder.iter().all(|&x| x < Q));
+
+    // quotient * divisor + remainder == dividend mod Q
+    // Coefficients are parsed as [a_DEG, ..., a_0], therefore the remainder is aligned to the end of the product
+    let q = Q as u128;
+    let mut reconstructed = vec![0u128; DEG_DVD + 1];
+    for (i, &quot_coeff) in quotient.iter().enumerate() {
+        for (j, &dvs_coeff) in divisor.iter().enumerate() {
+            reconstructed[i + j] =
+                (reconstructed[i + j] + quot_coeff as u128 * dvs_coeff as u128) % q;
+        }
+    }
+    let offset = DEG_DVD + 1 - remainder.len();
+    for (i, &rem_coeff) in remainder.iter().enumerate() {
+        reconstructed[offset + i] = (reconstructed[offset + i] + rem_coeff as u128) % q;
+    }
+
+    let dividend = dividend.iter().map(|&x| x as u128).collect::<Vec<u128>>();
+    assert_eq!(reconstructed, dividend);
+});
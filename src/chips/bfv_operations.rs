@@ -0,0 +1,1572 @@
+use crate::chips::labels;
+use crate::chips::params::delta;
+use crate::chips::poly_distribution::{
+    check_poly_from_distribution_chi_error, check_poly_from_distribution_chi_key,
+};
+use crate::chips::poly_operations::{
+    poly_add, poly_add_mod_reduced, poly_assert_equal, poly_automorphism, poly_base_decompose,
+    poly_div_round, poly_dot, poly_eval_at_constant, poly_inf_norm, poly_is_zero, poly_mul_mod,
+    poly_reduce, poly_reduce_once, poly_reduce_rns, poly_scalar_mul,
+};
+use crate::chips::utils::{fits_in_field, mod_inverse, pow_mod};
+use halo2_base::gates::GateChip;
+use halo2_base::gates::GateInstructions;
+use halo2_base::poseidon::PoseidonChip;
+use halo2_base::safe_types::RangeChip;
+use halo2_base::safe_types::RangeInstructions;
+use halo2_base::utils::ScalarField;
+use halo2_base::AssignedValue;
+use halo2_base::Context;
+use halo2_base::QuantumCell::Constant;
+
+/// BFV ciphertext (c0, c1) of the ring R_q = Z_Q[x]/(x^(DEG+1) + 1)
+///
+/// * The ciphertext decrypts as c0 + c1 * s = Δm + e
+/// * `c0` and `c1` are parsed as vectors of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * The coefficients are in the range [0, Q - 1]
+#[derive(Clone, Debug)]
+pub struct Ciphertext<F: ScalarField> {
+    pub c0: Vec<AssignedValue<F>>,
+    pub c1: Vec<AssignedValue<F>>,
+}
+
+impl<F: ScalarField> Ciphertext<F> {
+    /// Build the homomorphic addition of the ciphertexts self and other, namely the component-wise sum reduced modulo Q
+    ///
+    /// * If self encrypts m1 and other encrypts m2, the output encrypts m1 + m2 mod T. The noise of the output is the sum of the noises
+    /// * DEG is the degree of the ciphertext polynomials
+    /// * Assumes that the coefficients of both ciphertexts are in the range [0, Q - 1]
+    pub fn bfv_add<const DEG: usize, const Q: u64>(
+        &self,
+        ctx: &mut Context<F>,
+        other: &Ciphertext<F>,
+        range: &RangeChip<F>,
+    ) -> Ciphertext<F> {
+        // Both ciphertexts are reduced, which satisfies the precondition of the `poly_add_mod_reduced` chip
+        let c0 = poly_add_mod_reduced::<DEG, Q, F>(ctx, &self.c0, &other.c0, range);
+        let c1 = poly_add_mod_reduced::<DEG, Q, F>(ctx, &self.c1, &other.c1, range);
+
+        Ciphertext { c0, c1 }
+    }
+
+    /// Build the homomorphic subtraction of the ciphertexts self and other, namely the component-wise difference reduced modulo Q
+    ///
+    /// * If self encrypts m1 and other encrypts m2, the output encrypts m1 - m2 mod T. The noise of the output is the difference of the noises
+    /// * DEG is the degree of the ciphertext polynomials
+    /// * Assumes that the coefficients of both ciphertexts are in the range [0, Q - 1]
+    pub fn bfv_sub<const DEG: usize, const Q: u64>(
+        &self,
+        ctx: &mut Context<F>,
+        other: &Ciphertext<F>,
+        range: &RangeChip<F>,
+    ) -> Ciphertext<F> {
+        let c0 = poly_sub_mod::<DEG, Q, F>(ctx, &self.c0, &other.c0, range);
+        let c1 = poly_sub_mod::<DEG, Q, F>(ctx, &self.c1, &other.c1, range);
+
+        Ciphertext { c0, c1 }
+    }
+}
+
+/// Build a - b with coefficients reduced modulo Q
+///
+/// * a - b is computed as a + Q - b, whose coefficients are in the range [1, 2Q - 1], which satisfies the precondition of the `poly_reduce_once` chip
+/// * Assumes that the coefficients of a and b are in the range [0, Q - 1]
+fn poly_sub_mod<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the input polynomials have degree DEG
+    assert_eq!(a.len() - 1, DEG);
+    assert_eq!(b.len() - 1, DEG);
+
+    let mut diff = vec![];
+
+    for i in 0..=DEG {
+        let shifted = range.gate().add(ctx, a[i], Constant(F::from(Q)));
+        diff.push(range.gate().sub(ctx, shifted, b[i]));
+    }
+
+    poly_reduce_once::<DEG, Q, F>(ctx, diff, range)
+}
+
+/// Switch a ciphertext (c0, c1) decryptable under the secret key s1 into a ciphertext (c0', c1') decryptable under the secret key s2
+///
+/// * DEG is the degree of the ciphertext polynomials. The ring is R_q = Z_Q[x]/(x^(DEG+1) + 1)
+/// * T is the base of the gadget decomposition and L the number of digits. Assumes that T^L >= Q
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * The ciphertext decrypts as c0 + c1 * s1 = Δm + e
+/// * The key-switching key is given as two vectors of L polynomials `ksk0` and `ksk1` such that for each j in [0, L):
+///     - ksk1[j] = a_j where a_j is sampled uniformly from R_q
+///     - ksk0[j] = -(a_j * s2 + e_j) + T^j * s1 where e_j is sampled from the distribution ChiError
+/// * The output is computed as c0' = c0 + sum_j d_j * ksk0[j] and c1' = sum_j d_j * ksk1[j] where d_j are the base T digits of c1
+/// * It follows that c0' + c1' * s2 = c0 + c1 * s1 - sum_j d_j * e_j
+/// * Assumes that the coefficients of c0, c1, ksk0[j] and ksk1[j] are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p and (Q-1) * L < p where p is the prime field of the circuit
+pub fn bfv_key_switch<
+    const DEG: usize,
+    const Q: u64,
+    const T: u64,
+    const L: usize,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    c0: Vec<AssignedValue<F>>,
+    c1: Vec<AssignedValue<F>>,
+    ksk0: Vec<Vec<AssignedValue<F>>>,
+    ksk1: Vec<Vec<AssignedValue<F>>>,
+    range: &RangeChip<F>,
+) -> (Vec<AssignedValue<F>>, Vec<AssignedValue<F>>) {
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
+
+    // assert that the ciphertext polynomials have degree DEG
+    assert_eq!(c0.len() - 1, DEG);
+    assert_eq!(c1.len() - 1, DEG);
+
+    // assert that the key-switching key is made of L pairs of polynomials of degree DEG
+    assert_eq!(ksk0.len(), L);
+    assert_eq!(ksk1.len(), L);
+    for j in 0..L {
+        assert_eq!(ksk0[j].len() - 1, DEG);
+        assert_eq!(ksk1[j].len() - 1, DEG);
+    }
+
+    // Decompose c1 into L digit polynomials with coefficients in the range [0, T - 1]
+    // The coefficients of c1 are in the range [0, Q - 1] by assumption, which satisfies the assumption of the `poly_base_decompose` chip
+    let digits = poly_base_decompose::<DEG, Q, T, L, F>(ctx, c1, range);
+
+    // The coefficients of the digits are in the range [0, T - 1] and T <= Q since T^L >= Q
+    // Therefore the inputs satisfy the assumption of the `poly_dot` chip
+    let d_ksk0 = poly_dot::<DEG, Q, F>(ctx, digits.clone(), ksk0, range);
+    let d_ksk1 = poly_dot::<DEG, Q, F>(ctx, digits, ksk1, range);
+
+    // COEFFICIENTS OVERFLOW ANALYSIS
+    // The coefficients of c0 are in the range [0, Q - 1] by assumption
+    // The coefficients of d_ksk0 are in the range [0, Q - 1] according to the logic of the `poly_dot` chip
+    // Therefore the coefficients of c0 + d_ksk0 are in the range [0, 2Q - 2]
+    let c0_prime = poly_add::<DEG, F>(ctx, &c0, &d_ksk0, range.gate());
+
+    // get the number of bits needed to represent the value of 2Q - 2
+    // The bound is computed on u128 so that it cannot wrap around for Q above 2^63
+    let binary_representation = format!("{:b}", (2 * Q as u128 - 2));
+    let num_bits = binary_representation.len();
+
+    let c0_prime = poly_reduce::<DEG, Q, F>(ctx, c0_prime, range, num_bits);
+
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("bfv_key_switch", ctx, stats_start);
+
+    (c0_prime, d_ksk1)
+}
+
+/// Rotate the plaintext slots of a ciphertext (c0, c1) by `steps` positions
+///
+/// * DEG is the degree of the ciphertext polynomials. The ring is R_q = Z_Q[x]/(x^(DEG+1) + 1) and DEG + 1 must be a power of 2
+/// * T is the base of the gadget decomposition and L the number of digits used by the key switching. Assumes that T^L >= Q
+/// * Slot layout: the plaintext modulus t is assumed to satisfy t = 1 mod 2(DEG+1), so that the plaintext ring splits into DEG+1 slots.
+///   The slots are arranged as a 2 x (DEG+1)/2 matrix, where the slot (i, j) holds the evaluation of the plaintext at the root psi^((-1)^i * 3^j)
+/// * Applying the automorphism x -> x^(3^steps mod 2(DEG+1)) rotates each row of the matrix cyclically by `steps` positions to the left
+/// * The automorphism turns a ciphertext decryptable under s into a ciphertext decryptable under s(x^(3^steps)).
+///   It is switched back to s with the rotation key `rtk0`, `rtk1`, namely the key-switching key of the `bfv_key_switch` chip from s1 = s(x^(3^steps)) to s2 = s
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of c0, c1, rtk0[j] and rtk1[j] are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p and (Q-1) * L < p where p is the prime field of the circuit
+pub fn slot_rotate<const DEG: usize, const Q: u64, const T: u64, const L: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    c0: Vec<AssignedValue<F>>,
+    c1: Vec<AssignedValue<F>>,
+    steps: usize,
+    rtk0: Vec<Vec<AssignedValue<F>>>,
+    rtk1: Vec<Vec<AssignedValue<F>>>,
+    range: &RangeChip<F>,
+) -> (Vec<AssignedValue<F>>, Vec<AssignedValue<F>>) {
+    let n = DEG + 1;
+
+    // The Galois element corresponding to a rotation by `steps` positions is 3^steps mod 2(DEG+1)
+    let galois_elt = pow_mod(3, steps as u64, 2 * n as u64) as usize;
+
+    // The coefficients of c0 and c1 are in the range [0, Q - 1] by assumption
+    // Therefore the coefficients of the rotated polynomials are in the range [0, Q - 1] according to the logic of the `poly_automorphism` chip
+    let c0_rot = poly_automorphism::<DEG, Q, F>(ctx, c0, galois_elt, range.gate());
+    let c1_rot = poly_automorphism::<DEG, Q, F>(ctx, c1, galois_elt, range.gate());
+
+    // The rotated ciphertext satisfies the assumptions of the `bfv_key_switch` chip
+    bfv_key_switch::<DEG, Q, T, L, F>(ctx, c0_rot, c1_rot, rtk0, rtk1, range)
+}
+
+/// Enforce that `rotated` is the rotation of the plaintext slots of the ciphertext ct by `steps` positions under the rotation key `rtk0`, `rtk1`
+///
+/// * The rotation is recomputed inside the circuit with the `slot_rotate` chip, namely `poly_automorphism` followed by `bfv_key_switch`, and compared to `rotated` coefficient by coefficient
+/// * This is meant to debug rotation keys: the coefficients are labelled "rotated.c0", "rotated.c1", "rotated_expected.c0" and "rotated_expected.c1", therefore with the `debug` feature a mismatch panics with the labels and the index of the first differing coefficient
+/// * See `slot_rotate` for the slot layout and the format of the rotation key
+/// * DEG is the degree of the ciphertext polynomials. The ring is R_q = Z_Q[x]/(x^(DEG+1) + 1) and DEG + 1 must be a power of 2
+/// * T is the base of the gadget decomposition and L the number of digits used by the key switching. Assumes that T^L >= Q
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of ct, rotated, rtk0[j] and rtk1[j] are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p and (Q-1) * L < p where p is the prime field of the circuit
+#[allow(clippy::too_many_arguments)]
+pub fn bfv_verify_rotation<
+    const DEG: usize,
+    const Q: u64,
+    const T: u64,
+    const L: usize,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    ct: &Ciphertext<F>,
+    rotated: &Ciphertext<F>,
+    steps: usize,
+    rtk0: Vec<Vec<AssignedValue<F>>>,
+    rtk1: Vec<Vec<AssignedValue<F>>>,
+    range: &RangeChip<F>,
+) {
+    // assert that the rotated ciphertext polynomials have degree DEG
+    for poly in [&rotated.c0, &rotated.c1] {
+        assert_eq!(poly.len() - 1, DEG);
+    }
+
+    let (c0_expected, c1_expected) =
+        slot_rotate::<DEG, Q, T, L, F>(ctx, ct.c0.clone(), ct.c1.clone(), steps, rtk0, rtk1, range);
+
+    labels::label_poly(&rotated.c0, "rotated.c0");
+    labels::label_poly(&rotated.c1, "rotated.c1");
+    labels::label_poly(&c0_expected, "rotated_expected.c0");
+    labels::label_poly(&c1_expected, "rotated_expected.c1");
+
+    // Enforce that the claimed rotation is equal to the rotation computed inside the circuit
+    for i in 0..=DEG {
+        labels::constrain_equal(ctx, &rotated.c0[i], &c0_expected[i]);
+        labels::constrain_equal(ctx, &rotated.c1[i], &c1_expected[i]);
+    }
+}
+
+/// Enforce that m lives in R_t and return Δ * m, where Δ = Q/T rounded to the lower integer
+///
+/// * This is the encoding of the plaintext shared by the public-key and the symmetric encryption
+/// * The coefficients of the output are in the range [0, (T-1) * Δ], which is a subset of [0, Q - Δ]
+fn bfv_scale_plaintext<const DEG: usize, const Q: u64, const T: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    m: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // Enforce that m lives in R_t
+    for coeff in &m {
+        range.check_less_than_safe(ctx, *coeff, T);
+    }
+
+    // Δ = Q/T rounded to the lower integer
+    let delta = delta::<Q, T>();
+
+    // COEFFICIENTS OVERFLOW ANALYSIS
+    // The coefficients of m are in the range [0, T - 1], therefore the coefficients of m_delta are in the range [0, (T-1) * Δ] which is a subset of [0, Q - 1]
+    poly_scalar_mul::<DEG, F>(ctx, m, Constant(F::from(delta)), range.gate())
+}
+
+/// Build the BFV public-key encryption (c0, c1) of the message m under the public key (pk0, pk1) with the randomness u, e0, e1
+///
+/// * c0 = pk0 * u + Δ * m + e0 and c1 = pk1 * u + e1 in R_q, where Δ = Q/T rounded to the lower integer
+/// * Since pk0 = -(a * s + e) and pk1 = a, the ciphertext decrypts as c0 + c1 * s = Δ * m + e0 + e1 * s - e * u
+/// * DEG is the degree of the polynomials. The ring is R_q = Z_Q[x]/(x^(DEG+1) + 1) and DEG + 1 must be a power of 2
+/// * T is the plaintext modulus and B the bound of the distribution ChiError
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Enforces that:
+///     - u is sampled from the distribution ChiKey, namely its coefficients are in [0, 1, Q-1]
+///     - e0 and e1 are sampled from the distribution ChiError, namely their coefficients are in the range [0, B] OR [Q-B, Q-1]
+///     - m lives in R_t, namely its coefficients are in the range [0, T - 1]
+/// * Returns (c0, c1) with coefficients in the range [0, Q - 1]
+/// * Assumes that the coefficients of pk0 and pk1 are in the range [0, Q - 1]
+/// * Assumes that B < Q and T < Q
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+#[allow(clippy::too_many_arguments)]
+pub fn bfv_encrypt<const DEG: usize, const Q: u64, const T: u64, const B: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    pk0: Vec<AssignedValue<F>>,
+    pk1: Vec<AssignedValue<F>>,
+    m: Vec<AssignedValue<F>>,
+    u: Vec<AssignedValue<F>>,
+    e0: Vec<AssignedValue<F>>,
+    e1: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> (Vec<AssignedValue<F>>, Vec<AssignedValue<F>>) {
+    // assert that the input polynomials have degree DEG
+    for poly in [&pk0, &pk1, &m, &u, &e0, &e1] {
+        assert_eq!(poly.len() - 1, DEG);
+    }
+
+    // Enforce that the randomness is sampled from the right distributions
+    // The coefficients of u, e0 and e1 are therefore in the range [0, Q - 1]
+    check_poly_from_distribution_chi_key::<DEG, Q, F>(ctx, u.clone(), range.gate());
+    check_poly_from_distribution_chi_error::<DEG, Q, B, F>(ctx, e0.clone(), range);
+    check_poly_from_distribution_chi_error::<DEG, Q, B, F>(ctx, e1.clone(), range);
+
+    let m_delta = bfv_scale_plaintext::<DEG, Q, T, F>(ctx, m, range);
+
+    // The coefficients of pk0, pk1 and u are in the range [0, Q - 1], which satisfies the assumption of the `poly_mul_mod` chip
+    // The coefficients of pk0_u and pk1_u are in the range [0, Q - 1]
+    let pk0_u = poly_mul_mod::<DEG, Q, F>(ctx, pk0, u.clone(), range);
+    let pk1_u = poly_mul_mod::<DEG, Q, F>(ctx, pk1, u, range);
+
+    // The coefficients of pk0_u + m_delta + e0 are in the range [0, 3Q - 3]
+    let c0 = poly_add::<DEG, F>(ctx, &pk0_u, &m_delta, range.gate());
+    let c0 = poly_add::<DEG, F>(ctx, &c0, &e0, range.gate());
+
+    // get the number of bits needed to represent the value of 3Q - 3
+    // The bound is computed on u128 so that it cannot wrap around for Q above 2^62
+    let binary_representation = format!("{:b}", (3 * Q as u128 - 3));
+    let num_bits = binary_representation.len();
+
+    let c0 = poly_reduce::<DEG, Q, F>(ctx, c0, range, num_bits);
+
+    // The coefficients of pk1_u + e1 are in the range [0, 2Q - 2], which satisfies the precondition of the `poly_reduce_once` chip
+    let c1 = poly_add::<DEG, F>(ctx, &pk1_u, &e1, range.gate());
+    let c1 = poly_reduce_once::<DEG, Q, F>(ctx, c1, range);
+
+    (c0, c1)
+}
+
+/// Build the BFV symmetric (secret-key) encryption (c0, c1) of the message m under the secret key s with the randomness a, e
+///
+/// * c0 = -(a * s + e) + Δ * m and c1 = a in R_q, where Δ = Q/T rounded to the lower integer
+/// * Compared to `bfv_encrypt`, the mask a * s + e is computed from the secret key instead of the public key, and no ephemeral key u is needed. The ciphertext decrypts as c0 + c1 * s = Δ * m - e
+/// * a is sampled uniformly from R_q, it is not constrained by the chip
+/// * DEG is the degree of the polynomials. The ring is R_q = Z_Q[x]/(x^(DEG+1) + 1) and DEG + 1 must be a power of 2
+/// * T is the plaintext modulus and B the bound of the distribution ChiError
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Enforces that:
+///     - s is sampled from the distribution ChiKey, namely its coefficients are in [0, 1, Q-1]
+///     - e is sampled from the distribution ChiError, namely its coefficients are in the range [0, B] OR [Q-B, Q-1]
+///     - m lives in R_t, namely its coefficients are in the range [0, T - 1]
+/// * Returns (c0, c1) with coefficients in the range [0, Q - 1]
+/// * Assumes that the coefficients of a are in the range [0, Q - 1]
+/// * Assumes that B < Q and T < Q
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn bfv_encrypt_symmetric<
+    const DEG: usize,
+    const Q: u64,
+    const T: u64,
+    const B: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    s: Vec<AssignedValue<F>>,
+    m: Vec<AssignedValue<F>>,
+    e: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> (Vec<AssignedValue<F>>, Vec<AssignedValue<F>>) {
+    // assert that the input polynomials have degree DEG
+    for poly in [&a, &s, &m, &e] {
+        assert_eq!(poly.len() - 1, DEG);
+    }
+
+    // Enforce that the secret key and the error are sampled from the right distributions
+    // The coefficients of s and e are therefore in the range [0, Q - 1]
+    check_poly_from_distribution_chi_key::<DEG, Q, F>(ctx, s.clone(), range.gate());
+    check_poly_from_distribution_chi_error::<DEG, Q, B, F>(ctx, e.clone(), range);
+
+    let m_delta = bfv_scale_plaintext::<DEG, Q, T, F>(ctx, m, range);
+
+    // The coefficients of a and s are in the range [0, Q - 1], which satisfies the assumption of the `poly_mul_mod` chip
+    // The coefficients of a_s are in the range [0, Q - 1]
+    let a_s = poly_mul_mod::<DEG, Q, F>(ctx, a.clone(), s, range);
+
+    // COEFFICIENTS OVERFLOW ANALYSIS
+    // -(a_s + e) is computed as 2Q - a_s - e, which is congruent modulo Q and in the range [2, 2Q]
+    // The coefficients of m_delta are in the range [0, Q - Δ], therefore the coefficients of m_delta + 2Q - a_s - e are in the range [2, 3Q - Δ] which is a subset of [0, 3Q - 1]
+    let mut c0 = vec![];
+
+    // get the number of bits needed to represent the value of 3Q - 1
+    // The bound is computed on u128 so that it cannot wrap around for Q above 2^62
+    let binary_representation = format!("{:b}", (3 * Q as u128 - 1));
+    let num_bits = binary_representation.len();
+
+    for i in 0..=DEG {
+        let shifted = range
+            .gate()
+            .add(ctx, m_delta[i], Constant(F::from(Q) * F::from(2)));
+        let diff = range.gate().sub(ctx, shifted, a_s[i]);
+        let diff = range.gate().sub(ctx, diff, e[i]);
+        c0.push(diff);
+    }
+
+    let c0 = poly_reduce::<DEG, Q, F>(ctx, c0, range, num_bits);
+
+    (c0, a)
+}
+
+/// Enforce that (c0, c1) is a well-formed BFV encryption under the public key (pk0, pk1) of some message m with some bounded randomness u, e0, e1
+///
+/// * m, u, e0 and e1 are private witnesses. The chip does not reveal them, it only proves that they exist and are well-formed
+/// * The encryption is computed inside the circuit with the `bfv_encrypt` chip and compared to (c0, c1)
+/// * DEG is the degree of the polynomials. The ring is R_q = Z_Q[x]/(x^(DEG+1) + 1) and DEG + 1 must be a power of 2
+/// * T is the plaintext modulus and B the bound of the distribution ChiError
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Enforces that:
+///     - u is sampled from the distribution ChiKey, namely its coefficients are in [0, 1, Q-1]
+///     - e0 and e1 are sampled from the distribution ChiError, namely their coefficients are in the range [0, B] OR [Q-B, Q-1]
+///     - m lives in R_t, namely its coefficients are in the range [0, T - 1]
+///     - c0 = pk0 * u + Δ * m + e0 and c1 = pk1 * u + e1 in R_q, where Δ = Q/T rounded to the lower integer
+/// * Assumes that the coefficients of pk0, pk1, c0 and c1 are in the range [0, Q - 1]. They are meant to be public inputs of the circuit
+/// * Assumes that B < Q and T < Q
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+#[allow(clippy::too_many_arguments)]
+pub fn bfv_ciphertext_valid<
+    const DEG: usize,
+    const Q: u64,
+    const T: u64,
+    const B: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    pk0: Vec<AssignedValue<F>>,
+    pk1: Vec<AssignedValue<F>>,
+    c0: Vec<AssignedValue<F>>,
+    c1: Vec<AssignedValue<F>>,
+    m: Vec<AssignedValue<F>>,
+    u: Vec<AssignedValue<F>>,
+    e0: Vec<AssignedValue<F>>,
+    e1: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) {
+    // assert that the ciphertext polynomials have degree DEG
+    for poly in [&c0, &c1] {
+        assert_eq!(poly.len() - 1, DEG);
+    }
+
+    let (c0_expected, c1_expected) =
+        bfv_encrypt::<DEG, Q, T, B, F>(ctx, pk0, pk1, m, u, e0, e1, range);
+
+    labels::label_poly(&c0, "c0");
+    labels::label_poly(&c1, "c1");
+    labels::label_poly(&c0_expected, "c0_expected");
+    labels::label_poly(&c1_expected, "c1_expected");
+
+    // Enforce that the ciphertext is equal to the encryption computed inside the circuit
+    for i in 0..=DEG {
+        labels::constrain_equal(ctx, &c0[i], &c0_expected[i]);
+        labels::constrain_equal(ctx, &c1[i], &c1_expected[i]);
+    }
+}
+
+/// Enforce that the noise e of a ciphertext is small enough for the ciphertext to decrypt correctly, and return its infinity norm
+///
+/// * A BFV ciphertext decrypts as c0 + c1 * s = Δm + e in R_q, where Δ = Q/T. Decryption rounds (T/Q) * (Δm + e), which recovers m if and only if the noise satisfies ||e||_∞ < Q/(2T)
+/// * The chip computes ||e||_∞ with the `poly_inf_norm` chip and enforces ||e||_∞ * 2T < Q, namely ||e||_∞ <= (Q-1)/(2T)
+/// * e is meant to be a private witness, e.g. computed outside the circuit after a chain of homomorphic operations
+/// * DEG is the degree of the noise polynomial and T is the plaintext modulus
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of e are in the range [0, Q - 1], where c represents c - Q when c > Q/2
+/// * Assumes that 2T <= Q
+pub fn noise_budget<const DEG: usize, const Q: u64, const T: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    e: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> AssignedValue<F> {
+    assert!(2 * T <= Q);
+
+    // The infinity norm is in the range [0, Q/2]
+    let norm = poly_inf_norm::<DEG, Q, F>(ctx, e, range);
+
+    // ||e||_∞ < Q/(2T) is equivalent to ||e||_∞ < (Q-1)/(2T) + 1 on integers
+    let bound = (Q - 1) / (2 * T) + 1;
+    range.check_less_than_safe(ctx, norm, bound);
+
+    norm
+}
+
+/// Enforce that the ciphertext ct decrypts to the message m under the secret key s, and return m
+///
+/// * m and e are private witnesses computed outside the circuit, e.g. m is the output of the decryption and e = c0 + c1 * s - Δ * m in centered representation
+/// * Enforces that:
+///     - s is sampled from the distribution ChiKey, namely its coefficients are in [0, 1, Q-1]
+///     - m lives in R_t, namely its coefficients are in the range [0, T - 1]
+///     - the coefficients of e are in the range [0, Q - 1], where c represents c - Q when c > Q/2, and ||e||_∞ < Δ/2
+///     - c0 + c1 * s = Δ * m + e in R_q, where Δ = Q/T rounded to the lower integer
+/// * Since two messages m != m' satisfy ||Δ * (m - m')||_∞ >= Δ in R_q, the bound ||e||_∞ < Δ/2 makes m uniquely determined by ct and s
+/// * DEG is the degree of the polynomials. The ring is R_q = Z_Q[x]/(x^(DEG+1) + 1) and DEG + 1 must be a power of 2
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of c0 and c1 are in the range [0, Q - 1]
+/// * Assumes that 2 <= T < Q and that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn bfv_decrypt_check<const DEG: usize, const Q: u64, const T: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    ct: &Ciphertext<F>,
+    s: Vec<AssignedValue<F>>,
+    m: Vec<AssignedValue<F>>,
+    e: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the input polynomials have degree DEG
+    for poly in [&ct.c0, &ct.c1, &s, &m, &e] {
+        assert_eq!(poly.len() - 1, DEG);
+    }
+
+    // Enforce that the secret key is sampled from the right distribution
+    // The coefficients of s are therefore in the range [0, Q - 1]
+    check_poly_from_distribution_chi_key::<DEG, Q, F>(ctx, s.clone(), range.gate());
+
+    // Enforce that the coefficients of e are in the range [0, Q - 1], which satisfies the assumption of the `poly_inf_norm` chip
+    for coeff in &e {
+        range.check_less_than_safe(ctx, *coeff, Q);
+    }
+
+    // ||e||_∞ < Δ/2 is equivalent to ||e||_∞ < (Δ-1)/2 + 1 on integers
+    let delta = delta::<Q, T>();
+    let norm = poly_inf_norm::<DEG, Q, F>(ctx, e.clone(), range);
+    range.check_less_than_safe(ctx, norm, (delta - 1) / 2 + 1);
+
+    // Enforce that m lives in R_t. The coefficients of m_delta are in the range [0, Q - Δ]
+    let m_delta = bfv_scale_plaintext::<DEG, Q, T, F>(ctx, m.clone(), range);
+
+    // The coefficients of c1 and s are in the range [0, Q - 1], which satisfies the assumption of the `poly_mul_mod` chip
+    let c1_s = poly_mul_mod::<DEG, Q, F>(ctx, ct.c1.clone(), s, range);
+
+    // Both sides are sums of reduced polynomials, which satisfies the precondition of the `poly_add_mod_reduced` chip
+    let lhs = poly_add_mod_reduced::<DEG, Q, F>(ctx, &ct.c0, &c1_s, range);
+    let rhs = poly_add_mod_reduced::<DEG, Q, F>(ctx, &m_delta, &e, range);
+
+    // Both sides are reduced, therefore they are equal if and only if they are congruent modulo Q
+    poly_assert_equal::<DEG, F>(ctx, &lhs, &rhs);
+
+    m
+}
+
+/// Enforce that the ciphertexts ct_a and ct_b, possibly encrypted under different secret keys, encrypt the same plaintext
+///
+/// * Each ciphertext is decrypted with the `bfv_decrypt_check` chip, with its secret key s, message m and noise e as private witnesses. The plaintexts are then constrained to be equal with `poly_assert_equal`
+/// * The plaintext is not revealed: only its equality between the two ciphertexts is proven
+/// * DEG is the degree of the polynomials. The ring is R_q = Z_Q[x]/(x^(DEG+1) + 1) and DEG + 1 must be a power of 2
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the ciphertexts are in the range [0, Q - 1]
+/// * Assumes that 2 <= T < Q and that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+#[allow(clippy::too_many_arguments)]
+pub fn bfv_plaintext_equal<const DEG: usize, const Q: u64, const T: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    ct_a: &Ciphertext<F>,
+    ct_b: &Ciphertext<F>,
+    s_a: Vec<AssignedValue<F>>,
+    s_b: Vec<AssignedValue<F>>,
+    m_a: Vec<AssignedValue<F>>,
+    m_b: Vec<AssignedValue<F>>,
+    e_a: Vec<AssignedValue<F>>,
+    e_b: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) {
+    let m_a = bfv_decrypt_check::<DEG, Q, T, F>(ctx, ct_a, s_a, m_a, e_a, range);
+    let m_b = bfv_decrypt_check::<DEG, Q, T, F>(ctx, ct_b, s_b, m_b, e_b, range);
+
+    // The plaintexts are in the range [0, T - 1], therefore they are equal if and only if they encrypt the same message
+    poly_assert_equal::<DEG, F>(ctx, &m_a, &m_b);
+}
+
+/// Enforce that the ciphertext ct is an encryption of zero under the secret key s
+///
+/// * The ciphertext is decrypted with the `bfv_decrypt_check` chip, with s, the message m and the noise e as private witnesses, and the plaintext is enforced to be the zero polynomial with `poly_is_zero`
+/// * Compared to `bfv_plaintext_equal`, the plaintext is compared to the constant zero instead of the plaintext of another ciphertext
+/// * DEG is the degree of the polynomials. The ring is R_q = Z_Q[x]/(x^(DEG+1) + 1) and DEG + 1 must be a power of 2
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the ciphertext are in the range [0, Q - 1]
+/// * Assumes that 2 <= T < Q and that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn bfv_is_zero_ciphertext<const DEG: usize, const Q: u64, const T: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    ct: &Ciphertext<F>,
+    s: Vec<AssignedValue<F>>,
+    m: Vec<AssignedValue<F>>,
+    e: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) {
+    let m = bfv_decrypt_check::<DEG, Q, T, F>(ctx, ct, s, m, e, range);
+
+    // The plaintext is in the range [0, T - 1], therefore it is the zero polynomial if and only if all its coefficients are zero in the field
+    let is_zero = poly_is_zero::<DEG, F>(ctx, &m, range.gate());
+    range.gate().assert_is_const(ctx, &is_zero, &F::one());
+}
+
+/// Blind (re-randomize) the ciphertext ct by adding the encryption of zero zero_ct, and return the blinded ciphertext
+///
+/// * The blinded ciphertext is ct + zero_ct computed with `Ciphertext::bfv_add`, which hides the operations that produced ct
+/// * zero_ct is enforced to be an encryption of zero under the secret key s with the `bfv_is_zero_ciphertext` chip, with s and the noise e_zero as private witnesses.
+///   Therefore the blinded ciphertext encrypts the same plaintext as ct: if ct decrypts as Δm + e, the blinded ciphertext decrypts as Δm + e + e_zero
+/// * The noise grows by e_zero, with ||e_zero||_∞ < Δ/2. The decryption of the blinded ciphertext can be checked with `bfv_decrypt_check` or `noise_budget`
+/// * DEG is the degree of the polynomials. The ring is R_q = Z_Q[x]/(x^(DEG+1) + 1) and DEG + 1 must be a power of 2
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of ct and zero_ct are in the range [0, Q - 1]
+/// * Assumes that 2 <= T < Q and that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn bfv_blind<const DEG: usize, const Q: u64, const T: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    ct: &Ciphertext<F>,
+    zero_ct: &Ciphertext<F>,
+    s: Vec<AssignedValue<F>>,
+    e_zero: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Ciphertext<F> {
+    // The message of zero_ct is enforced to be the zero polynomial by the `bfv_is_zero_ciphertext` chip
+    let m_zero = (0..=DEG)
+        .map(|_| ctx.load_witness(F::zero()))
+        .collect::<Vec<_>>();
+    bfv_is_zero_ciphertext::<DEG, Q, T, F>(ctx, zero_ct, s, m_zero, e_zero, range);
+
+    // Both ciphertexts are reduced, which satisfies the assumption of `bfv_add`
+    ct.bfv_add::<DEG, Q>(ctx, zero_ct, range)
+}
+
+/// Enforce that (a, b) is a valid RLWE sample for the secret s and the error e, namely b = a * s + e in R_q = Z_Q[x]/(x^(DEG+1) + 1)
+///
+/// * This is the building block underpinning the public key of BFV/BGV, e.g. (pk1, pk0) = (a, -(a * s + e)), and is reusable across schemes
+/// * s is enforced to be sampled from the distribution chi key (ternary) and e from the distribution chi error (bounded by B)
+/// * DEG is the degree of the polynomials. DEG + 1 must be a power of 2
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of a and b are in the range [0, Q - 1]
+/// * Assumes that B < Q and that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn rlwe_sample_check<const DEG: usize, const Q: u64, const B: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    b: Vec<AssignedValue<F>>,
+    s: Vec<AssignedValue<F>>,
+    e: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) {
+    // assert that the input polynomials have degree DEG
+    for poly in [&a, &b, &s, &e] {
+        assert_eq!(poly.len() - 1, DEG);
+    }
+
+    // Enforce that the secret and the error are sampled from the right distributions
+    // The coefficients of s and e are therefore in the range [0, Q - 1]
+    check_poly_from_distribution_chi_key::<DEG, Q, F>(ctx, s.clone(), range.gate());
+    check_poly_from_distribution_chi_error::<DEG, Q, B, F>(ctx, e.clone(), range);
+
+    // The coefficients of a and s are in the range [0, Q - 1], which satisfies the assumption of the `poly_mul_mod` chip
+    // The coefficients of a_s are in the range [0, Q - 1]
+    let a_s = poly_mul_mod::<DEG, Q, F>(ctx, a, s, range);
+
+    // The coefficients of a_s + e are in the range [0, 2Q - 2], which satisfies the precondition of the `poly_reduce_once` chip
+    let b_expected = poly_add::<DEG, F>(ctx, &a_s, &e, range.gate());
+    let b_expected = poly_reduce_once::<DEG, Q, F>(ctx, b_expected, range);
+
+    // Enforce that b is equal to a * s + e computed inside the circuit
+    for i in 0..=DEG {
+        ctx.constrain_equal(&b[i], &b_expected[i]);
+    }
+}
+
+/// Width of the Poseidon permutation used by `commit_randomness` and `verify_poly_opening`
+const POSEIDON_T: usize = 3;
+/// Rate of the Poseidon sponge used by `commit_randomness` and `verify_poly_opening`
+const POSEIDON_RATE: usize = 2;
+/// Number of full rounds of the Poseidon permutation used by `commit_randomness` and `verify_poly_opening`
+const POSEIDON_R_F: usize = 8;
+/// Number of partial rounds of the Poseidon permutation used by `commit_randomness` and `verify_poly_opening`
+const POSEIDON_R_P: usize = 57;
+
+/// Commit to the encryption randomness u with a Poseidon hash and expose the commitment as a public input
+///
+/// * DEG is the degree of u. The ring is R_q = Z_Q[x]/(x^(DEG+1) + 1)
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Hashing convention: a fresh Poseidon sponge of width 3 and rate 2 (8 full rounds, 57 partial rounds) absorbs the DEG + 1 coefficients of u in the order [u_DEG, u_DEG-1, ..., u_0], then a single field element is squeezed
+/// * The commitment is pushed to `make_public` and returned
+/// * The binding to the encryption relies on copy constraints: `u` must be the same assigned cells that are used to compute the ciphertext, e.g. in `poly_mul_equal_deg(pk0, u)`. Substituting a different u in the encryption changes the cells and therefore the commitment
+/// * The commitment is deterministic and is not blinded. Since u is sampled from the small distribution ChiKey, it binds u but does not hide it
+pub fn commit_randomness<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    u: &[AssignedValue<F>],
+    gate: &GateChip<F>,
+    make_public: &mut Vec<AssignedValue<F>>,
+) -> AssignedValue<F> {
+    // assert that the degree of u is equal to DEG
+    assert_eq!(u.len() - 1, DEG);
+
+    let commitment = poseidon_hash_poly(ctx, u, gate);
+
+    make_public.push(commitment);
+
+    commitment
+}
+
+/// Hash the coefficients of a polynomial with a fresh Poseidon sponge
+///
+/// * The sponge absorbs the coefficients in the order [a_DEG, a_DEG-1, ..., a_0], then a single field element is squeezed
+fn poseidon_hash_poly<F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    gate: &GateChip<F>,
+) -> AssignedValue<F> {
+    let mut poseidon =
+        PoseidonChip::<F, POSEIDON_T, POSEIDON_RATE>::new(ctx, POSEIDON_R_F, POSEIDON_R_P)
+            .expect("poseidon_hash_poly: invalid Poseidon parameters");
+
+    poseidon.update(a);
+    poseidon
+        .squeeze(ctx, gate)
+        .expect("poseidon_hash_poly: failed to squeeze the Poseidon sponge")
+}
+
+/// Enforces that a Poseidon commitment to a polynomial opens to the claimed evaluation at a public point
+///
+/// * Commitment scheme: the commitment to a is the Poseidon hash of its coefficients, computed with the same convention as `commit_randomness`, namely a fresh sponge of width 3 and rate 2 (8 full rounds, 57 partial rounds) absorbing [a_DEG, a_DEG-1, ..., a_0] and squeezing a single field element
+/// * The opening is checked by re-hashing the coefficients of a and re-evaluating a at `point` with `poly_eval_at_constant`. Both the commitment and the evaluation must match
+/// * Compared to a KZG opening, the coefficients of a are witnesses of the circuit: the check costs a hash of DEG + 1 field elements instead of a pairing, and the proof hides a only if the proof system is zero knowledge
+/// * The evaluation is performed modulo the prime p of the circuit and is not reduced modulo Q
+/// * DEG is the degree of a
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+pub fn verify_poly_opening<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    commitment: AssignedValue<F>,
+    point: F,
+    claimed_eval: AssignedValue<F>,
+    gate: &GateChip<F>,
+) {
+    // assert that the degree of a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    // Enforce that the commitment opens to a
+    let expected_commitment = poseidon_hash_poly(ctx, a, gate);
+    ctx.constrain_equal(&expected_commitment, &commitment);
+
+    // Enforce that a(point) is equal to the claimed evaluation
+    let eval = poly_eval_at_constant::<DEG, F>(ctx, a, point, gate);
+    ctx.constrain_equal(&eval, &claimed_eval);
+}
+
+/// Scale a polynomial down from the extended modulus Q * P to the modulus Q with rounding, namely the "mod-down" step of the BFV multiplication in the extended RNS basis
+///
+/// * The input polynomial a with coefficients in [0, Q * P - 1] is first decomposed in the RNS basis {Q, P} with `poly_reduce_rns`, as a_q = a mod Q and a_p = a mod P
+/// * The output is round(a / P) mod Q, with ties rounded up
+/// * Fast base conversion: the centered remainder [a]_P in [-(P-1)/2, P/2) is converted from the base {P} to the base {Q} as a_p - P * round(a_p / P), where the rounded quotient round(a_p / P) is 1 if a_p >= (P+1)/2 and 0 otherwise.
+///   It is computed with `poly_div_round`. Since a - [a]_P is a multiple of P and round(a / P) = (a - [a]_P) / P, the output is (a_q - [a]_P) * P^-1 mod Q
+/// * Base conversion constants: the moduli {Q, P} of the RNS basis, P, by which a_p is divided with rounding and which is added back when the remainder is lifted to a negative value, and P^-1 mod Q, computed outside the circuit with `utils::mod_inverse`
+/// * DEG is the degree of the polynomial
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that Q is prime and that P is not a multiple of Q, such that P is invertible modulo Q
+/// * Assumes that the coefficients of a are in the range [0, Q * P - 1]
+pub fn mod_down<const DEG: usize, const Q: u64, const P: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the input polynomial has degree DEG
+    assert_eq!(a.len() - 1, DEG);
+    assert!(
+        P >= 2,
+        "mod_down: the extension modulus P must be at least 2"
+    );
+
+    // P^-1 mod Q
+    let p_inv = mod_inverse(P % Q, Q);
+
+    // The coefficients of a are in the range [0, Q * P - 1]
+    let qp_bound = (Q as u128 * P as u128) - 1;
+    assert!(
+        fits_in_field::<F>(qp_bound),
+        "mod_down: the extended modulus Q * P may overflow the prime field of the circuit"
+    );
+
+    // To keep a_q - a_p non negative, a multiple of Q greater than P - 1 is added: offset = Q * (floor((P - 1) / Q) + 1)
+    let offset_multiple = (P as u128 - 1) / Q as u128 + 1;
+    let offset = offset_multiple * Q as u128;
+    let offset_fe = F::from(Q) * F::from(offset_multiple as u64);
+
+    // a_q + offset - a_p + P * round(a_p / P) is in the range [0, (Q - 1) + offset + P]
+    // Multiplying by P^-1 mod Q gives a value in the range [0, ((Q - 1) + offset + P) * (Q - 1)]
+    let bound = ((Q as u128 - 1) + offset + P as u128)
+        .checked_mul(Q as u128 - 1)
+        .expect("mod_down: the bound of the base conversion overflows u128");
+    assert!(
+        fits_in_field::<F>(bound),
+        "mod_down: the base conversion may overflow the prime field of the circuit"
+    );
+
+    // get the number of bits needed to represent the bounds of a, of a_p + P/2 < 2P and of the base conversion
+    let qp_num_bits = format!("{:b}", qp_bound).len();
+    let lift_num_bits = format!("{:b}", 2 * P as u128 - 1).len();
+    let num_bits = format!("{:b}", bound).len();
+
+    // RNS decomposition of a in the basis {Q, P}
+    let [a_q, a_p] = poly_reduce_rns::<DEG, 2, F>(ctx, &a, [Q, P], range, qp_num_bits);
+
+    // round(a_p / P) is 1 if the centered remainder [a]_P is negative and 0 otherwise
+    let lift = poly_div_round::<DEG, F>(ctx, &a_p, P, range, lift_num_bits);
+
+    let mut out = vec![];
+
+    for i in 0..=DEG {
+        // a_q - [a]_P + offset = a_q + offset - a_p + P * round(a_p / P)
+        let shifted = range.gate().add(ctx, a_q[i], Constant(offset_fe));
+        let diff = range.gate().sub(ctx, shifted, a_p[i]);
+        let diff = range
+            .gate()
+            .mul_add(ctx, lift[i], Constant(F::from(P)), diff);
+
+        // (a_q - [a]_P) * P^-1 mod Q
+        let scaled = range.gate().mul(ctx, diff, Constant(F::from(p_inv)));
+        let rem = range.div_mod(ctx, scaled, Q, num_bits).1;
+        out.push(rem);
+    }
+
+    // assert that the output polynomial has degree DEG
+    assert_eq!(out.len() - 1, DEG);
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::test_utils::{
+        load_poly, mock_run, ring_add, ring_automorphism, ring_mul, ring_neg,
+    };
+    use crate::chips::utils::{
+        mod_inverse, pow_mod, primitive_root_of_unity, vec_assigned_to_vec_u64,
+    };
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    const DEG: usize = 3;
+    const Q: u64 = 12289;
+
+    /// Reference decryption of the phase c0 + c1 * s = Δm + e, namely m = round(t * (c0 + c1 * s) / Q) mod t
+    fn decrypt(c0: &[u64], c1: &[u64], s: &[u64], t: u64) -> Vec<u64> {
+        ring_add(c0, &ring_mul(c1, s, Q), Q)
+            .iter()
+            .map(|&phase| ((phase * t + Q / 2) / Q) % t)
+            .collect()
+    }
+
+    #[test]
+    fn bfv_key_switch_preserves_plaintext() {
+        // Gadget base and number of digits, 16^4 >= Q
+        const T: u64 = 16;
+        const L: usize = 4;
+        // Plaintext modulus
+        let t = 4;
+        let delta = Q / t;
+
+        let s1 = [1, 0, Q - 1, 1];
+        let s2 = [0, Q - 1, 1, 1];
+        let m = [3, 0, 1, 2];
+        let e = [1, 0, Q - 1, 0];
+        let a = [5021, 77, 11002, 3];
+
+        // c0 = -(a * s1) + Δm + e, c1 = a
+        let delta_m = m.iter().map(|&m_i| delta * m_i).collect::<Vec<u64>>();
+        let c0 = ring_add(
+            &ring_add(&ring_neg(&ring_mul(&a, &s1, Q), Q), &delta_m, Q),
+            &e,
+            Q,
+        );
+        let c1 = a.to_vec();
+        assert_eq!(decrypt(&c0, &c1, &s1, t), m);
+
+        // ksk0[j] = -(a_j * s2 + e_j) + T^j * s1, ksk1[j] = a_j
+        let ksk_a = [
+            [1234, 9876, 42, 7],
+            [3000, 1, 12288, 600],
+            [17, 8191, 4096, 255],
+            [11111, 2222, 333, 44],
+        ];
+        let ksk_e = [
+            [0, 1, 0, 0],
+            [Q - 1, 0, 0, 1],
+            [0, 0, 1, 0],
+            [1, 0, 0, Q - 1],
+        ];
+        let mut ksk0 = vec![];
+        let mut ksk1 = vec![];
+        for j in 0..L {
+            let gadget_s1 = s1
+                .iter()
+                .map(|&s_i| T.pow(j as u32) * s_i % Q)
+                .collect::<Vec<u64>>();
+            let masked = ring_add(&ring_mul(&ksk_a[j], &s2, Q), &ksk_e[j], Q);
+            ksk0.push(ring_add(&ring_neg(&masked, Q), &gadget_s1, Q));
+            ksk1.push(ksk_a[j].to_vec());
+        }
+
+        let ((c0_prime, c1_prime), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let c0 = load_poly(ctx, &c0);
+            let c1 = load_poly(ctx, &c1);
+            let ksk0 = ksk0.iter().map(|poly| load_poly(ctx, poly)).collect();
+            let ksk1 = ksk1.iter().map(|poly| load_poly(ctx, poly)).collect();
+            let (c0_prime, c1_prime) =
+                bfv_key_switch::<DEG, Q, T, L, Fr>(ctx, c0, c1, ksk0, ksk1, range);
+            (
+                vec_assigned_to_vec_u64(&c0_prime),
+                vec_assigned_to_vec_u64(&c1_prime),
+            )
+        });
+
+        assert!(satisfied);
+        assert_eq!(decrypt(&c0_prime, &c1_prime, &s2, t), m);
+    }
+
+    /// Points at which the plaintext slots are evaluated in the layout of `slot_rotate`: slot i * n/2 + j is the evaluation at psi^((-1)^i * 3^j) mod t
+    fn slot_points(n: usize, t: u64) -> Vec<u64> {
+        let n = n as u64;
+        let psi = primitive_root_of_unity(2 * n, t);
+
+        (0..2)
+            .flat_map(|i| {
+                (0..n / 2).map(move |j| {
+                    let e = pow_mod(3, j, 2 * n);
+                    pow_mod(psi, if i == 0 { e } else { 2 * n - e }, t)
+                })
+            })
+            .collect()
+    }
+
+    /// Reference decoding of the plaintext m, parsed as [m_n-1, ..., m_0], into its slots
+    fn decode_slots(m: &[u64], t: u64) -> Vec<u64> {
+        slot_points(m.len(), t)
+            .iter()
+            .map(|&x| m.iter().fold(0, |acc, &m_i| (acc * x + m_i) % t))
+            .collect()
+    }
+
+    /// Reference encoding of the slots into a plaintext, namely m_k = n^-1 * sum_i slots[i] * points[i]^-k mod t
+    fn encode_slots(slots: &[u64], t: u64) -> Vec<u64> {
+        let n = slots.len();
+        let points = slot_points(n, t);
+        let n_inv = mod_inverse(n as u64, t);
+
+        (0..n)
+            .rev()
+            .map(|k| {
+                let sum = slots.iter().zip(&points).fold(0, |acc, (&slot, &x)| {
+                    (acc + slot * pow_mod(x, ((2 * n - k) % (2 * n)) as u64, t)) % t
+                });
+                sum * n_inv % t
+            })
+            .collect()
+    }
+
+    #[test]
+    fn slot_rotate_rotates_the_rows_of_the_slots() {
+        const DEG: usize = 7;
+        // Gadget base and number of digits, 16^4 >= Q
+        const T: u64 = 16;
+        const L: usize = 4;
+        // Plaintext modulus, t = 1 mod 2(DEG+1) such that the plaintext ring splits into DEG+1 slots
+        let t = 17;
+        let delta = Q / t;
+
+        // The slots form the 2 x 4 matrix [[1, 2, 3, 4], [5, 6, 7, 8]]
+        let slots = [1, 2, 3, 4, 5, 6, 7, 8];
+        let m = encode_slots(&slots, t);
+        assert_eq!(decode_slots(&m, t), slots.to_vec());
+
+        let s = [1, 0, Q - 1, 1, 0, 0, 1, Q - 1];
+        let e = [1, 0, Q - 1, 0, 0, 1, 0, 0];
+        let a = [5021, 77, 11002, 3, 9000, 1, 12000, 42];
+
+        // c0 = -(a * s) + Δm + e, c1 = a
+        let delta_m = m.iter().map(|&m_i| delta * m_i).collect::<Vec<u64>>();
+        let c0 = ring_add(
+            &ring_add(&ring_neg(&ring_mul(&a, &s, Q), Q), &delta_m, Q),
+            &e,
+            Q,
+        );
+        let c1 = a.to_vec();
+        assert_eq!(decrypt(&c0, &c1, &s, t), m);
+
+        // Rotation key from s(x^3) to s: rtk0[j] = -(a_j * s) + T^j * s(x^3), rtk1[j] = a_j
+        let s_rot = ring_automorphism(&s, 3, Q);
+        let mut rtk0 = vec![];
+        let mut rtk1 = vec![];
+        for j in 0..L {
+            let rtk_a = (0..=DEG)
+                .map(|i| (1234 * (j as u64 + 1) + 97 * i as u64) % Q)
+                .collect::<Vec<u64>>();
+            let gadget_s_rot = s_rot
+                .iter()
+                .map(|&s_i| T.pow(j as u32) * s_i % Q)
+                .collect::<Vec<u64>>();
+            rtk0.push(ring_add(
+                &ring_neg(&ring_mul(&rtk_a, &s, Q), Q),
+                &gadget_s_rot,
+                Q,
+            ));
+            rtk1.push(rtk_a);
+        }
+
+        let ((c0_rot, c1_rot), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let c0 = load_poly(ctx, &c0);
+            let c1 = load_poly(ctx, &c1);
+            let rtk0 = rtk0.iter().map(|poly| load_poly(ctx, poly)).collect();
+            let rtk1 = rtk1.iter().map(|poly| load_poly(ctx, poly)).collect();
+            let (c0_rot, c1_rot) =
+                slot_rotate::<DEG, Q, T, L, Fr>(ctx, c0, c1, 1, rtk0, rtk1, range);
+            (
+                vec_assigned_to_vec_u64(&c0_rot),
+                vec_assigned_to_vec_u64(&c1_rot),
+            )
+        });
+
+        assert!(satisfied);
+        // Each row of the matrix is rotated by one position to the left
+        let m_rot = decrypt(&c0_rot, &c1_rot, &s, t);
+        assert_eq!(decode_slots(&m_rot, t), vec![2, 3, 4, 1, 6, 7, 8, 5]);
+    }
+
+    /// Reference public key encryption c0 = pk0 * u + Δm + e0, c1 = pk1 * u + e1 with Δ = Q/t
+    fn encrypt(
+        pk: (&[u64], &[u64]),
+        m: &[u64],
+        u: &[u64],
+        e0: &[u64],
+        e1: &[u64],
+        t: u64,
+    ) -> (Vec<u64>, Vec<u64>) {
+        let delta_m = m.iter().map(|&m_i| Q / t * m_i).collect::<Vec<u64>>();
+        let c0 = ring_add(&ring_add(&ring_mul(pk.0, u, Q), &delta_m, Q), e0, Q);
+        let c1 = ring_add(&ring_mul(pk.1, u, Q), e1, Q);
+        (c0, c1)
+    }
+
+    /// Reference key pair with pk0 = -(a * s + e) and pk1 = a
+    fn key_pair() -> (Vec<u64>, Vec<u64>, Vec<u64>) {
+        let s = [1, 0, Q - 1, 1];
+        let a = [5021, 77, 11002, 3];
+        let e = [1, 0, Q - 1, 0];
+        let pk0 = ring_neg(&ring_add(&ring_mul(&a, &s, Q), &e, Q), Q);
+        (s.to_vec(), pk0, a.to_vec())
+    }
+
+    #[test]
+    fn bfv_ciphertext_valid_accepts_only_well_formed_ciphertexts() {
+        const T: u64 = 4;
+        const B: u64 = 2;
+
+        let (s, pk0, pk1) = key_pair();
+        let m = [3, 0, 1, 2];
+        let u = [0, 1, Q - 1, 1];
+        let e0 = [2, 0, Q - 1, 1];
+        let e1 = [0, Q - 2, 1, 0];
+        let (c0, c1) = encrypt((&pk0, &pk1), &m, &u, &e0, &e1, T);
+        assert_eq!(decrypt(&c0, &c1, &s, T), m);
+
+        let check = |c0: &[u64], e0: &[u64]| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let pk0 = load_poly(ctx, &pk0);
+                let pk1 = load_poly(ctx, &pk1);
+                let c0 = load_poly(ctx, c0);
+                let c1 = load_poly(ctx, &c1);
+                let m = load_poly(ctx, &m);
+                let u = load_poly(ctx, &u);
+                let e0 = load_poly(ctx, e0);
+                let e1 = load_poly(ctx, &e1);
+                bfv_ciphertext_valid::<DEG, Q, T, B, Fr>(
+                    ctx, pk0, pk1, c0, c1, m, u, e0, e1, range,
+                );
+            })
+            .1
+        };
+
+        assert!(check(&c0, &e0));
+
+        // A ciphertext that is not the encryption of m with the witnessed randomness
+        let mut tampered = c0.clone();
+        tampered[1] = (tampered[1] + 1) % Q;
+
+        #[cfg(not(feature = "debug"))]
+        assert!(!check(&tampered, &e0));
+
+        // With the `debug` feature, the mismatch panics at synthesis time with the label of the coefficient
+        #[cfg(feature = "debug")]
+        {
+            let err =
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| check(&tampered, &e0)))
+                    .unwrap_err();
+            let msg = err.downcast_ref::<String>().unwrap();
+            assert!(msg.starts_with("copy constraint failed: c0[1] "));
+        }
+
+        // A consistent ciphertext whose error e0 has a coefficient outside the range [0, B] OR [Q-B, Q-1]
+        let e0_large = [3, 0, Q - 1, 1];
+        let c0_large = encrypt((&pk0, &pk1), &m, &u, &e0_large, &e1, T).0;
+        assert!(!check(&c0_large, &e0_large));
+    }
+
+    #[test]
+    fn noise_budget_boundary() {
+        // Q/(2T) = 12289/8 = 1536.125, therefore a noise of norm 1536 decrypts correctly and 1537 does not
+        const T: u64 = 4;
+
+        let cases = [
+            ([1536, 0, 3, Q - 1], true),
+            ([0, Q - 1536, 1, 0], true),
+            ([1537, 0, 3, Q - 1], false),
+            ([0, Q - 1537, 1, 0], false),
+        ];
+
+        for (e, within_budget) in cases {
+            let (norm, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+                let e = load_poly(ctx, &e);
+                *noise_budget::<DEG, Q, T, Fr>(ctx, e, range).value()
+            });
+            assert_eq!(satisfied, within_budget);
+            let expected = if within_budget { 1536 } else { 1537 };
+            assert_eq!(norm, Fr::from(expected));
+        }
+    }
+
+    #[test]
+    fn commit_randomness_binds_u() {
+        // Computes the commitment to u and, if given, constrains it to be equal to the public commitment `expected`
+        let commit = |u: [u64; 4], expected: Option<Fr>| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let u = load_poly(ctx, &u);
+                let mut make_public = vec![];
+                let commitment =
+                    commit_randomness::<DEG, Fr>(ctx, &u, range.gate(), &mut make_public);
+                assert_eq!(make_public.len(), 1);
+                if let Some(expected) = expected {
+                    let expected = ctx.load_witness(expected);
+                    ctx.constrain_equal(&commitment, &expected);
+                }
+                *commitment.value()
+            })
+        };
+
+        let u = [0, 1, Q - 1, 1];
+        let (commitment, satisfied) = commit(u, None);
+        assert!(satisfied);
+
+        // The same u opens the commitment
+        let (_, satisfied) = commit(u, Some(commitment));
+        assert!(satisfied);
+
+        // A different u does not
+        let other_u = [1, 1, Q - 1, 1];
+        let (other_commitment, satisfied) = commit(other_u, Some(commitment));
+        assert!(!satisfied);
+        assert_ne!(other_commitment, commitment);
+    }
+
+    #[test]
+    fn rlwe_sample_check_valid_and_invalid_samples() {
+        const B: u64 = 2;
+
+        let a = [5021, 77, 11002, 3];
+        let s = [1, 0, Q - 1, 1];
+        let e = [1, 0, Q - 2, 2];
+        let b = ring_add(&ring_mul(&a, &s, Q), &e, Q);
+
+        let check = |b: &[u64], s: &[u64], e: &[u64]| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                let b = load_poly(ctx, b);
+                let s = load_poly(ctx, s);
+                let e = load_poly(ctx, e);
+                rlwe_sample_check::<DEG, Q, B, Fr>(ctx, a, b, s, e, range);
+            })
+            .1
+        };
+
+        assert!(check(&b, &s, &e));
+
+        // b is not a * s + e
+        let mut tampered = b.clone();
+        tampered[0] = (tampered[0] + 1) % Q;
+        assert!(!check(&tampered, &s, &e));
+
+        // b = a * s + e with a secret that is not ternary
+        let s_wide = [2, 0, Q - 1, 1];
+        let b_wide = ring_add(&ring_mul(&a, &s_wide, Q), &e, Q);
+        assert!(!check(&b_wide, &s_wide, &e));
+
+        // b = a * s + e with an error larger than B
+        let e_wide = [3, 0, Q - 2, 2];
+        let b_wide = ring_add(&ring_mul(&a, &s, Q), &e_wide, Q);
+        assert!(!check(&b_wide, &s, &e_wide));
+    }
+
+    #[test]
+    fn bfv_encrypt_both_modes_decrypt() {
+        const T: u64 = 4;
+        const B: u64 = 2;
+
+        let (s, pk0, pk1) = key_pair();
+        let m = [3, 0, 1, 2];
+        let u = [0, 1, Q - 1, 1];
+        let e0 = [2, 0, Q - 1, 1];
+        let e1 = [0, Q - 2, 1, 0];
+        let a = [1234, 9876, 42, 7];
+
+        let ((public, symmetric), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let pk0 = load_poly(ctx, &pk0);
+            let pk1 = load_poly(ctx, &pk1);
+            let m = load_poly(ctx, &m);
+            let u = load_poly(ctx, &u);
+            let e0 = load_poly(ctx, &e0);
+            let e1 = load_poly(ctx, &e1);
+            let a = load_poly(ctx, &a);
+            let s = load_poly(ctx, &s);
+
+            let (c0, c1) =
+                bfv_encrypt::<DEG, Q, T, B, Fr>(ctx, pk0, pk1, m.clone(), u, e0.clone(), e1, range);
+            let public = (vec_assigned_to_vec_u64(&c0), vec_assigned_to_vec_u64(&c1));
+
+            let (c0, c1) = bfv_encrypt_symmetric::<DEG, Q, T, B, Fr>(ctx, a, s, m, e0, range);
+            let symmetric = (vec_assigned_to_vec_u64(&c0), vec_assigned_to_vec_u64(&c1));
+
+            (public, symmetric)
+        });
+
+        assert!(satisfied);
+        assert_eq!(public, encrypt((&pk0, &pk1), &m, &u, &e0, &e1, T));
+        assert_eq!(decrypt(&public.0, &public.1, &s, T), m);
+        assert_eq!(symmetric.1, a);
+        assert_eq!(decrypt(&symmetric.0, &symmetric.1, &s, T), m);
+    }
+
+    #[test]
+    fn bfv_add_and_sub_decrypt_to_the_sum_and_difference() {
+        let t = 4;
+
+        let (s, pk0, pk1) = key_pair();
+        let m1 = [3, 0, 1, 2];
+        let m2 = [2, 3, 0, 1];
+        let (c1_0, c1_1) = encrypt(
+            (&pk0, &pk1),
+            &m1,
+            &[0, 1, Q - 1, 1],
+            &[2, 0, Q - 1, 1],
+            &[0, Q - 2, 1, 0],
+            t,
+        );
+        let (c2_0, c2_1) = encrypt(
+            (&pk0, &pk1),
+            &m2,
+            &[1, Q - 1, 0, 1],
+            &[0, 1, 0, Q - 2],
+            &[1, 0, 0, 2],
+            t,
+        );
+
+        let ((sum, diff), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let ct1 = Ciphertext {
+                c0: load_poly(ctx, &c1_0),
+                c1: load_poly(ctx, &c1_1),
+            };
+            let ct2 = Ciphertext {
+                c0: load_poly(ctx, &c2_0),
+                c1: load_poly(ctx, &c2_1),
+            };
+            let sum = ct1.bfv_add::<DEG, Q>(ctx, &ct2, range);
+            let diff = ct1.bfv_sub::<DEG, Q>(ctx, &ct2, range);
+            (
+                (
+                    vec_assigned_to_vec_u64(&sum.c0),
+                    vec_assigned_to_vec_u64(&sum.c1),
+                ),
+                (
+                    vec_assigned_to_vec_u64(&diff.c0),
+                    vec_assigned_to_vec_u64(&diff.c1),
+                ),
+            )
+        });
+
+        assert!(satisfied);
+        // m1 + m2 mod 4 and m1 - m2 mod 4
+        assert_eq!(decrypt(&sum.0, &sum.1, &s, t), vec![1, 3, 1, 3]);
+        assert_eq!(decrypt(&diff.0, &diff.1, &s, t), vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn verify_poly_opening_correct_and_tampered() {
+        let a = [1, 2, 3, 4];
+        // a(5) = 125 + 2 * 25 + 3 * 5 + 4
+        let point = Fr::from(5);
+        let eval = Fr::from(194);
+
+        // Commits to `committed` and checks the opening of the commitment to a at `point` against `claimed_eval`
+        let open = |committed: [u64; 4], claimed_eval: Fr| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let committed = load_poly(ctx, &committed);
+                let commitment = poseidon_hash_poly(ctx, &committed, range.gate());
+                let a = load_poly(ctx, &a);
+                let claimed_eval = ctx.load_witness(claimed_eval);
+                verify_poly_opening::<DEG, Fr>(
+                    ctx,
+                    &a,
+                    commitment,
+                    point,
+                    claimed_eval,
+                    range.gate(),
+                );
+            })
+            .1
+        };
+
+        assert!(open(a, eval));
+        // Wrong evaluation
+        assert!(!open(a, eval + Fr::from(1)));
+        // Commitment to another polynomial
+        assert!(!open([1, 2, 3, 5], eval));
+    }
+
+    /// Reference mod-down on integers, namely round(a / p) mod q with ties rounded up
+    fn mod_down_reference(a: &[u64], q: u64, p: u64) -> Vec<u64> {
+        a.iter().map(|&a_i| ((a_i + p / 2) / p) % q).collect()
+    }
+
+    #[test]
+    fn mod_down_matches_reference() {
+        // P = 10 is even and has ties, P = 7 is odd. Q * P - 1 = 969 and 678 round up to Q, which wraps around to 0
+        let cases: [(u64, [u64; 4], [u64; 4]); 4] = [
+            (10, [0, 4, 5, 969], [0, 0, 1, 0]),
+            (10, [15, 25, 14, 500], [2, 3, 1, 50]),
+            (7, [3, 4, 678, 350], [0, 1, 0, 50]),
+            (7, [96, 97, 11, 6], [14, 14, 2, 1]),
+        ];
+
+        for (p, a, expected) in cases {
+            assert_eq!(mod_down_reference(&a, 97, p), expected.to_vec());
+
+            let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                let out = if p == 10 {
+                    mod_down::<3, 97, 10, Fr>(ctx, a, range)
+                } else {
+                    mod_down::<3, 97, 7, Fr>(ctx, a, range)
+                };
+                vec_assigned_to_vec_u64(&out)
+            });
+
+            assert!(satisfied);
+            assert_eq!(out, expected.to_vec());
+        }
+    }
+
+    #[test]
+    fn bfv_plaintext_equal_same_and_different_messages() {
+        const T: u64 = 4;
+        let delta = Q / T;
+
+        // Symmetric encryption c0 = -(a * s) + Δm + e, c1 = a
+        let encrypt_sym = |a: &[u64], s: &[u64], m: &[u64], e: &[u64]| {
+            let delta_m = m.iter().map(|&m_i| delta * m_i).collect::<Vec<u64>>();
+            let c0 = ring_add(
+                &ring_add(&ring_neg(&ring_mul(a, s, Q), Q), &delta_m, Q),
+                e,
+                Q,
+            );
+            (c0, a.to_vec())
+        };
+
+        let s_a = [1, 0, Q - 1, 1];
+        let s_b = [0, Q - 1, 1, 1];
+        let e_a = [1, 0, Q - 1, 0];
+        let e_b = [0, Q - 2, 1, 2];
+        let m = [3, 0, 1, 2];
+        let ct_a = encrypt_sym(&[5021, 77, 11002, 3], &s_a, &m, &e_a);
+
+        let check = |m_b: &[u64]| {
+            let ct_b = encrypt_sym(&[1234, 9876, 42, 7], &s_b, m_b, &e_b);
+            assert_eq!(decrypt(&ct_b.0, &ct_b.1, &s_b, T), m_b);
+            mock_run::<Fr, _>(|ctx, range| {
+                let ct_a = Ciphertext {
+                    c0: load_poly(ctx, &ct_a.0),
+                    c1: load_poly(ctx, &ct_a.1),
+                };
+                let ct_b = Ciphertext {
+                    c0: load_poly(ctx, &ct_b.0),
+                    c1: load_poly(ctx, &ct_b.1),
+                };
+                let s_a = load_poly(ctx, &s_a);
+                let s_b = load_poly(ctx, &s_b);
+                let m_a = load_poly(ctx, &m);
+                let m_b = load_poly(ctx, m_b);
+                let e_a = load_poly(ctx, &e_a);
+                let e_b = load_poly(ctx, &e_b);
+                bfv_plaintext_equal::<DEG, Q, T, Fr>(
+                    ctx, &ct_a, &ct_b, s_a, s_b, m_a, m_b, e_a, e_b, range,
+                );
+            })
+            .1
+        };
+
+        // Both ciphertexts encrypt m under different keys
+        assert!(check(&m));
+        // The second ciphertext encrypts another message
+        assert!(!check(&[3, 0, 1, 1]));
+    }
+
+    #[test]
+    fn bfv_is_zero_ciphertext_zero_and_nonzero_plaintexts() {
+        const T: u64 = 4;
+        let delta = Q / T;
+
+        let s = [1, 0, Q - 1, 1];
+        let a = [5021, 77, 11002, 3];
+        let e = [1, 0, Q - 1, 0];
+
+        // Encrypts m symmetrically, c0 = -(a * s) + Δm + e, c1 = a, and checks that it is an encryption of zero with the witness `m_witness`
+        let check = |m: [u64; 4], m_witness: [u64; 4]| {
+            let delta_m = m.iter().map(|&m_i| delta * m_i).collect::<Vec<u64>>();
+            let c0 = ring_add(
+                &ring_add(&ring_neg(&ring_mul(&a, &s, Q), Q), &delta_m, Q),
+                &e,
+                Q,
+            );
+            assert_eq!(decrypt(&c0, &a, &s, T), m);
+            mock_run::<Fr, _>(|ctx, range| {
+                let ct = Ciphertext {
+                    c0: load_poly(ctx, &c0),
+                    c1: load_poly(ctx, &a),
+                };
+                let s = load_poly(ctx, &s);
+                let m = load_poly(ctx, &m_witness);
+                let e = load_poly(ctx, &e);
+                bfv_is_zero_ciphertext::<DEG, Q, T, Fr>(ctx, &ct, s, m, e, range);
+            })
+            .1
+        };
+
+        assert!(check([0, 0, 0, 0], [0, 0, 0, 0]));
+        // The plaintext of the ciphertext is not zero
+        assert!(!check([0, 0, 0, 1], [0, 0, 0, 1]));
+        // The decryption does not hold with the zero plaintext as witness
+        assert!(!check([0, 0, 0, 1], [0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn bfv_blind_preserves_decryption() {
+        const T: u64 = 4;
+        let delta = Q / T;
+
+        let (s, pk0, pk1) = key_pair();
+        let m = [3, 0, 1, 2];
+        let (c0, c1) = encrypt(
+            (&pk0, &pk1),
+            &m,
+            &[0, 1, Q - 1, 1],
+            &[2, 0, Q - 1, 1],
+            &[0, Q - 2, 1, 0],
+            T,
+        );
+
+        // Symmetric encryption of zero, z0 = -(a * s) + e_zero, z1 = a
+        let a = [1234, 9876, 42, 7];
+        let e_zero = [0, Q - 2, 1, 2];
+        let z0 = ring_add(&ring_neg(&ring_mul(&a, &s, Q), Q), &e_zero, Q);
+        assert_eq!(decrypt(&z0, &a, &s, T), vec![0; 4]);
+
+        let blind = |z0: &[u64]| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let ct = Ciphertext {
+                    c0: load_poly(ctx, &c0),
+                    c1: load_poly(ctx, &c1),
+                };
+                let zero_ct = Ciphertext {
+                    c0: load_poly(ctx, z0),
+                    c1: load_poly(ctx, &a),
+                };
+                let s = load_poly(ctx, &s);
+                let e_zero = load_poly(ctx, &e_zero);
+                let blinded = bfv_blind::<DEG, Q, T, Fr>(ctx, &ct, &zero_ct, s, e_zero, range);
+                (
+                    vec_assigned_to_vec_u64(&blinded.c0),
+                    vec_assigned_to_vec_u64(&blinded.c1),
+                )
+            })
+        };
+
+        let ((b0, b1), satisfied) = blind(&z0);
+        assert!(satisfied);
+        assert_ne!((&b0, &b1), (&c0, &c1));
+        assert_eq!(decrypt(&c0, &c1, &s, T), m);
+        assert_eq!(decrypt(&b0, &b1, &s, T), m);
+
+        // Blinding with an encryption of a nonzero plaintext is rejected
+        let z0_one = ring_add(&z0, &[0, 0, 0, delta], Q);
+        let (_, satisfied) = blind(&z0_one);
+        assert!(!satisfied);
+    }
+
+    #[test]
+    fn bfv_verify_rotation_correct_and_incorrect_outputs() {
+        // Gadget base and number of digits, 16^4 >= Q
+        const T: u64 = 16;
+        const L: usize = 4;
+
+        // The check recomputes the rotation, therefore the ciphertext and the rotation key do not need to be well formed
+        let c0 = [5021, 77, 11002, 3];
+        let c1 = [1234, 9876, 42, 7];
+        let rtk0 = (0..L)
+            .map(|j| {
+                (0..=DEG)
+                    .map(|i| (4321 * (j as u64 + 1) + 97 * i as u64) % Q)
+                    .collect()
+            })
+            .collect::<Vec<Vec<u64>>>();
+        let rtk1 = (0..L)
+            .map(|j| {
+                (0..=DEG)
+                    .map(|i| (1234 * (j as u64 + 1) + 31 * i as u64) % Q)
+                    .collect()
+            })
+            .collect::<Vec<Vec<u64>>>();
+
+        // Checks the claimed rotation by one step, or returns the rotation computed by `slot_rotate` if none is claimed
+        let rotate = |claimed: Option<&(Vec<u64>, Vec<u64>)>| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let ct = Ciphertext {
+                    c0: load_poly(ctx, &c0),
+                    c1: load_poly(ctx, &c1),
+                };
+                let rtk0 = rtk0.iter().map(|poly| load_poly(ctx, poly)).collect();
+                let rtk1 = rtk1.iter().map(|poly| load_poly(ctx, poly)).collect();
+                match claimed {
+                    Some((c0_rot, c1_rot)) => {
+                        let rotated = Ciphertext {
+                            c0: load_poly(ctx, c0_rot),
+                            c1: load_poly(ctx, c1_rot),
+                        };
+                        bfv_verify_rotation::<DEG, Q, T, L, Fr>(
+                            ctx, &ct, &rotated, 1, rtk0, rtk1, range,
+                        );
+                        None
+                    }
+                    None => {
+                        let (c0_rot, c1_rot) = slot_rotate::<DEG, Q, T, L, Fr>(
+                            ctx, ct.c0, ct.c1, 1, rtk0, rtk1, range,
+                        );
+                        Some((
+                            vec_assigned_to_vec_u64(&c0_rot),
+                            vec_assigned_to_vec_u64(&c1_rot),
+                        ))
+                    }
+                }
+            })
+        };
+
+        let (rotated, satisfied) = rotate(None);
+        assert!(satisfied);
+        let rotated = rotated.unwrap();
+
+        let (_, satisfied) = rotate(Some(&rotated));
+        assert!(satisfied);
+
+        // A rotation output with a wrong coefficient
+        let mut tampered = rotated.clone();
+        tampered.0[2] = (tampered.0[2] + 1) % Q;
+
+        #[cfg(not(feature = "debug"))]
+        {
+            let (_, satisfied) = rotate(Some(&tampered));
+            assert!(!satisfied);
+        }
+
+        // With the `debug` feature, the mismatch panics at synthesis time with the label of the coefficient
+        #[cfg(feature = "debug")]
+        {
+            let err =
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| rotate(Some(&tampered))))
+                    .unwrap_err();
+            let msg = err.downcast_ref::<String>().unwrap();
+            assert!(msg.starts_with("copy constraint failed: rotated.c0[2] "));
+        }
+    }
+}
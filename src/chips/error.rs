@@ -0,0 +1,92 @@
+use crate::chips::params::ParamsError;
+use crate::chips::utils::fits_in_capacity;
+use std::fmt;
+
+/// Error returned by the `try_` chips when a precondition on their inputs is not satisfied
+///
+/// * The chips without the `try_` prefix check the same preconditions and panic with the message of the error
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolyError {
+    /// The polynomial has no coefficient, therefore it has no degree
+    EmptyPolynomial,
+    /// The degree of a polynomial, namely its number of coefficients minus 1, is not the degree expected by the chip
+    DegreeMismatch { expected: usize, found: usize },
+    /// The divisor is a constant polynomial, which is not a cyclotomic polynomial
+    ConstantDivisor,
+    /// The degree of the dividend is neither 2 * DEG_DVS - 2 (with DEG_DVS < DEG_DVD) nor strictly less than DEG_DVS
+    InvalidDividendDegree { deg_dvd: usize, deg_dvs: usize },
+    /// The parameters of the ring are not supported, see `ParamsError`
+    Params(ParamsError),
+    /// The bound of the coefficients computed by the chip needs `bound_bits` bits, which exceeds the capacity of the prime field of the circuit, therefore they may overflow
+    FieldOverflow {
+        bound_bits: u32,
+        field_capacity: u32,
+    },
+}
+
+impl fmt::Display for PolyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolyError::EmptyPolynomial => write!(f, "the polynomial has no coefficient"),
+            PolyError::DegreeMismatch { expected, found } => write!(
+                f,
+                "expected a polynomial of degree {}, found degree {}",
+                expected, found
+            ),
+            PolyError::ConstantDivisor => write!(
+                f,
+                "the divisor must be a cyclotomic polynomial of degree at least 1, got a constant divisor"
+            ),
+            PolyError::InvalidDividendDegree { deg_dvd, deg_dvs } => write!(
+                f,
+                "the degree {} of the dividend must be 2 * {} - 2 or less than {}",
+                deg_dvd, deg_dvs, deg_dvs
+            ),
+            PolyError::Params(err) => write!(f, "{}", err),
+            PolyError::FieldOverflow {
+                bound_bits,
+                field_capacity,
+            } => write!(
+                f,
+                "the coefficients may need {} bits, which exceeds the {} bits of capacity of the field",
+                bound_bits, field_capacity
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PolyError {}
+
+impl From<ParamsError> for PolyError {
+    fn from(err: ParamsError) -> Self {
+        PolyError::Params(err)
+    }
+}
+
+/// Checks that the polynomial `poly`, parsed as a vector of coefficients [a_DEG, a_DEG-1, ..., a_1, a_0], has degree `deg`
+pub(crate) fn check_degree<T>(poly: &[T], deg: usize) -> Result<(), PolyError> {
+    if poly.is_empty() {
+        return Err(PolyError::EmptyPolynomial);
+    }
+
+    if poly.len() - 1 != deg {
+        return Err(PolyError::DegreeMismatch {
+            expected: deg,
+            found: poly.len() - 1,
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that every value in the range [0, bound] fits in a prime field of `field_capacity` bits of capacity, see `utils::fits_in_capacity`
+pub(crate) fn check_bound(bound: u128, field_capacity: u32) -> Result<(), PolyError> {
+    if !fits_in_capacity(bound, field_capacity) {
+        return Err(PolyError::FieldOverflow {
+            bound_bits: 128 - bound.leading_zeros(),
+            field_capacity,
+        });
+    }
+
+    Ok(())
+}
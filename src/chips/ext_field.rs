@@ -0,0 +1,209 @@
+use crate::chips::poly_operations::{poly_add, poly_mul_equal_deg};
+use halo2_base::gates::GateChip;
+use halo2_base::gates::GateInstructions;
+use halo2_base::utils::ScalarField;
+use halo2_base::AssignedValue;
+use halo2_base::Context;
+use halo2_base::QuantumCell::Constant;
+
+/// Assigned element c0 + c1 * u of the degree 2 extension F[u]/(u^2 - W) of the prime field F of the circuit
+///
+/// * Each element is represented by a pair of assigned base field cells (c0, c1)
+/// * W is a constant of F that must be a quadratic non-residue, such that u^2 - W is irreducible and F[u]/(u^2 - W) is a field. W is passed as a const generic to the chips that multiply
+/// * The arithmetic is performed modulo the prime p of the circuit, therefore no overflow analysis is needed
+#[derive(Clone, Copy, Debug)]
+pub struct Ext2<F: ScalarField> {
+    pub c0: AssignedValue<F>,
+    pub c1: AssignedValue<F>,
+}
+
+impl<F: ScalarField> Ext2<F> {
+    /// Assigns the element c0 + c1 * u as a witness
+    pub fn load_witness(ctx: &mut Context<F>, c0: F, c1: F) -> Self {
+        Ext2 {
+            c0: ctx.load_witness(c0),
+            c1: ctx.load_witness(c1),
+        }
+    }
+}
+
+/// Enforces the sum of two elements of the extension
+///
+/// * (a0 + a1 * u) + (b0 + b1 * u) = (a0 + b0) + (a1 + b1) * u
+pub fn ext2_add<F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Ext2<F>,
+    b: Ext2<F>,
+    gate: &GateChip<F>,
+) -> Ext2<F> {
+    Ext2 {
+        c0: gate.add(ctx, a.c0, b.c0),
+        c1: gate.add(ctx, a.c1, b.c1),
+    }
+}
+
+/// Enforces the product of two elements of the extension F[u]/(u^2 - W)
+///
+/// * (a0 + a1 * u) * (b0 + b1 * u) = (a0 * b0 + W * a1 * b1) + (a0 * b1 + a1 * b0) * u, since u^2 = W
+/// * Assumes that W is a quadratic non-residue of F
+pub fn ext2_mul<const W: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Ext2<F>,
+    b: Ext2<F>,
+    gate: &GateChip<F>,
+) -> Ext2<F> {
+    let a0_b0 = gate.mul(ctx, a.c0, b.c0);
+    let a1_b1 = gate.mul(ctx, a.c1, b.c1);
+    let c0 = gate.mul_add(ctx, a1_b1, Constant(F::from(W)), a0_b0);
+
+    let a1_b0 = gate.mul(ctx, a.c1, b.c0);
+    let c1 = gate.mul_add(ctx, a.c0, b.c1, a1_b0);
+
+    Ext2 { c0, c1 }
+}
+
+/// Splits a polynomial over the extension into its two components over F
+///
+/// * A = A0 + A1 * u where A0 and A1 are polynomials over F with the same degree as A
+fn poly_ext2_split<F: ScalarField>(
+    a: &[Ext2<F>],
+) -> (Vec<AssignedValue<F>>, Vec<AssignedValue<F>>) {
+    a.iter().map(|coeff| (coeff.c0, coeff.c1)).unzip()
+}
+
+/// Build the sum of the polynomials a and b over the extension F[u]/(u^2 - W) as sum of the coefficients
+///
+/// * The polynomials are split into their components A = A0 + A1 * u and B = B0 + B1 * u, which are added with `poly_add`
+/// * DEG is the degree of the input polynomials
+/// * Input polynomials are parsed as a vector of extension coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+pub fn poly_add_ext2<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[Ext2<F>],
+    b: &[Ext2<F>],
+    gate: &GateChip<F>,
+) -> Vec<Ext2<F>> {
+    // assert that the input polynomials have the same degree and this is equal to DEG
+    assert_eq!(a.len() - 1, b.len() - 1);
+    assert_eq!(a.len() - 1, DEG);
+
+    let (a0, a1) = poly_ext2_split(a);
+    let (b0, b1) = poly_ext2_split(b);
+
+    let c0 = poly_add::<DEG, F>(ctx, &a0, &b0, gate);
+    let c1 = poly_add::<DEG, F>(ctx, &a1, &b1, gate);
+
+    c0.into_iter()
+        .zip(c1)
+        .map(|(c0, c1)| Ext2 { c0, c1 })
+        .collect()
+}
+
+/// Build the product of the polynomials a and b over the extension F[u]/(u^2 - W)
+///
+/// * The polynomials are split into their components A = A0 + A1 * u and B = B0 + B1 * u over F
+/// * A * B = (A0 * B0 + W * A1 * B1) + (A0 * B1 + A1 * B0) * u, where A0 * B1 + A1 * B0 = (A0 + A1) * (B0 + B1) - A0 * B0 - A1 * B1.
+///   This takes three `poly_mul_equal_deg` over F instead of four
+/// * DEG is the degree of the input polynomials
+/// * Input polynomials are parsed as a vector of extension coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Returns the product of degree 2*DEG
+/// * Assumes that W is a quadratic non-residue of F
+pub fn poly_mul_equal_deg_ext2<const DEG: usize, const W: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[Ext2<F>],
+    b: &[Ext2<F>],
+    gate: &GateChip<F>,
+) -> Vec<Ext2<F>> {
+    // assert that the input polynomials have the same degree and this is equal to DEG
+    assert_eq!(a.len() - 1, b.len() - 1);
+    assert_eq!(a.len() - 1, DEG);
+
+    let (a0, a1) = poly_ext2_split(a);
+    let (b0, b1) = poly_ext2_split(b);
+
+    let a0_b0 = poly_mul_equal_deg::<DEG, F>(ctx, &a0, &b0, gate);
+    let a1_b1 = poly_mul_equal_deg::<DEG, F>(ctx, &a1, &b1, gate);
+
+    let a0_a1 = poly_add::<DEG, F>(ctx, &a0, &a1, gate);
+    let b0_b1 = poly_add::<DEG, F>(ctx, &b0, &b1, gate);
+    let cross = poly_mul_equal_deg::<DEG, F>(ctx, &a0_a1, &b0_b1, gate);
+
+    let mut c = vec![];
+
+    for i in 0..(2 * DEG + 1) {
+        // c0 = a0_b0 + W * a1_b1
+        let c0 = gate.mul_add(ctx, a1_b1[i], Constant(F::from(W)), a0_b0[i]);
+
+        // c1 = cross - a0_b0 - a1_b1
+        let sum = gate.add(ctx, a0_b0[i], a1_b1[i]);
+        let c1 = gate.sub(ctx, cross[i], sum);
+
+        c.push(Ext2 { c0, c1 });
+    }
+
+    // assert that the product polynomial has degree 2*DEG
+    assert_eq!(c.len() - 1, 2 * DEG);
+
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::test_utils::mock_run;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+    use halo2_base::safe_types::RangeInstructions;
+
+    // 7 generates the multiplicative group of Fr, therefore it is a quadratic non-residue
+    const W: u64 = 7;
+
+    /// Reference product of two elements (a0 + a1 * u) * (b0 + b1 * u) with u^2 = W, computed natively in Fr
+    fn ext2_mul_reference(a: (Fr, Fr), b: (Fr, Fr)) -> (Fr, Fr) {
+        (a.0 * b.0 + Fr::from(W) * a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+    }
+
+    #[test]
+    fn poly_ext2_matches_native_reference() {
+        let a = [
+            (Fr::from(3), -Fr::from(1)),
+            (Fr::from(0), Fr::from(5)),
+            (-Fr::from(2), Fr::from(7)),
+        ];
+        let b = [
+            (Fr::from(1), Fr::from(1)),
+            (Fr::from(4), Fr::from(0)),
+            (Fr::from(6), -Fr::from(3)),
+        ];
+
+        // Schoolbook product over the extension
+        let mut expected_prod = vec![(Fr::from(0), Fr::from(0)); 5];
+        for (i, a_i) in a.iter().enumerate() {
+            for (j, b_j) in b.iter().enumerate() {
+                let (c0, c1) = ext2_mul_reference(*a_i, *b_j);
+                expected_prod[i + j].0 += c0;
+                expected_prod[i + j].1 += c1;
+            }
+        }
+        let expected_sum = a
+            .iter()
+            .zip(&b)
+            .map(|(a_i, b_i)| (a_i.0 + b_i.0, a_i.1 + b_i.1))
+            .collect::<Vec<_>>();
+
+        let ((sum, prod), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = a.map(|(c0, c1)| Ext2::load_witness(ctx, c0, c1));
+            let b = b.map(|(c0, c1)| Ext2::load_witness(ctx, c0, c1));
+            let values = |poly: Vec<Ext2<Fr>>| {
+                poly.iter()
+                    .map(|c| (*c.c0.value(), *c.c1.value()))
+                    .collect::<Vec<_>>()
+            };
+            let sum = poly_add_ext2::<2, Fr>(ctx, &a, &b, range.gate());
+            let prod = poly_mul_equal_deg_ext2::<2, W, Fr>(ctx, &a, &b, range.gate());
+            (values(sum), values(prod))
+        });
+
+        assert!(satisfied);
+        assert_eq!(sum, expected_sum);
+        assert_eq!(prod, expected_prod);
+    }
+}
@@ -0,0 +1,174 @@
+use halo2_base::utils::ScalarField;
+use halo2_base::AssignedValue;
+use halo2_base::Context;
+#[cfg(feature = "debug")]
+use std::cell::RefCell;
+#[cfg(feature = "debug")]
+use std::collections::HashMap;
+
+// Labels of the cells of the current thread, indexed by (context id, offset of the cell in the context)
+#[cfg(feature = "debug")]
+thread_local! {
+    static LABELS: RefCell<HashMap<(usize, usize), String>> = RefCell::new(HashMap::new());
+}
+
+/// Attaches the human readable label `name` to the assigned cell `cell`, e.g. "quotient[7]"
+///
+/// * The label is only recorded with the `debug` feature. Without it, this function is a no-op and `name` is never called, therefore no string is formatted
+/// * Cells assigned in witness generation only mode have no position in their context and are not labeled
+/// * Labeling a cell twice overwrites the previous label
+#[inline(always)]
+pub fn label<F: ScalarField, S: Into<String>>(cell: &AssignedValue<F>, name: impl FnOnce() -> S) {
+    #[cfg(feature = "debug")]
+    if let Some(cell) = cell.cell {
+        let name = name().into();
+        LABELS.with(|labels| {
+            labels
+                .borrow_mut()
+                .insert((cell.context_id, cell.offset), name)
+        });
+    }
+
+    #[cfg(not(feature = "debug"))]
+    let _ = (cell, name);
+}
+
+/// Attaches the label "name[i]" to the i-th coefficient of the polynomial `poly`
+///
+/// * The coefficients are indexed as in the vector, namely poly[0] is the leading coefficient
+/// * Same as `label` for each coefficient. A no-op without the `debug` feature
+#[inline(always)]
+pub fn label_poly<F: ScalarField>(poly: &[AssignedValue<F>], name: &str) {
+    for (i, coeff) in poly.iter().enumerate() {
+        label(coeff, || format!("{}[{}]", name, i));
+    }
+}
+
+/// Returns the label attached to a cell, if any
+///
+/// * Always returns None without the `debug` feature
+pub fn cell_label<F: ScalarField>(cell: &AssignedValue<F>) -> Option<String> {
+    #[cfg(feature = "debug")]
+    {
+        let cell = cell.cell?;
+        LABELS.with(|labels| {
+            labels
+                .borrow()
+                .get(&(cell.context_id, cell.offset))
+                .cloned()
+        })
+    }
+
+    #[cfg(not(feature = "debug"))]
+    {
+        let _ = cell;
+        None
+    }
+}
+
+/// Returns a description of a cell for diagnostics: its label if any, followed by its position "(context c, offset o)"
+pub fn describe<F: ScalarField>(cell: &AssignedValue<F>) -> String {
+    let position = match cell.cell {
+        Some(position) => format!(
+            "(context {}, offset {})",
+            position.context_id, position.offset
+        ),
+        None => "(unpositioned)".to_string(),
+    };
+
+    match cell_label(cell) {
+        Some(name) => format!("{} {}", name, position),
+        None => position,
+    }
+}
+
+/// Enforces that the cells a and b are equal with `Context::constrain_equal`, reporting the labels of the cells if their values differ
+///
+/// * The mock prover reports an unsatisfied copy constraint by its column and row in the final layout, which is only known after synthesis. With the `debug` feature, this function checks the values at synthesis time and panics with the labels of both cells, e.g. "c0[3] (context 0, offset 1542)"
+/// * Without the `debug` feature, this is exactly `ctx.constrain_equal(a, b)`
+/// * The constraint is emitted in both cases, therefore the circuit is the same with or without the feature
+pub fn constrain_equal<F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &AssignedValue<F>,
+    b: &AssignedValue<F>,
+) {
+    #[cfg(feature = "debug")]
+    assert!(
+        a.value() == b.value(),
+        "copy constraint failed: {} = {:?} differs from {} = {:?}",
+        describe(a),
+        a.value(),
+        describe(b),
+        b.value()
+    );
+
+    ctx.constrain_equal(a, b);
+}
+
+/// Clears the labels recorded so far on the current thread
+///
+/// * A no-op without the `debug` feature
+pub fn reset() {
+    #[cfg(feature = "debug")]
+    LABELS.with(|labels| labels.borrow_mut().clear());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::test_utils::{load_poly, mock_run};
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn labeled_cells_are_described_by_their_label() {
+        mock_run::<Fr, _>(|ctx, _| {
+            reset();
+            let c0 = load_poly(ctx, &[1, 2, 3, 4]);
+            let unlabeled = ctx.load_witness(Fr::from(5));
+            label_poly(&c0, "c0");
+
+            assert_eq!(cell_label(&c0[3]), Some("c0[3]".to_string()));
+            assert_eq!(cell_label(&unlabeled), None);
+            assert!(describe(&c0[3]).starts_with("c0[3] (context "));
+
+            // Labeling a cell again overwrites its label
+            label(&c0[3], || "c0_expected[3]");
+            assert_eq!(cell_label(&c0[3]), Some("c0_expected[3]".to_string()));
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    #[should_panic(expected = "copy constraint failed: c0[3] (context ")]
+    fn failing_copy_constraint_reports_the_labeled_cell() {
+        mock_run::<Fr, _>(|ctx, _| {
+            reset();
+            let c0 = load_poly(ctx, &[1, 2, 3, 4]);
+            let c0_expected = load_poly(ctx, &[1, 2, 3, 5]);
+            label_poly(&c0, "c0");
+            label_poly(&c0_expected, "c0_expected");
+
+            for i in 0..4 {
+                constrain_equal(ctx, &c0[i], &c0_expected[i]);
+            }
+        });
+    }
+
+    #[test]
+    #[cfg(not(feature = "debug"))]
+    fn without_debug_labels_are_not_recorded() {
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, _| {
+            let c0 = load_poly(ctx, &[1, 2, 3, 4]);
+            let c0_expected = load_poly(ctx, &[1, 2, 3, 5]);
+            label_poly(&c0, "c0");
+            assert_eq!(cell_label(&c0[3]), None);
+
+            // The failing copy constraint is only reported by the mock prover
+            for i in 0..4 {
+                constrain_equal(ctx, &c0[i], &c0_expected[i]);
+            }
+        });
+        assert!(!satisfied);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::chips::poly_operations::{poly_divide_by_cyclo, poly_mul_equal_deg};
+use halo2_base::gates::GateChip;
+use halo2_base::safe_types::RangeChip;
+use halo2_base::utils::ScalarField;
+use halo2_base::Context;
+
+/// Layout hint for a chip invocation
+///
+/// * `advice_cells` is the number of advice cells assigned by the chip, excluding the cells of its inputs
+/// * `lookup_cells` is the number of cells that the chip sends to the lookup table of the range chip
+///
+/// The hints are derived by synthesizing the chip on dummy inputs inside a fresh `Context` and measuring how many cells it assigns.
+/// The number of cells assigned by the gates of halo2-lib only depends on the degree, Q and `lookup_bits`, not on the value of the inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LayoutHint {
+    pub advice_cells: usize,
+    pub lookup_cells: usize,
+}
+
+impl LayoutHint {
+    /// Number of advice columns needed to fit `advice_cells` in a circuit of 2^k rows, `minimum_rows` of which are reserved for blinding
+    pub fn num_advice(&self, k: usize, minimum_rows: usize) -> usize {
+        let max_rows = (1 << k) - minimum_rows;
+        (self.advice_cells + max_rows - 1) / max_rows
+    }
+
+    /// Number of lookup advice columns needed to fit `lookup_cells` in a circuit of 2^k rows, `minimum_rows` of which are reserved for blinding
+    pub fn num_lookup_advice(&self, k: usize, minimum_rows: usize) -> usize {
+        let max_rows = (1 << k) - minimum_rows;
+        (self.lookup_cells + max_rows - 1) / max_rows
+    }
+}
+
+/// Returns the layout hint of the `poly_mul_equal_deg` chip for input polynomials of degree DEG
+///
+/// * `poly_mul_equal_deg` does not perform any range check, therefore `lookup_cells` is 0
+pub fn poly_mul_equal_deg_layout_hint<const DEG: usize, F: ScalarField>() -> LayoutHint {
+    let mut ctx = Context::<F>::new(false, 0);
+    let gate = GateChip::<F>::default();
+
+    let a = ctx.assign_witnesses(vec![F::zero(); DEG + 1]);
+    let b = ctx.assign_witnesses(vec![F::zero(); DEG + 1]);
+
+    let advice_before = ctx.advice.len();
+    let lookup_before = ctx.cells_to_lookup.len();
+
+    poly_mul_equal_deg::<DEG, F>(&mut ctx, &a, &b, &gate);
+
+    LayoutHint {
+        advice_cells: ctx.advice.len() - advice_before,
+        lookup_cells: ctx.cells_to_lookup.len() - lookup_before,
+    }
+}
+
+/// Returns the layout hint of the `poly_divide_by_cyclo` chip for a dividend of degree DEG_DVD, a cyclotomic divisor of degree DEG_DVS and modulus Q
+///
+/// * `lookup_bits` is the number of bits of the lookup table of the range chip. It must be less than the `k` of the circuit
+/// * The range checks are decomposed in limbs of `lookup_bits` bits, therefore a larger `lookup_bits` reduces both `advice_cells` and `lookup_cells`
+pub fn poly_divide_by_cyclo_layout_hint<
+    const DEG_DVD: usize,
+    const DEG_DVS: usize,
+    const Q: u64,
+    F: ScalarField,
+>(
+    lookup_bits: usize,
+) -> LayoutHint {
+    let mut ctx = Context::<F>::new(false, 0);
+    let range = RangeChip::<F>::default(lookup_bits);
+
+    // The dividend is the zero polynomial and the divisor is the cyclotomic polynomial x^DEG_DVS + 1
+    let dividend = ctx.assign_witnesses(vec![F::zero(); DEG_DVD + 1]);
+    let mut divisor_coeffs = vec![F::zero(); DEG_DVS + 1];
+    divisor_coeffs[0] = F::one();
+    divisor_coeffs[DEG_DVS] = F::one();
+    let divisor = ctx.assign_witnesses(divisor_coeffs);
+
+    let advice_before = ctx.advice.len();
+    let lookup_before = ctx.cells_to_lookup.len();
+
+    poly_divide_by_cyclo::<DEG_DVD, DEG_DVS, Q, F>(&mut ctx, dividend, divisor, &range);
+
+    LayoutHint {
+        advice_cells: ctx.advice.len() - advice_before,
+        lookup_cells: ctx.cells_to_lookup.len() - lookup_before,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::test_utils::{load_poly, mock_run, LOOKUP_BITS};
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+    use halo2_base::safe_types::RangeInstructions;
+
+    #[test]
+    fn poly_mul_equal_deg_layout_hint_matches_build() {
+        let hint = poly_mul_equal_deg_layout_hint::<3, Fr>();
+
+        let (cells, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[3, 1, 4, 1]);
+            let b = load_poly(ctx, &[5, 9, 2, 6]);
+
+            let advice_before = ctx.advice.len();
+            let lookup_before = ctx.cells_to_lookup.len();
+            poly_mul_equal_deg::<3, Fr>(ctx, &a, &b, range.gate());
+
+            (
+                ctx.advice.len() - advice_before,
+                ctx.cells_to_lookup.len() - lookup_before,
+            )
+        });
+
+        assert!(satisfied);
+        assert_eq!(cells, (hint.advice_cells, hint.lookup_cells));
+    }
+
+    #[test]
+    fn poly_divide_by_cyclo_layout_hint_matches_build() {
+        let hint = poly_divide_by_cyclo_layout_hint::<6, 4, 97, Fr>(LOOKUP_BITS);
+
+        let (cells, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let dividend = load_poly(ctx, &[96, 12, 0, 55, 7, 1, 80]);
+            let divisor = load_poly(ctx, &[1, 0, 0, 0, 1]);
+
+            let advice_before = ctx.advice.len();
+            let lookup_before = ctx.cells_to_lookup.len();
+            poly_divide_by_cyclo::<6, 4, 97, Fr>(ctx, dividend, divisor, range);
+
+            (
+                ctx.advice.len() - advice_before,
+                ctx.cells_to_lookup.len() - lookup_before,
+            )
+        });
+
+        assert!(satisfied);
+        assert_eq!(cells, (hint.advice_cells, hint.lookup_cells));
+        assert!(hint.lookup_cells > 0);
+    }
+}
@@ -1,3 +1,15 @@
+pub mod bfv_operations;
+pub mod error;
+pub mod ext_field;
+pub mod labels;
+pub mod layout;
+pub mod ntt;
+pub mod params;
 pub mod poly_distribution;
 pub mod poly_operations;
+pub mod poly_packing;
+#[cfg(feature = "stats")]
+pub mod stats;
+#[cfg(test)]
+pub(crate) mod test_utils;
 pub mod utils;
@@ -0,0 +1,631 @@
+use crate::chips::poly_operations::{poly_hadamard, poly_truncate};
+use crate::chips::utils::{fits_in_field, is_prime, pow_mod, primitive_root_of_unity};
+use halo2_base::gates::GateInstructions;
+use halo2_base::safe_types::RangeChip;
+use halo2_base::safe_types::RangeInstructions;
+use halo2_base::utils::ScalarField;
+use halo2_base::AssignedValue;
+use halo2_base::Context;
+use halo2_base::QuantumCell::Constant;
+
+/// Polynomial of the ring R_q = Z_Q[x]/(x^(DEG+1) + 1) in evaluation (point-value) form
+///
+/// * `evals[i]` is the evaluation a(psi^(2i+1)) mod Q where psi is a primitive 2(DEG+1)-th root of unity modulo Q
+/// * The points psi^(2i+1) for i in [0, DEG] are the roots of x^(DEG+1) + 1, therefore the product in R_q becomes a pointwise product of the evaluations
+/// * The evaluations are in the range [0, Q - 1]
+#[derive(Clone, Debug)]
+pub struct EvalPoly<F: ScalarField> {
+    pub evals: Vec<AssignedValue<F>>,
+}
+
+/// Constants of the negacyclic NTT of the ring R_q = Z_Q[x]/(x^(DEG+1) + 1)
+///
+/// * The constants are evaluated once at compile time for each pair (DEG, Q), therefore the primality of Q is not checked again on each call of `to_eval` and `from_eval`
+/// * Q must be prime and 2(DEG+1) must divide Q - 1, otherwise the build fails with the message of the failed assertion
+struct NttConstants<const DEG: usize, const Q: u64>;
+
+impl<const DEG: usize, const Q: u64> NttConstants<DEG, Q> {
+    /// psi, a primitive 2(DEG+1)-th root of unity modulo Q
+    const PSI: u64 = {
+        let n = DEG as u64 + 1;
+        assert!(
+            n.is_power_of_two(),
+            "the NTT requires DEG + 1 to be a power of 2"
+        );
+        assert!(is_prime(Q), "the NTT requires a prime Q");
+        assert!(
+            (Q - 1) % (2 * n) == 0,
+            "the NTT requires 2(DEG+1) to divide Q - 1"
+        );
+
+        primitive_root_of_unity(2 * n, Q)
+    };
+
+    /// psi^-1 = psi^(2(DEG+1) - 1) mod Q, since psi^(2(DEG+1)) = 1 mod Q
+    const PSI_INV: u64 = pow_mod(Self::PSI, 2 * (DEG as u64 + 1) - 1, Q);
+
+    /// (DEG+1)^-1 = (DEG+1)^(Q-2) mod Q (Fermat's little theorem)
+    const N_INV: u64 = pow_mod(DEG as u64 + 1, Q - 2, Q);
+}
+
+/// Converts a polynomial from coefficient form to evaluation form (forward negacyclic NTT)
+///
+/// * DEG is the degree of the polynomial. DEG + 1 must be a power of 2
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Each evaluation is computed as an inner product between the coefficients and the constant twiddle factors psi^((2i+1)j) mod Q, followed by a single reduction modulo Q
+/// * Q must be prime and 2(DEG+1) must divide Q - 1, which is checked at compile time by `NttConstants`
+/// * Assumes that the coefficients of a are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn to_eval<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> EvalPoly<F> {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    let n = DEG as u64 + 1;
+    let psi = NttConstants::<DEG, Q>::PSI;
+
+    // The bound is computed on u128 so that it cannot wrap around for Q above 32 bits
+    let sum_bound = (Q as u128 - 1)
+        .checked_mul(Q as u128 - 1)
+        .and_then(|bound| bound.checked_mul(n as u128))
+        .expect("to_eval: the bound of the inner products overflows u128");
+    assert!(
+        fits_in_field::<F>(sum_bound),
+        "to_eval: the inner products may overflow the prime field of the circuit"
+    );
+
+    // get the number of bits needed to represent the value of (Q-1) * (Q-1) * (DEG+1)
+    let binary_representation = format!("{:b}", sum_bound);
+    let num_bits = binary_representation.len();
+
+    let mut evals = vec![];
+
+    for i in 0..n {
+        // a[DEG - j] is the coefficient of x^j, it is multiplied by psi^((2i+1)j)
+        let twiddles = (0..n)
+            .rev()
+            .map(|j| Constant(F::from(pow_mod(psi, ((2 * i + 1) * j) % (2 * n), Q))));
+
+        // The inner product is the sum of DEG + 1 products of values in the range [0, Q - 1]
+        // Therefore it is known to have <= `num_bits` bits, which satisfies the assumption of `div_mod`
+        let eval = range.gate().inner_product(ctx, a.iter().copied(), twiddles);
+        let eval = range.div_mod(ctx, eval, Q, num_bits).1;
+        evals.push(eval);
+    }
+
+    EvalPoly { evals }
+}
+
+/// Converts a polynomial from evaluation form to coefficient form (inverse negacyclic NTT)
+///
+/// * DEG is the degree of the output polynomial. DEG + 1 must be a power of 2
+/// * Output polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Each coefficient a_j is computed as (DEG+1)^-1 * sum_i evals[i] * psi^(-(2i+1)j) mod Q. The factor (DEG+1)^-1 is folded in the constant twiddle factors
+/// * Since (DEG+1)^-1 is an inverse modulo Q, it cannot be expressed as a rational witness of the field. Folding it in the constants emits no inversion constraint
+/// * Q must be prime and 2(DEG+1) must divide Q - 1, which is checked at compile time by `NttConstants`
+/// * Assumes that the evaluations are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn from_eval<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: EvalPoly<F>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the number of evaluations is equal to DEG + 1
+    assert_eq!(a.evals.len() - 1, DEG);
+
+    let n = DEG as u64 + 1;
+    let psi_inv = NttConstants::<DEG, Q>::PSI_INV;
+    let n_inv = NttConstants::<DEG, Q>::N_INV;
+
+    // The bound is computed on u128 so that it cannot wrap around for Q above 32 bits
+    let sum_bound = (Q as u128 - 1)
+        .checked_mul(Q as u128 - 1)
+        .and_then(|bound| bound.checked_mul(n as u128))
+        .expect("from_eval: the bound of the inner products overflows u128");
+    assert!(
+        fits_in_field::<F>(sum_bound),
+        "from_eval: the inner products may overflow the prime field of the circuit"
+    );
+
+    // get the number of bits needed to represent the value of (Q-1) * (Q-1) * (DEG+1)
+    let binary_representation = format!("{:b}", sum_bound);
+    let num_bits = binary_representation.len();
+
+    let mut c = vec![];
+
+    // Build the coefficients from the leading one a_DEG down to the constant term a_0
+    for j in (0..n).rev() {
+        let twiddles = (0..n).map(|i| {
+            let twiddle = pow_mod(psi_inv, ((2 * i + 1) * j) % (2 * n), Q);
+            Constant(F::from(
+                (twiddle as u128 * n_inv as u128 % Q as u128) as u64,
+            ))
+        });
+
+        // The inner product is the sum of DEG + 1 products of values in the range [0, Q - 1]
+        // Therefore it is known to have <= `num_bits` bits, which satisfies the assumption of `div_mod`
+        let coeff = range
+            .gate()
+            .inner_product(ctx, a.evals.iter().copied(), twiddles);
+        let coeff = range.div_mod(ctx, coeff, Q, num_bits).1;
+        c.push(coeff);
+    }
+
+    // assert that the output polynomial has degree DEG
+    assert_eq!(c.len() - 1, DEG);
+
+    c
+}
+
+/// Build the sum of two polynomials in evaluation form, namely the pointwise sum of the evaluations reduced modulo Q
+///
+/// * DEG is the degree of the polynomials. Each polynomial has DEG + 1 evaluations
+/// * Assumes that the evaluations are in the range [0, Q - 1]
+pub fn eval_add<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &EvalPoly<F>,
+    b: &EvalPoly<F>,
+    range: &RangeChip<F>,
+) -> EvalPoly<F> {
+    // assert that the input polynomials have DEG + 1 evaluations
+    assert_eq!(a.evals.len() - 1, DEG);
+    assert_eq!(b.evals.len() - 1, DEG);
+
+    // get the number of bits needed to represent the value of 2Q - 2
+    // The bound is computed on u128 so that it cannot wrap around for Q above 2^63
+    let binary_representation = format!("{:b}", (2 * Q as u128 - 2));
+    let num_bits = binary_representation.len();
+
+    let sums = (0..=DEG)
+        .map(|i| range.gate().add(ctx, a.evals[i], b.evals[i]))
+        .collect();
+
+    eval_reduce::<DEG, Q, F>(ctx, EvalPoly { evals: sums }, range, num_bits)
+}
+
+/// Build the product of two polynomials in evaluation form, namely the pointwise product of the evaluations reduced modulo Q
+///
+/// * This is equivalent to the product in R_q = Z_Q[x]/(x^(DEG+1) + 1) of the polynomials in coefficient form
+/// * DEG is the degree of the polynomials. Each polynomial has DEG + 1 evaluations
+/// * Assumes that the evaluations are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) < p where p is the prime field of the circuit
+pub fn eval_mul<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &EvalPoly<F>,
+    b: &EvalPoly<F>,
+    range: &RangeChip<F>,
+) -> EvalPoly<F> {
+    // assert that the input polynomials have DEG + 1 evaluations
+    assert_eq!(a.evals.len() - 1, DEG);
+    assert_eq!(b.evals.len() - 1, DEG);
+
+    // The bound is computed on u128 so that it cannot wrap around for Q above 32 bits
+    let prod_bound = (Q as u128 - 1) * (Q as u128 - 1);
+    assert!(
+        fits_in_field::<F>(prod_bound),
+        "eval_mul: the pointwise products may overflow the prime field of the circuit"
+    );
+
+    // get the number of bits needed to represent the value of (Q-1) * (Q-1)
+    let binary_representation = format!("{:b}", prod_bound);
+    let num_bits = binary_representation.len();
+
+    // The pointwise product is the Hadamard product of the evaluations
+    let prods = poly_hadamard::<DEG, F>(ctx, &a.evals, &b.evals, range.gate());
+
+    eval_reduce::<DEG, Q, F>(ctx, EvalPoly { evals: prods }, range, num_bits)
+}
+
+/// Takes a polynomial in evaluation form and output a new polynomial in evaluation form reduced by applying modulo Q to each evaluation
+///
+/// * This is the evaluation form analogue of `poly_reduce`
+/// * The reduction modulo the cyclotomic polynomial x^(DEG+1) + 1 is free in evaluation form: the evaluation points psi^(2i+1) are the roots of x^(DEG+1) + 1, therefore any multiple of x^(DEG+1) + 1 evaluates to zero and never needs to be removed.
+///   Only the pointwise reduction modulo Q remains
+/// * DEG is the degree of the polynomial. The polynomial has DEG + 1 evaluations
+/// * It assumes that the evaluations can be expressed in at most num_bits bits
+pub fn eval_reduce<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: EvalPoly<F>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> EvalPoly<F> {
+    // assert that the input polynomial has DEG + 1 evaluations
+    assert_eq!(input.evals.len() - 1, DEG);
+
+    let mut evals = vec![];
+
+    // Enforce that input.evals[i] % Q = evals[i]
+    for eval in input.evals {
+        let eval = range.div_mod(ctx, eval, Q, num_bits).1;
+        evals.push(eval);
+    }
+
+    EvalPoly { evals }
+}
+
+/// Returns, for each plaintext slot, the index in `EvalPoly::evals` of the evaluation that holds it
+///
+/// * The DEG+1 slots are arranged as a 2 x (DEG+1)/2 matrix. Slot i * (DEG+1)/2 + j holds the evaluation at psi^((-1)^i * 3^j)
+/// * This is the layout assumed by the `slot_rotate` chip, where the automorphism x -> x^(3^steps) rotates each row of the matrix
+fn slot_indices<const DEG: usize>() -> Vec<usize> {
+    let n = DEG as u64 + 1;
+    assert!(n.is_power_of_two() && n >= 2);
+
+    let mut indices = vec![];
+
+    for i in 0..2 {
+        for j in 0..n / 2 {
+            let mut e = pow_mod(3, j, 2 * n);
+            if i == 1 {
+                e = 2 * n - e;
+            }
+            // evals[k] is the evaluation at psi^(2k+1)
+            indices.push(((e - 1) / 2) as usize);
+        }
+    }
+
+    indices
+}
+
+/// Encodes a vector of plaintext slots into a polynomial of the plaintext ring R_t = Z_T[x]/(x^(DEG+1) + 1) (SIMD packing)
+///
+/// * DEG is the degree of the output polynomial. DEG + 1 must be a power of 2 and is the number of slots
+/// * T is the plaintext modulus. Assumes that T is prime and that 2(DEG+1) divides T - 1, such that R_t splits into DEG+1 slots
+/// * The slots are arranged as described in `slot_rotate`: slot i * (DEG+1)/2 + j is the evaluation of the output polynomial at psi^((-1)^i * 3^j) where psi is a primitive 2(DEG+1)-th root of unity modulo T
+/// * The encoding is an inverse negacyclic NTT modulo T, performed with the `from_eval` chip
+/// * Output polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the slots are in the range [0, T - 1]
+/// * Assumes that (T-1) * (T-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn slots_to_poly<const DEG: usize, const T: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    slots: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the number of slots is equal to DEG + 1
+    assert_eq!(slots.len() - 1, DEG);
+
+    let mut evals = slots.clone();
+    for (slot, idx) in slots.into_iter().zip(slot_indices::<DEG>()) {
+        evals[idx] = slot;
+    }
+
+    from_eval::<DEG, T, F>(ctx, EvalPoly { evals }, range)
+}
+
+/// Decodes a polynomial of the plaintext ring R_t = Z_T[x]/(x^(DEG+1) + 1) into its vector of plaintext slots
+///
+/// * Inverse of `slots_to_poly`. The decoding is a forward negacyclic NTT modulo T, performed with the `to_eval` chip
+/// * DEG is the degree of the input polynomial. DEG + 1 must be a power of 2 and is the number of slots
+/// * T is the plaintext modulus. Assumes that T is prime and that 2(DEG+1) divides T - 1
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the input polynomial are in the range [0, T - 1]
+/// * Assumes that (T-1) * (T-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn poly_to_slots<const DEG: usize, const T: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    let evals = to_eval::<DEG, T, F>(ctx, a, range).evals;
+
+    let slots = slot_indices::<DEG>()
+        .into_iter()
+        .map(|idx| evals[idx])
+        .collect::<Vec<_>>();
+
+    // assert that the number of slots is equal to DEG + 1
+    assert_eq!(slots.len() - 1, DEG);
+
+    slots
+}
+
+/// Enforces that `evals` is the evaluation form of the polynomial `coeffs`, namely that `evals` was computed correctly by a prover
+///
+/// * The inverse negacyclic NTT is run on `evals` with `from_eval` and the output is constrained to be equal to `coeffs` coefficient by coefficient
+/// * Since the evaluation form is supplied by the prover, each evaluation is first constrained to be in the range [0, Q - 1]
+/// * DEG is the degree of the polynomial. DEG + 1 must be a power of 2
+/// * Input polynomial `coeffs` is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that Q is prime and that 2(DEG+1) divides Q - 1
+/// * Assumes that the coefficients of `coeffs` are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn verify_eval_form<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    coeffs: &[AssignedValue<F>],
+    evals: EvalPoly<F>,
+    range: &RangeChip<F>,
+) {
+    // assert that the degree of coeffs is equal to DEG
+    assert_eq!(coeffs.len() - 1, DEG);
+
+    // Enforce that the evaluations are in the range [0, Q - 1], which is the assumption of `from_eval`
+    for eval in &evals.evals {
+        range.check_less_than_safe(ctx, *eval, Q);
+    }
+
+    let c = from_eval::<DEG, Q, F>(ctx, evals, range);
+
+    // The coefficients of c and coeffs are in the range [0, Q - 1], therefore they are equal if and only if the polynomials are equal
+    for i in 0..=DEG {
+        ctx.constrain_equal(&c[i], &coeffs[i]);
+    }
+}
+
+/// Enforces that the evaluations `evals` are consistent with a polynomial of degree at most DEG and returns its coefficients
+///
+/// * `evals` holds EVAL_DEG + 1 evaluations, where EVAL_DEG >= DEG. They are interpreted as the evaluation form of a polynomial of the ring Z_Q[x]/(x^(EVAL_DEG+1) + 1), namely evals[i] is the evaluation at psi^(2i+1) where psi is a primitive 2(EVAL_DEG+1)-th root of unity modulo Q
+/// * The inverse negacyclic NTT is run with `from_eval` and the coefficients of degree greater than DEG are enforced to be zero with `poly_truncate`
+/// * When EVAL_DEG = DEG the transform is not oversampled: any vector of DEG + 1 evaluations corresponds to a polynomial of degree at most DEG, and no coefficient is enforced to be zero. The check is meaningful for oversampled transforms, where EVAL_DEG > DEG
+/// * Since the evaluations are supplied by an untrusted source, each of them is first constrained to be in the range [0, Q - 1]
+/// * Returns the DEG + 1 coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term, in the range [0, Q - 1]
+/// * Assumes that Q is prime, that EVAL_DEG + 1 is a power of 2 and that 2(EVAL_DEG+1) divides Q - 1
+/// * Assumes that (Q-1) * (Q-1) * (EVAL_DEG+1) < p where p is the prime field of the circuit
+pub fn assert_valid_eval_form<
+    const DEG: usize,
+    const EVAL_DEG: usize,
+    const Q: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    evals: EvalPoly<F>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the number of evaluations is equal to EVAL_DEG + 1
+    assert_eq!(evals.evals.len() - 1, EVAL_DEG);
+
+    // Enforce that the evaluations are in the range [0, Q - 1], which is the assumption of `from_eval`
+    for eval in &evals.evals {
+        range.check_less_than_safe(ctx, *eval, Q);
+    }
+
+    let coeffs = from_eval::<EVAL_DEG, Q, F>(ctx, evals, range);
+
+    // Enforce that the coefficients of degree greater than DEG are zero
+    poly_truncate::<EVAL_DEG, DEG, F>(ctx, coeffs, range.gate())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::poly_operations::{poly_add, poly_automorphism, poly_mul_mod, poly_reduce};
+    use crate::chips::test_utils::{load_poly, mock_run};
+    use crate::chips::utils::{mod_inverse, vec_assigned_to_vec_u64};
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    // 2 * 8 divides 97 - 1, therefore Z_97[x]/(x^8 + 1) supports the negacyclic NTT
+    const DEG: usize = 7;
+    const Q: u64 = 97;
+
+    /// Inverse NTT that scales the unscaled coefficients by the explicit constant (DEG+1)^-1 mod Q, followed by a second reduction
+    fn from_eval_explicit_inverse(
+        ctx: &mut Context<Fr>,
+        a: &EvalPoly<Fr>,
+        range: &RangeChip<Fr>,
+    ) -> Vec<AssignedValue<Fr>> {
+        let n = DEG as u64 + 1;
+        let psi_inv = mod_inverse(NttConstants::<DEG, Q>::PSI, Q);
+        let n_inv = mod_inverse(n, Q);
+        let num_bits = format!("{:b}", (Q - 1) * (Q - 1) * n).len();
+
+        (0..n)
+            .rev()
+            .map(|j| {
+                let twiddles = (0..n)
+                    .map(|i| Constant(Fr::from(pow_mod(psi_inv, ((2 * i + 1) * j) % (2 * n), Q))));
+                let coeff = range
+                    .gate()
+                    .inner_product(ctx, a.evals.iter().copied(), twiddles);
+                let coeff = range.div_mod(ctx, coeff, Q, num_bits).1;
+
+                let scaled = range.gate().mul(ctx, coeff, Constant(Fr::from(n_inv)));
+                range.div_mod(ctx, scaled, Q, num_bits).1
+            })
+            .collect()
+    }
+
+    #[test]
+    fn from_eval_matches_explicit_inverse() {
+        let a = [7, 0, 96, 3, 41, 1, 0, 88];
+
+        let ((folded, explicit, folded_cells, explicit_cells), satisfied) =
+            mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                let evals = to_eval::<DEG, Q, Fr>(ctx, a, range);
+
+                let before = ctx.advice.len();
+                let folded = from_eval::<DEG, Q, Fr>(ctx, evals.clone(), range);
+                let folded_cells = ctx.advice.len() - before;
+
+                let before = ctx.advice.len();
+                let explicit = from_eval_explicit_inverse(ctx, &evals, range);
+                let explicit_cells = ctx.advice.len() - before;
+
+                (
+                    vec_assigned_to_vec_u64(&folded),
+                    vec_assigned_to_vec_u64(&explicit),
+                    folded_cells,
+                    explicit_cells,
+                )
+            });
+
+        assert!(satisfied);
+        assert_eq!(folded, explicit);
+        // The round trip through the evaluation form recovers the coefficients
+        assert_eq!(folded, a.to_vec());
+        // Folding (DEG+1)^-1 in the twiddle factors saves a multiplication and a reduction per coefficient
+        assert!(folded_cells < explicit_cells);
+    }
+
+    #[test]
+    fn packed_slots_are_added_slot_wise() {
+        // 2 * 4 divides 17 - 1, therefore Z_17[x]/(x^4 + 1) splits into 4 slots
+        let ((packed, sum), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let slots_a = load_poly(ctx, &[1, 2, 3, 4]);
+            let slots_b = load_poly(ctx, &[5, 6, 7, 16]);
+
+            let a = slots_to_poly::<3, 17, Fr>(ctx, slots_a, range);
+            let b = slots_to_poly::<3, 17, Fr>(ctx, slots_b, range);
+            let packed = poly_to_slots::<3, 17, Fr>(ctx, a.clone(), range);
+
+            // The coefficients of a + b are in the range [0, 32], which can be expressed in 6 bits
+            let sum = poly_add::<3, Fr>(ctx, &a, &b, range.gate());
+            let sum = poly_reduce::<3, 17, Fr>(ctx, sum, range, 6);
+            let sum = poly_to_slots::<3, 17, Fr>(ctx, sum, range);
+
+            (
+                vec_assigned_to_vec_u64(&packed),
+                vec_assigned_to_vec_u64(&sum),
+            )
+        });
+
+        assert!(satisfied);
+        assert_eq!(packed, vec![1, 2, 3, 4]);
+        assert_eq!(sum, vec![6, 8, 10, 3]);
+    }
+
+    #[test]
+    fn automorphism_rotates_the_rows_of_slot_indices() {
+        // The slots of Z_17[x]/(x^8 + 1) form the 2 x 4 matrix [[1, 2, 3, 4], [5, 6, 7, 8]]
+        let slots = [1, 2, 3, 4, 5, 6, 7, 8];
+        assert_eq!(slot_indices::<7>(), vec![0, 1, 4, 5, 7, 6, 3, 2]);
+
+        for (steps, expected) in [(1, [2, 3, 4, 1, 6, 7, 8, 5]), (2, [3, 4, 1, 2, 7, 8, 5, 6])] {
+            let (rotated, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+                let slots = load_poly(ctx, &slots);
+                let m = slots_to_poly::<7, 17, Fr>(ctx, slots, range);
+
+                // Galois element of `slot_rotate`
+                let galois_elt = pow_mod(3, steps, 16) as usize;
+                let m_rot = poly_automorphism::<7, 17, Fr>(ctx, m, galois_elt, range.gate());
+
+                vec_assigned_to_vec_u64(&poly_to_slots::<7, 17, Fr>(ctx, m_rot, range))
+            });
+
+            assert!(satisfied);
+            assert_eq!(rotated, expected.to_vec());
+        }
+    }
+
+    #[test]
+    fn ntt_constants() {
+        let psi = NttConstants::<DEG, Q>::PSI;
+
+        // psi is a primitive 2(DEG+1)-th root of unity, namely psi^(DEG+1) = -1 mod Q
+        assert_eq!(pow_mod(psi, DEG as u64 + 1, Q), Q - 1);
+        assert_eq!(psi * NttConstants::<DEG, Q>::PSI_INV % Q, 1);
+        assert_eq!((DEG as u64 + 1) * NttConstants::<DEG, Q>::N_INV % Q, 1);
+    }
+
+    #[test]
+    fn eval_mul_matches_poly_mul_mod() {
+        let a = [7, 0, 96, 3, 41, 1, 0, 88];
+        let b = [1, 2, 3, 4, 5, 6, 7, 96];
+
+        let ((ntt_prod, prod), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            let b = load_poly(ctx, &b);
+
+            let a_eval = to_eval::<DEG, Q, Fr>(ctx, a.clone(), range);
+            let b_eval = to_eval::<DEG, Q, Fr>(ctx, b.clone(), range);
+            let prod_eval = eval_mul::<DEG, Q, Fr>(ctx, &a_eval, &b_eval, range);
+            let ntt_prod = from_eval::<DEG, Q, Fr>(ctx, prod_eval, range);
+
+            let prod = poly_mul_mod::<DEG, Q, Fr>(ctx, a, b, range);
+
+            (
+                vec_assigned_to_vec_u64(&ntt_prod),
+                vec_assigned_to_vec_u64(&prod),
+            )
+        });
+
+        assert!(satisfied);
+        assert_eq!(ntt_prod, prod);
+    }
+
+    /// Reference evaluation form, namely the evaluations a(psi^(2i+1)) mod Q
+    fn evals_reference(a: &[u64]) -> Vec<u64> {
+        let n = DEG as u64 + 1;
+        let psi = NttConstants::<DEG, Q>::PSI;
+        (0..n)
+            .map(|i| {
+                let x = pow_mod(psi, 2 * i + 1, Q);
+                a.iter().fold(0, |acc, &a_k| (acc * x + a_k) % Q)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn verify_eval_form_passing_and_tampered() {
+        let a = [7, 0, 96, 3, 41, 1, 0, 88];
+        let evals = evals_reference(&a);
+
+        let check = |evals: &[u64]| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let coeffs = load_poly(ctx, &a);
+                let evals = EvalPoly {
+                    evals: load_poly(ctx, evals),
+                };
+                verify_eval_form::<DEG, Q, Fr>(ctx, &coeffs, evals, range);
+            })
+            .1
+        };
+
+        assert!(check(&evals));
+
+        let mut tampered = evals.clone();
+        tampered[3] = (tampered[3] + 1) % Q;
+        assert!(!check(&tampered));
+    }
+
+    #[test]
+    fn assert_valid_eval_form_oversampled() {
+        let check = |evals: &[u64]| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let evals = EvalPoly {
+                    evals: load_poly(ctx, evals),
+                };
+                vec_assigned_to_vec_u64(&assert_valid_eval_form::<3, DEG, Q, Fr>(ctx, evals, range))
+            })
+        };
+
+        // 8 evaluations of a polynomial of degree 3
+        let evals = evals_reference(&[0, 0, 0, 0, 1, 2, 3, 96]);
+        let (coeffs, satisfied) = check(&evals);
+        assert!(satisfied);
+        assert_eq!(coeffs, vec![1, 2, 3, 96]);
+
+        // 8 evaluations of a polynomial of degree 5
+        let evals = evals_reference(&[0, 0, 7, 0, 1, 2, 3, 96]);
+        let (_, satisfied) = check(&evals);
+        assert!(!satisfied);
+    }
+
+    #[test]
+    fn eval_reduce_round_trip_matches_poly_mul_mod() {
+        let a = [7, 0, 96, 3, 41, 1, 0, 88];
+        let b = [1, 96, 5, 0, 60, 2, 33, 9];
+
+        let ((via_evals, reference), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            let b = load_poly(ctx, &b);
+            let a_evals = to_eval::<DEG, Q, Fr>(ctx, a.clone(), range);
+            let b_evals = to_eval::<DEG, Q, Fr>(ctx, b.clone(), range);
+
+            // The pointwise products are in the range [0, 96 * 96] and have at most 14 bits
+            let prods = EvalPoly {
+                evals: poly_hadamard::<DEG, Fr>(ctx, &a_evals.evals, &b_evals.evals, range.gate()),
+            };
+            let reduced = eval_reduce::<DEG, Q, Fr>(ctx, prods, range, 14);
+            let via_evals = from_eval::<DEG, Q, Fr>(ctx, reduced, range);
+
+            let reference = poly_mul_mod::<DEG, Q, Fr>(ctx, a, b, range);
+            (
+                vec_assigned_to_vec_u64(&via_evals),
+                vec_assigned_to_vec_u64(&reference),
+            )
+        });
+        assert!(satisfied);
+        assert_eq!(via_evals, reference);
+    }
+}
@@ -0,0 +1,544 @@
+use halo2_base::utils::ScalarField;
+use num_bigint::BigUint;
+use std::fmt;
+
+// Re-exported for the `params!` macro, such that callers do not need to depend on halo2_base directly
+#[doc(hidden)]
+pub use halo2_base;
+
+/// Error returned by `PolyParams::new` when a parameter set does not satisfy the preconditions of the chips
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParamsError {
+    /// deg + 1 is not a power of 2, therefore x^(deg+1) + 1 is not a power of 2 cyclotomic polynomial
+    InvalidDegree(usize),
+    /// The modulus is smaller than 2
+    InvalidModulus(u64),
+    /// The modulus is not smaller than the prime p of the field
+    ModulusExceedsField { q: u64, field_capacity: u32 },
+    /// (q-1) * (q-1) * (deg+1) is not smaller than the prime p of the field, therefore the product of two polynomials may overflow
+    OverflowBound {
+        bound_bits: u32,
+        field_capacity: u32,
+    },
+}
+
+impl fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamsError::InvalidDegree(deg) => {
+                write!(f, "deg + 1 = {} must be a power of 2", deg + 1)
+            }
+            ParamsError::InvalidModulus(q) => write!(f, "the modulus {} must be at least 2", q),
+            ParamsError::ModulusExceedsField { q, field_capacity } => write!(
+                f,
+                "the modulus {} does not fit in the {} bits of capacity of the field",
+                q, field_capacity
+            ),
+            ParamsError::OverflowBound {
+                bound_bits,
+                field_capacity,
+            } => write!(
+                f,
+                "(q-1) * (q-1) * (deg+1) needs {} bits, which exceeds the {} bits of capacity of the field",
+                bound_bits, field_capacity
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParamsError {}
+
+/// Parameters of the ring R_q = Z_q[x]/(x^(deg+1) + 1), validated against the prime field of the circuit
+///
+/// * `deg` is the degree of the polynomials of the ring
+/// * `q` is the modulus of the coefficients
+/// * `field_capacity` is the capacity in bits of the prime field of the circuit, namely every value < 2^field_capacity is smaller than the prime p
+///
+/// A `PolyParams` can only be built with `PolyParams::new`, therefore holding one guarantees the overflow bound trusted by the `_with_params` chips, namely `poly_mul_mod_with_params`, `poly_reduce_with_params` and `poly_divide_by_cyclo_with_params`.
+/// It does not guarantee the preconditions of the other chips, which check their own bounds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PolyParams {
+    deg: usize,
+    q: u64,
+    field_capacity: u32,
+}
+
+/// Number of bits needed to represent x
+const fn num_bits(x: u128) -> u32 {
+    128 - x.leading_zeros()
+}
+
+/// Number of bits of (q-1) * (q-1) * (deg+1)
+const fn mul_bound_bits(deg: usize, q: u64) -> u32 {
+    // (q-1) * (q-1) fits in a u128. If the product with deg + 1 overflows, the number of bits is bounded by the sum of the number of bits of the factors
+    let square = (q as u128 - 1) * (q as u128 - 1);
+    match square.checked_mul(deg as u128 + 1) {
+        Some(bound) => num_bits(bound),
+        None => num_bits(square) + num_bits(deg as u128 + 1),
+    }
+}
+
+/// Capacity in bits of the prime field F, usable in constant expressions
+pub const fn field_capacity<F: ScalarField>() -> u32 {
+    F::CAPACITY
+}
+
+/// Validates the parameters of the ring R_q = Z_q[x]/(x^(deg+1) + 1) against a prime field of capacity `field_capacity` bits
+///
+/// * Same checks as `PolyParams::new`. This is a `const fn`, therefore it can be evaluated at compile time, e.g. by the `params!` macro
+pub const fn validate(deg: usize, q: u64, field_capacity: u32) -> Result<(), ParamsError> {
+    if !(deg + 1).is_power_of_two() {
+        return Err(ParamsError::InvalidDegree(deg));
+    }
+
+    if q < 2 {
+        return Err(ParamsError::InvalidModulus(q));
+    }
+
+    if num_bits(q as u128) > field_capacity {
+        return Err(ParamsError::ModulusExceedsField { q, field_capacity });
+    }
+
+    let bound_bits = mul_bound_bits(deg, q);
+
+    if bound_bits > field_capacity {
+        return Err(ParamsError::OverflowBound {
+            bound_bits,
+            field_capacity,
+        });
+    }
+
+    Ok(())
+}
+
+/// Number of bits of (q-1) * (q-1) * (deg+1), namely the `num_bits` to pass to `poly_reduce` after `poly_mul_equal_deg`
+pub const fn mul_num_bits(deg: usize, q: u64) -> usize {
+    mul_bound_bits(deg, q) as usize
+}
+
+/// Scaling factor Δ = Q/T rounded to the lower integer, by which BFV encryption multiplies the plaintext
+///
+/// * T is the plaintext modulus and Q the ciphertext modulus
+/// * Panics if T < 2 or T >= Q, namely if Δ would be smaller than 1 or the plaintext space would be trivial. In a constant context, e.g. `const DELTA: u64 = delta::<Q, T>();`, an invalid pair fails to compile
+/// * T does not need to divide Q: the scheme variant implemented by the BFV chips uses Δ = floor(Q/T) and absorbs the rounding error Q mod T in the noise
+pub const fn delta<const Q: u64, const T: u64>() -> u64 {
+    assert!(T >= 2, "the plaintext modulus T must be at least 2");
+    assert!(
+        T < Q,
+        "the plaintext modulus T must be smaller than the ciphertext modulus Q"
+    );
+
+    Q / T
+}
+
+impl PolyParams {
+    /// Validates the parameters of the ring against the prime field F of the circuit
+    ///
+    /// * deg + 1 must be a power of 2
+    /// * q must be at least 2 and smaller than the prime p of F
+    /// * (q-1) * (q-1) * (deg+1) must be smaller than p, such that the product of two polynomials with coefficients in [0, q - 1] does not overflow.
+    ///   It also covers the division of such a product by the cyclotomic polynomial x^(deg+1) + 1, whose bound is (q-1) * (deg+1).
+    ///   It does not cover chains of operations without intermediate reductions, e.g. the sum of several products, whose bound must be checked by the caller
+    pub fn new<F: ScalarField>(deg: usize, q: u64) -> Result<Self, ParamsError> {
+        let field_capacity = F::CAPACITY;
+
+        validate(deg, q, field_capacity)?;
+
+        Ok(PolyParams {
+            deg,
+            q,
+            field_capacity,
+        })
+    }
+
+    /// Degree of the polynomials of the ring
+    pub fn deg(&self) -> usize {
+        self.deg
+    }
+
+    /// Modulus of the coefficients
+    pub fn q(&self) -> u64 {
+        self.q
+    }
+
+    /// Capacity in bits of the prime field of the circuit
+    pub fn field_capacity(&self) -> u32 {
+        self.field_capacity
+    }
+
+    /// Number of bits of (q-1) * (q-1) * (deg+1), namely the `num_bits` to pass to `poly_reduce` after `poly_mul_equal_deg`
+    pub fn mul_num_bits(&self) -> usize {
+        mul_num_bits(self.deg, self.q)
+    }
+
+    /// Returns true if the parameters match the const generics DEG and Q of a chip
+    ///
+    /// * Chips are generic over DEG and Q, this allows to check once that they are called with a validated parameter set
+    pub fn matches<const DEG: usize, const Q: u64>(&self) -> bool {
+        self.deg == DEG && self.q == Q
+    }
+}
+
+/// Capacity in bits of the BN254 scalar field, the default field of the circuits
+const BN254_CAPACITY: u32 = field_capacity::<halo2_base::halo2_proofs::halo2curves::bn256::Fr>();
+
+/// Fixed BFV parameter set, validated at compile time
+///
+/// * `n` is the degree of the cyclotomic polynomial x^n + 1 and `deg` = n - 1 the degree of the polynomials of the ring, namely the `DEG` of the chips
+/// * `q` is the ciphertext modulus and `t` the plaintext modulus
+/// * `mul_num_bits` is the number of bits of (q-1) * (q-1) * n, namely the `num_bits` to pass to `poly_reduce` after `poly_mul_equal_deg`
+/// * `delta` is the scaling factor Δ = q/t rounded to the lower integer, see `delta`
+///
+/// The bundles below are built with `BfvParamSet::new` in a constant context, therefore an invalid bundle fails to compile
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BfvParamSet {
+    pub n: usize,
+    pub q: u64,
+    pub t: u64,
+    pub deg: usize,
+    pub mul_num_bits: usize,
+    pub delta: u64,
+}
+
+impl BfvParamSet {
+    /// Builds the parameter set (n, q, t) and precomputes `deg`, `mul_num_bits` and `delta`
+    ///
+    /// * The ring is validated with `validate` against the BN254 field: n must be a power of 2 and (q-1) * (q-1) * n must be smaller than the prime of the field.
+    ///   This bound also covers the precondition (q-1) * (n-1) + q-1 < p of `poly_divide_by_cyclo` on the product of two polynomials of the ring
+    /// * t must be at least 2 and smaller than q
+    /// * Panics if a check fails, which is a compilation error when called in a constant context
+    pub const fn new(n: usize, q: u64, t: u64) -> Self {
+        assert!(
+            n > 0,
+            "the degree n of the cyclotomic polynomial must be positive"
+        );
+        assert!(
+            validate(n - 1, q, BN254_CAPACITY).is_ok(),
+            "invalid parameter set: n must be a power of 2 and (q-1) * (q-1) * n must be smaller than the prime of the field"
+        );
+        assert!(
+            t >= 2 && t < q,
+            "the plaintext modulus t must be at least 2 and smaller than q"
+        );
+
+        BfvParamSet {
+            n,
+            q,
+            t,
+            deg: n - 1,
+            mul_num_bits: mul_num_bits(n - 1, q),
+            delta: q / t,
+        }
+    }
+}
+
+// The following bundles use a prime q = 1 mod 2n, which also supports the negacyclic NTT, below the bound on log2(q) of the HomomorphicEncryption.org security standard for 128 bits of classical security with a ternary secret key
+
+/// n = 1024 with a 27-bit q = 2^27 - 2^11 + 1 (the standard allows up to 27 bits) and t = 257
+pub const BFV_N1024_Q27BIT: BfvParamSet = BfvParamSet::new(1024, 134215681, 257);
+
+/// n = 2048 with a 54-bit q (the standard allows up to 54 bits) and t = 65537
+pub const BFV_N2048_Q54BIT: BfvParamSet = BfvParamSet::new(2048, 18014398509404161, 65537);
+
+/// n = 4096 with a 54-bit q (the standard allows up to 109 bits) and t = 65537
+pub const BFV_N4096_Q54BIT: BfvParamSet = BfvParamSet::new(4096, 18014398509309953, 65537);
+
+/// Operation of a chain of chips whose effect on the bound of the coefficients is tracked by `operation_bound`
+///
+/// * The operands are polynomials of the ring, namely of degree `deg` with coefficients in the range [0, q - 1], unless stated otherwise
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundOp {
+    /// Addition of a polynomial with coefficients in the range [0, q - 1], e.g. `poly_add`: the bound grows by q - 1
+    Add,
+    /// Multiplication by a polynomial with coefficients in the range [0, q - 1] and of degree `deg`, e.g. `poly_mul_equal_deg`: each coefficient is the sum of at most deg + 1 products, therefore the bound is multiplied by (q - 1) * (deg + 1)
+    Mul,
+    /// Multiplication by a polynomial with coefficients in the range [0, bound], where each coefficient of the product is the sum of at most `terms` products, e.g. `poly_mul_diff_deg`: the bound is multiplied by bound * terms
+    MulBy { bound: u64, terms: usize },
+    /// Multiplication by a scalar in the range [0, k], e.g. `poly_scalar_mul`: the bound is multiplied by k
+    ScalarMul(u64),
+    /// Reduction modulo q, e.g. `poly_reduce`: the bound becomes q - 1
+    Reduce,
+}
+
+/// Returns the worst-case bound of the coefficients at the end of a chain of operations, starting from a polynomial with coefficients in the range [0, q - 1]
+///
+/// * `ops` is applied in order, see `BoundOp` for the effect of each operation. `deg` is the degree of the polynomials of the ring
+/// * The bound is computed on `BigUint`, therefore it never overflows, and no constraint is synthesized. Every coefficient of the result is in the range [0, bound]
+/// * `bound.bits()` is the `num_bits` to pass to `poly_reduce` at the end of the chain, and the chain does not overflow the prime field of the circuit as long as `bound.bits()` is at most its capacity
+/// * For instance, the product quotient * divisor of `poly_divide_by_cyclo` followed by the addition of the remainder is `[MulBy { bound: 1, terms: DEG_DVD - DEG_DVS + 1 }, Add]`, with the bound (Q-1) * (DEG_DVD - DEG_DVS + 1) + Q-1
+pub fn operation_bound(ops: &[BoundOp], q: u64, deg: usize) -> BigUint {
+    assert!(q >= 2, "the modulus q must be at least 2");
+
+    let reduced = BigUint::from(q - 1);
+    let mut bound = reduced.clone();
+
+    for op in ops {
+        bound = match *op {
+            BoundOp::Add => bound + &reduced,
+            BoundOp::Mul => bound * &reduced * BigUint::from(deg as u64 + 1),
+            BoundOp::MulBy { bound: b, terms } => {
+                bound * BigUint::from(b) * BigUint::from(terms as u64)
+            }
+            BoundOp::ScalarMul(k) => bound * BigUint::from(k),
+            BoundOp::Reduce => reduced.clone(),
+        };
+    }
+
+    bound
+}
+
+/// Declares a fixed parameter set once, as constants validated at compile time
+///
+/// `params!(N = 1024, Q = 4637)` expands to the following constants in the current scope:
+///
+/// * `N`: degree of the cyclotomic polynomial x^N + 1. N must be a power of 2
+/// * `Q`: modulus of the ring R_q = Z_Q[x]/(x^N + 1)
+/// * `DEG`: degree N - 1 of the polynomials of the ring, namely the `DEG` of the ring chips
+/// * `DEG_DVD`: degree 2N - 2 of the product of two polynomials of the ring, namely the dividend of `poly_divide_by_cyclo`
+/// * `DEG_DVS`: degree N of the cyclotomic polynomial, namely the divisor of `poly_divide_by_cyclo`
+/// * `MUL_NUM_BITS`: number of bits of (Q-1) * (Q-1) * N, namely the `num_bits` to pass to `poly_reduce` after `poly_mul_equal_deg`
+/// * `Poly<F>`: alias of `Vec<AssignedValue<F>>`, the representation of a polynomial in the chips
+///
+/// The parameters are validated with `validate` against the field of the circuit, BN254 by default or the field given as `params!(N = .., Q = .., F = Fr)`.
+/// An invalid parameter set, e.g. one where (Q-1) * (Q-1) * N overflows the field, fails to compile
+#[macro_export]
+macro_rules! params {
+    (N = $n:expr, Q = $q:expr) => {
+        $crate::params!(
+            N = $n,
+            Q = $q,
+            F = $crate::chips::params::halo2_base::halo2_proofs::halo2curves::bn256::Fr
+        );
+    };
+    (N = $n:expr, Q = $q:expr, F = $f:ty) => {
+        pub const N: usize = $n;
+        pub const Q: u64 = $q;
+        pub const DEG: usize = N - 1;
+        pub const DEG_DVD: usize = 2 * N - 2;
+        pub const DEG_DVS: usize = N;
+        pub const MUL_NUM_BITS: usize = $crate::chips::params::mul_num_bits(DEG, Q);
+
+        pub type Poly<F> = Vec<$crate::chips::params::halo2_base::AssignedValue<F>>;
+
+        const _: () = assert!(
+            $crate::chips::params::validate(
+                DEG,
+                Q,
+                $crate::chips::params::field_capacity::<$f>()
+            )
+            .is_ok(),
+            "invalid parameter set: N must be a power of 2 and (Q-1) * (Q-1) * N must be smaller than the prime of the field"
+        );
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::utils::{fits_in_field, is_prime};
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn valid_params() {
+        let params = PolyParams::new::<Fr>(1023, 4637).unwrap();
+
+        assert_eq!(params.deg(), 1023);
+        assert_eq!(params.q(), 4637);
+        assert_eq!(params.field_capacity(), field_capacity::<Fr>());
+        assert!(params.matches::<1023, 4637>());
+        assert!(!params.matches::<1023, 4639>());
+        assert!(!params.matches::<511, 4637>());
+        // 4636 * 4636 * 1024 = 22008315904 needs 35 bits
+        assert_eq!(params.mul_num_bits(), 35);
+    }
+
+    #[test]
+    fn invalid_degree() {
+        for deg in [2, 1000, 1024] {
+            assert_eq!(
+                PolyParams::new::<Fr>(deg, 4637),
+                Err(ParamsError::InvalidDegree(deg))
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_modulus() {
+        for q in [0, 1] {
+            assert_eq!(
+                PolyParams::new::<Fr>(1023, q),
+                Err(ParamsError::InvalidModulus(q))
+            );
+        }
+    }
+
+    #[test]
+    fn modulus_exceeding_field() {
+        // No u64 modulus exceeds BN254, therefore the check is exercised against a field of 32 bits of capacity
+        assert_eq!(
+            validate(3, 1 << 40, 32),
+            Err(ParamsError::ModulusExceedsField {
+                q: 1 << 40,
+                field_capacity: 32
+            })
+        );
+    }
+
+    #[test]
+    fn overflow_bound() {
+        // (2^30 - 1) * (2^30 - 1) * 1024 needs 70 bits
+        assert_eq!(
+            validate(1023, 1 << 30, 64),
+            Err(ParamsError::OverflowBound {
+                bound_bits: 70,
+                field_capacity: 64
+            })
+        );
+        assert!(validate(1023, 1 << 30, 70).is_ok());
+    }
+
+    // The `Poly` alias expanded by `params!` is not used by the tests
+    #[allow(dead_code)]
+    mod bn254_params {
+        crate::params!(N = 1024, Q = 4637);
+    }
+
+    #[allow(dead_code)]
+    mod bn254_params_54bit {
+        crate::params!(N = 4096, Q = 18014398509309953, F = super::Fr);
+    }
+
+    #[test]
+    fn params_macro_constants_satisfy_the_overflow_bound() {
+        for (n, q, deg, deg_dvd, deg_dvs, mul_num_bits) in [
+            (
+                bn254_params::N,
+                bn254_params::Q,
+                bn254_params::DEG,
+                bn254_params::DEG_DVD,
+                bn254_params::DEG_DVS,
+                bn254_params::MUL_NUM_BITS,
+            ),
+            (
+                bn254_params_54bit::N,
+                bn254_params_54bit::Q,
+                bn254_params_54bit::DEG,
+                bn254_params_54bit::DEG_DVD,
+                bn254_params_54bit::DEG_DVS,
+                bn254_params_54bit::MUL_NUM_BITS,
+            ),
+        ] {
+            assert_eq!(deg, n - 1);
+            assert_eq!(deg_dvd, 2 * deg);
+            assert_eq!(deg_dvs, n);
+
+            // (Q-1) * (Q-1) * N fits in MUL_NUM_BITS bits, which fit in the field
+            let mul_bound = (q as u128 - 1) * (q as u128 - 1) * n as u128;
+            assert_eq!(mul_num_bits, format!("{:b}", mul_bound).len());
+            assert!(fits_in_field::<Fr>(mul_bound));
+            assert_eq!(
+                mul_num_bits,
+                PolyParams::new::<Fr>(deg, q).unwrap().mul_num_bits()
+            );
+
+            // Precondition of poly_divide_by_cyclo::<DEG_DVD, DEG_DVS, Q>
+            let division_bound =
+                (q as u128 - 1) * (deg_dvd - deg_dvs + 1) as u128 + (q as u128 - 1);
+            assert!(fits_in_field::<Fr>(division_bound));
+        }
+
+        // 4636 * 4636 * 1024 = 22008315904 needs 35 bits, and (Q-1) * (Q-1) * 4096 needs 120 bits for the 54-bit Q
+        assert_eq!(bn254_params::MUL_NUM_BITS, 35);
+        assert_eq!(bn254_params_54bit::MUL_NUM_BITS, 120);
+    }
+
+    #[test]
+    fn delta_of_several_moduli() {
+        // Evaluated at compile time
+        const DELTA: u64 = delta::<12289, 4>();
+        assert_eq!(DELTA, 3072);
+
+        assert_eq!(delta::<4637, 2>(), 2318);
+        assert_eq!(delta::<97, 96>(), 1);
+        // T divides Q
+        assert_eq!(delta::<1000, 10>(), 100);
+        assert_eq!(
+            delta::<18014398509309953, 65537>(),
+            18014398509309953 / 65537
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the plaintext modulus T must be at least 2")]
+    fn delta_rejects_small_plaintext_modulus() {
+        delta::<97, 1>();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the plaintext modulus T must be smaller than the ciphertext modulus Q"
+    )]
+    fn delta_rejects_plaintext_modulus_above_q() {
+        delta::<97, 97>();
+    }
+
+    #[test]
+    fn bfv_param_sets() {
+        for set in [BFV_N1024_Q27BIT, BFV_N2048_Q54BIT, BFV_N4096_Q54BIT] {
+            let params = PolyParams::new::<Fr>(set.deg, set.q).unwrap();
+            assert_eq!(set.deg, set.n - 1);
+            assert_eq!(set.mul_num_bits, params.mul_num_bits());
+
+            // (q-1) * (q-1) * n is computed on u128, the bundles are small enough for it not to overflow
+            let mul_bound = (set.q as u128 - 1) * (set.q as u128 - 1) * set.n as u128;
+            assert_eq!(set.mul_num_bits, format!("{:b}", mul_bound).len());
+
+            // Precondition of poly_divide_by_cyclo on the product of two polynomials of the ring
+            let division_bound = (set.q as u128 - 1) * (set.n as u128 - 1) + (set.q as u128 - 1);
+            assert!(fits_in_field::<Fr>(division_bound));
+
+            assert!(set.t >= 2 && set.t < set.q);
+            assert_eq!(set.delta, set.q / set.t);
+
+            // q is a prime congruent to 1 modulo 2n, which supports the negacyclic NTT
+            assert!(is_prime(set.q));
+            assert_eq!((set.q - 1) % (2 * set.n as u64), 0);
+        }
+    }
+
+    #[test]
+    fn operation_bound_of_chains() {
+        // Without operations, the coefficients are reduced
+        assert_eq!(operation_bound(&[], 97, 3), BigUint::from(96u64));
+
+        // quotient * divisor + remainder of poly_divide_by_cyclo with DEG_DVD - DEG_DVS + 1 = 3 terms
+        let division = [BoundOp::MulBy { bound: 1, terms: 3 }, BoundOp::Add];
+        assert_eq!(
+            operation_bound(&division, 97, 3),
+            BigUint::from(96u64 * 3 + 96)
+        );
+
+        // The product of two polynomials of the ring needs the `mul_num_bits` of the ring
+        let product = operation_bound(&[BoundOp::Mul], 97, 3);
+        assert_eq!(product, BigUint::from(96u64 * 96 * 4));
+        assert_eq!(product.bits() as usize, mul_num_bits(3, 97));
+
+        // A reduction resets the bound, whatever the operations before it
+        let chain = [
+            BoundOp::ScalarMul(5),
+            BoundOp::Mul,
+            BoundOp::Reduce,
+            BoundOp::Add,
+        ];
+        assert_eq!(operation_bound(&chain, 97, 3), BigUint::from(96u64 * 2));
+
+        // The bound is computed on BigUint and exceeds u128 without wrapping around
+        let q = u64::MAX - 58;
+        let bound = operation_bound(&[BoundOp::Mul, BoundOp::Mul], q, 1023);
+        assert_eq!(
+            bound,
+            BigUint::from(q - 1).pow(3) * BigUint::from(1024u64 * 1024)
+        );
+        assert_eq!(bound.bits(), 212);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::chips::error::{check_degree, PolyError};
 use halo2_base::gates::GateChip;
 use halo2_base::safe_types::GateInstructions;
 use halo2_base::safe_types::RangeChip;
@@ -12,6 +13,7 @@ use halo2_base::QuantumCell::Constant;
 /// * Namely, that the coefficients are in the range [0, B] OR [Q-B, Q-1]
 /// * DEG is the degree of the polynomial
 /// * Assumes that B < Q
+/// * Panics if a precondition on the inputs is not satisfied. See `try_check_poly_from_distribution_chi_error` for a version returning a `PolyError`
 pub fn check_poly_from_distribution_chi_error<
     const DEG: usize,
     const Q: u64,
@@ -22,8 +24,28 @@ pub fn check_poly_from_distribution_chi_error<
     a: Vec<AssignedValue<F>>,
     range: &RangeChip<F>,
 ) {
-    // assert that the degree of the polynomial a is equal to DEG
-    assert_eq!(a.len() - 1, DEG);
+    try_check_poly_from_distribution_chi_error::<DEG, Q, B, F>(ctx, a, range)
+        .unwrap_or_else(|err| panic!("check_poly_from_distribution_chi_error: {}", err))
+}
+
+/// Same as `check_poly_from_distribution_chi_error`, but returns an error instead of panicking if a precondition on the inputs is not satisfied
+///
+/// * Returns `PolyError::EmptyPolynomial` or `PolyError::DegreeMismatch` if the degree of a is not DEG
+pub fn try_check_poly_from_distribution_chi_error<
+    const DEG: usize,
+    const Q: u64,
+    const B: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Result<(), PolyError> {
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
+
+    // check that the degree of the polynomial a is equal to DEG
+    check_degree(&a, DEG)?;
 
     // The goal is to check that coeff is in the range [0, B] OR [Q-B, Q-1]
     // We split this check into two checks:
@@ -75,19 +97,40 @@ pub fn check_poly_from_distribution_chi_error<
         let bool = range.gate.is_equal(ctx, in_range, Constant(F::from(1)));
         range.gate.assert_is_const(ctx, &bool, &F::from(1));
     }
+
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("check_poly_from_distribution_chi_error", ctx, stats_start);
+
+    Ok(())
 }
 
 /// Enforce that polynomial a of degree DEG is sampled from the distribution chi key
 ///
 /// * Namely, that the coefficients are in the range [0, 1, Q-1].
 /// * DEG is the degree of the polynomial
+/// * Panics if a precondition on the inputs is not satisfied. See `try_check_poly_from_distribution_chi_key` for a version returning a `PolyError`
 pub fn check_poly_from_distribution_chi_key<const DEG: usize, const Q: u64, F: ScalarField>(
     ctx: &mut Context<F>,
     a: Vec<AssignedValue<F>>,
     gate: &GateChip<F>,
 ) {
-    // assert that the degree of the polynomial a is equal to DEG
-    assert_eq!(a.len() - 1, DEG);
+    try_check_poly_from_distribution_chi_key::<DEG, Q, F>(ctx, a, gate)
+        .unwrap_or_else(|err| panic!("check_poly_from_distribution_chi_key: {}", err))
+}
+
+/// Same as `check_poly_from_distribution_chi_key`, but returns an error instead of panicking if a precondition on the inputs is not satisfied
+///
+/// * Returns `PolyError::EmptyPolynomial` or `PolyError::DegreeMismatch` if the degree of a is not DEG
+pub fn try_check_poly_from_distribution_chi_key<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    gate: &GateChip<F>,
+) -> Result<(), PolyError> {
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
+
+    // check that the degree of the polynomial a is equal to DEG
+    check_degree(&a, DEG)?;
 
     // In order to check that coeff is equal to either 0, 1 or q-1
     // The constraint that we want to enforce is:
@@ -114,4 +157,105 @@ pub fn check_poly_from_distribution_chi_key<const DEG: usize, const Q: u64, F: S
         let bool = gate.is_zero(ctx, factor_1_2_3);
         gate.assert_is_const(ctx, &bool, &F::from(1));
     }
+
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("check_poly_from_distribution_chi_key", ctx, stats_start);
+
+    Ok(())
+}
+
+/// Enforce that the histogram of the coefficients of polynomial a of degree DEG falls within the expected ranges, and return the histogram
+///
+/// * For each value v in [-B, B], the number of coefficients equal to v is counted and enforced to be in the range [bounds[v + B].0, bounds[v + B].1]
+/// * The negative value -v is represented by the coefficient Q - v. The total count is enforced to be DEG + 1, namely every coefficient is in the range [0, B] OR [Q-B, Q-1]
+/// * Returns the 2B + 1 counts, where counts[v + B] is the number of coefficients equal to v
+/// * LIMITATION: this is a coarse distributional guarantee, NOT a statistical test. It proves that the counts are within the given ranges, e.g. the expected counts of a discrete Gaussian plus or minus a few standard deviations, but says nothing about the independence of the coefficients or about their positions
+/// * DEG is the degree of the polynomial
+/// * Assumes that B < Q/2 and that bounds[i].0 <= bounds[i].1 <= DEG + 1
+pub fn check_coeff_histogram<const DEG: usize, const Q: u64, const B: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    bounds: &[(u64, u64)],
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    // assert that there is one range per value in [-B, B]
+    assert_eq!(bounds.len() as u64, 2 * B + 1);
+    assert!(B < Q / 2);
+
+    let mut counts = Vec::with_capacity(bounds.len());
+
+    for (i, (lo, hi)) in bounds.iter().enumerate() {
+        assert!(lo <= hi && *hi <= DEG as u64 + 1);
+
+        // The value v = i - B is represented by v if v >= 0 and by Q + v otherwise
+        let value = if i as u64 >= B {
+            i as u64 - B
+        } else {
+            Q - (B - i as u64)
+        };
+
+        // Count the coefficients equal to value. The count is in the range [0, DEG + 1]
+        let matches = a
+            .iter()
+            .map(|coeff| range.gate.is_equal(ctx, *coeff, Constant(F::from(value))))
+            .collect::<Vec<_>>();
+        let count = range.gate.sum(ctx, matches);
+
+        // Enforce that count is in the range [lo, hi], namely count - lo is in the range [0, hi - lo]
+        // If count < lo, count - lo wraps around to a value close to p, which is rejected by the range check of the `check_less_than_safe` chip
+        let shifted = range.gate.sub(ctx, count, Constant(F::from(*lo)));
+        range.check_less_than_safe(ctx, shifted, hi - lo + 1);
+
+        counts.push(count);
+    }
+
+    // Enforce that every coefficient has been counted, namely every coefficient is in the range [-B, B]
+    let total = range.gate.sum(ctx, counts.clone());
+    range
+        .gate
+        .assert_is_const(ctx, &total, &F::from(DEG as u64 + 1));
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::test_utils::{load_poly, mock_run};
+    use crate::chips::utils::vec_assigned_to_vec_u64;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn check_coeff_histogram_counts_and_bounds() {
+        // Counts of the values -2, -1, 0, 1, 2 are 1, 1, 3, 2, 1
+        let a = [0, 1, 96, 0, 2, 95, 0, 1];
+        let bounds = [(0, 2), (0, 2), (2, 5), (1, 3), (0, 2)];
+
+        let check = |a: &[u64], bounds: &[(u64, u64)]| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, a);
+                let counts = check_coeff_histogram::<7, 97, 2, Fr>(ctx, a, bounds, range);
+                vec_assigned_to_vec_u64(&counts)
+            })
+        };
+
+        let (counts, satisfied) = check(&a, &bounds);
+        assert!(satisfied);
+        assert_eq!(counts, vec![1, 1, 3, 2, 1]);
+
+        // Too many zeroes
+        let (_, satisfied) = check(&a, &[(0, 2), (0, 2), (0, 2), (1, 3), (0, 2)]);
+        assert!(!satisfied);
+
+        // Too few coefficients equal to 2
+        let (_, satisfied) = check(&a, &[(0, 2), (0, 2), (2, 5), (1, 3), (2, 3)]);
+        assert!(!satisfied);
+
+        // The coefficient 3 is outside of [-B, B] and is not counted
+        let (_, satisfied) = check(&[0, 1, 96, 0, 3, 95, 0, 1], &bounds);
+        assert!(!satisfied);
+    }
 }
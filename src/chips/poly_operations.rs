@@ -1,4 +1,10 @@
-use crate::chips::utils::{div_euclid, vec_assigned_to_vec_u64};
+use crate::chips::error::{check_bound, check_degree, PolyError};
+use crate::chips::labels::label_poly;
+use crate::chips::ntt::to_eval;
+use crate::chips::params::PolyParams;
+use crate::chips::utils::{
+    div_by_cyclo_witness, fits_in_capacity, fits_in_field, inverse_mod_xk, vec_assigned_to_vec_u64,
+};
 use halo2_base::gates::GateChip;
 use halo2_base::gates::GateInstructions;
 use halo2_base::safe_types::RangeChip;
@@ -13,15 +19,31 @@ use halo2_base::QuantumCell;
 /// * DEG is the degree of the input polynomials
 /// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
 /// * It assumes that the coefficients are constrained such to overflow during the polynomial addition
+/// * Panics if a precondition on the inputs is not satisfied. See `try_poly_add` for a version returning a `PolyError`
 pub fn poly_add<const DEG: usize, F: ScalarField>(
     ctx: &mut Context<F>,
-    a: Vec<AssignedValue<F>>,
-    b: Vec<AssignedValue<F>>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
     gate: &GateChip<F>,
 ) -> Vec<AssignedValue<F>> {
-    // assert that the input polynomials have the same degree and this is equal to DEG
-    assert_eq!(a.len() - 1, b.len() - 1);
-    assert_eq!(a.len() - 1, DEG);
+    try_poly_add::<DEG, F>(ctx, a, b, gate).unwrap_or_else(|err| panic!("poly_add: {}", err))
+}
+
+/// Same as `poly_add`, but returns an error instead of panicking if a precondition on the inputs is not satisfied
+///
+/// * Returns `PolyError::EmptyPolynomial` or `PolyError::DegreeMismatch` if the degree of a or b is not DEG
+pub fn try_poly_add<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    gate: &GateChip<F>,
+) -> Result<Vec<AssignedValue<F>>, PolyError> {
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
+
+    // check that the input polynomials have degree DEG
+    check_degree(a, DEG)?;
+    check_degree(b, DEG)?;
 
     let mut c = vec![];
 
@@ -33,7 +55,42 @@ pub fn poly_add<const DEG: usize, F: ScalarField>(
     // assert that the sum polynomial has degree DEG
     assert_eq!(c.len() - 1, DEG);
 
-    c
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("poly_add", ctx, stats_start);
+
+    Ok(c)
+}
+
+/// Build the sum of the polynomials a, b and c as sum of the coefficients in a single pass
+///
+/// * Compared to two chained `poly_add` calls, the three coefficients are summed with a single `sum` gate and no intermediate polynomial is allocated
+/// * DEG is the degree of the input polynomials
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * If the coefficients of a, b and c are bounded by a_bound, b_bound and c_bound, the coefficients of the sum are bounded by a_bound + b_bound + c_bound
+/// * It assumes that a_bound + b_bound + c_bound < p where p is the prime field of the circuit
+pub fn poly_add3<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    c: &[AssignedValue<F>],
+    gate: &GateChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the input polynomials have the same degree and this is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+    assert_eq!(b.len() - 1, DEG);
+    assert_eq!(c.len() - 1, DEG);
+
+    let mut d = vec![];
+
+    for i in 0..=DEG {
+        let val = gate.sum(ctx, [a[i], b[i], c[i]]);
+        d.push(val);
+    }
+
+    // assert that the sum polynomial has degree DEG
+    assert_eq!(d.len() - 1, DEG);
+
+    d
 }
 
 /// Build the product of the polynomials a and b as dot product of the coefficients of a and b
@@ -42,15 +99,32 @@ pub fn poly_add<const DEG: usize, F: ScalarField>(
 /// * DEG is the degree of the input polynomials
 /// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
 /// * It assumes that the coefficients are constrained such to overflow during the polynomial multiplication
+/// * Panics if a precondition on the inputs is not satisfied. See `try_poly_mul_equal_deg` for a version returning a `PolyError`
 pub fn poly_mul_equal_deg<const DEG: usize, F: ScalarField>(
     ctx: &mut Context<F>,
-    a: Vec<AssignedValue<F>>,
-    b: Vec<AssignedValue<F>>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
     gate: &GateChip<F>,
 ) -> Vec<AssignedValue<F>> {
-    // assert that the input polynomials have the same degree and this is equal to DEG
-    assert_eq!(a.len() - 1, b.len() - 1);
-    assert_eq!(a.len() - 1, DEG);
+    try_poly_mul_equal_deg::<DEG, F>(ctx, a, b, gate)
+        .unwrap_or_else(|err| panic!("poly_mul_equal_deg: {}", err))
+}
+
+/// Same as `poly_mul_equal_deg`, but returns an error instead of panicking if a precondition on the inputs is not satisfied
+///
+/// * Returns `PolyError::EmptyPolynomial` or `PolyError::DegreeMismatch` if the degree of a or b is not DEG
+pub fn try_poly_mul_equal_deg<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    gate: &GateChip<F>,
+) -> Result<Vec<AssignedValue<F>>, PolyError> {
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
+
+    // check that the input polynomials have degree DEG
+    check_degree(a, DEG)?;
+    check_degree(b, DEG)?;
 
     let mut c = vec![];
 
@@ -71,9 +145,12 @@ pub fn poly_mul_equal_deg<const DEG: usize, F: ScalarField>(
             }
         }
 
+        // The accumulator starts from the constant zero. A witness would be a free advice cell that the prover could set to any value
         let c_val = coefficient_accumaltor
             .iter()
-            .fold(ctx.load_witness(F::zero()), |acc, x| gate.add(ctx, acc, *x));
+            .fold(ctx.load_constant(F::zero()), |acc, x| {
+                gate.add(ctx, acc, *x)
+            });
 
         c.push(c_val);
     }
@@ -81,22 +158,168 @@ pub fn poly_mul_equal_deg<const DEG: usize, F: ScalarField>(
     // assert that the product polynomial has degree 2*DEG
     assert_eq!(c.len() - 1, 2 * DEG);
 
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("poly_mul_equal_deg", ctx, stats_start);
+
+    Ok(c)
+}
+
+/// Build the product of the polynomials a and b together with an upper bound on the coefficients of the product
+///
+/// * Returns (c, c_bound) where c is the output of `poly_mul_equal_deg` and c_bound = a_bound * b_bound * (DEG + 1)
+/// * The coefficient c_k is the sum of at most DEG + 1 products a[i] * b[k - i], each of them being at most a_bound * b_bound
+/// * c_bound can be used to compute the `num_bits` required by `poly_reduce` on the product
+/// * DEG is the degree of the input polynomials
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of a are in the range [0, a_bound] and the coefficients of b are in the range [0, b_bound]
+/// * Panics if c_bound does not fit in a u64
+/// * It assumes that c_bound < p where p is the prime field of the circuit
+pub fn poly_mul_bounded<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    a_bound: u64,
+    b_bound: u64,
+    gate: &GateChip<F>,
+) -> (Vec<AssignedValue<F>>, u64) {
+    let c_bound = a_bound
+        .checked_mul(b_bound)
+        .and_then(|bound| bound.checked_mul(DEG as u64 + 1))
+        .expect("coefficient bound of the product overflows u64");
+
+    let c = poly_mul_equal_deg::<DEG, F>(ctx, a, b, gate);
+
+    (c, c_bound)
+}
+
+/// Build acc + a * b, accumulating the product of the polynomials a and b into the polynomial acc
+///
+/// * Compared to `poly_add(acc, poly_mul_equal_deg(a, b))`, each product a[i] * b[k - i] is accumulated into acc_k with a single `mul_add` gate. No separate addition pass over the product coefficients is needed
+/// * DEG is the degree of the input polynomials a and b. acc is of degree 2*DEG, namely the degree of the product
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * If the coefficients of acc, a and b are bounded by acc_bound, a_bound and b_bound, the coefficients of the output are bounded by acc_bound + a_bound * b_bound * (DEG + 1)
+/// * It assumes that the coefficients are constrained such to overflow during the polynomial multiplication and accumulation
+pub fn poly_mul_accumulate<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    acc: &[AssignedValue<F>],
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    gate: &GateChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the input polynomials have the same degree and this is equal to DEG
+    assert_eq!(a.len() - 1, b.len() - 1);
+    assert_eq!(a.len() - 1, DEG);
+
+    // assert that the accumulator has degree 2*DEG
+    assert_eq!(acc.len() - 1, 2 * DEG);
+
+    let mut c = vec![];
+
+    for k in 0..(2 * DEG + 1) {
+        // c_k = acc_k + sum of a[i] * b[k - i]
+        let mut c_val = acc[k];
+        for i in k.saturating_sub(DEG)..=k.min(DEG) {
+            c_val = gate.mul_add(ctx, a[i], b[k - i], c_val);
+        }
+        c.push(c_val);
+    }
+
+    // assert that the output polynomial has degree 2*DEG
+    assert_eq!(c.len() - 1, 2 * DEG);
+
     c
 }
 
+/// Source of the coefficients of a polynomial, either a dense vector of assigned coefficients or a closure returning the coefficients on demand
+///
+/// * Coefficients are indexed as in a dense vector [a_n, a_n-1, ..., a_1, a_0] where a_0 is the constant term and n is the degree of the polynomial, namely `get(i)` is the coefficient of x^(n - i)
+/// * A closure avoids materializing the mostly constant coefficients of sparse or structured polynomials, e.g. the cyclotomic polynomial x^N + 1 (see `CoeffSource::cyclotomic`)
+/// * Coefficients returned as `QuantumCell::Constant(F::zero())` are known to be zero and the chips accepting a `CoeffSource` skip the corresponding terms
+pub enum CoeffSource<'a, F: ScalarField> {
+    Dense(&'a [AssignedValue<F>]),
+    Lazy {
+        deg: usize,
+        coeff: Box<dyn Fn(usize) -> QuantumCell<F> + 'a>,
+    },
+}
+
+impl<'a, F: ScalarField> CoeffSource<'a, F> {
+    /// Builds a source of degree `deg` whose i-th coefficient is `coeff(i)`
+    pub fn from_fn(deg: usize, coeff: impl Fn(usize) -> QuantumCell<F> + 'a) -> Self {
+        CoeffSource::Lazy {
+            deg,
+            coeff: Box::new(coeff),
+        }
+    }
+
+    /// Builds the cyclotomic polynomial x^N + 1 as a source of constant coefficients [1, 0, ..., 0, 1]
+    ///
+    /// * Compared to `cyclotomic_divisor`, no cell is assigned and the N - 1 zero coefficients are skipped during the multiplication
+    pub fn cyclotomic<const N: usize>() -> Self {
+        Self::from_fn(N, |i| {
+            if i == 0 || i == N {
+                QuantumCell::Constant(F::one())
+            } else {
+                QuantumCell::Constant(F::zero())
+            }
+        })
+    }
+
+    /// Returns the degree of the polynomial
+    pub fn deg(&self) -> usize {
+        match self {
+            CoeffSource::Dense(coeffs) => coeffs.len() - 1,
+            CoeffSource::Lazy { deg, .. } => *deg,
+        }
+    }
+
+    /// Returns the i-th coefficient, namely the coefficient of x^(deg - i)
+    pub fn get(&self, i: usize) -> QuantumCell<F> {
+        assert!(i <= self.deg());
+        match self {
+            CoeffSource::Dense(coeffs) => QuantumCell::Existing(coeffs[i]),
+            CoeffSource::Lazy { coeff, .. } => coeff(i),
+        }
+    }
+}
+
+impl<'a, F: ScalarField> From<&'a [AssignedValue<F>]> for CoeffSource<'a, F> {
+    fn from(coeffs: &'a [AssignedValue<F>]) -> Self {
+        CoeffSource::Dense(coeffs)
+    }
+}
+
+impl<'a, F: ScalarField> From<&'a Vec<AssignedValue<F>>> for CoeffSource<'a, F> {
+    fn from(coeffs: &'a Vec<AssignedValue<F>>) -> Self {
+        CoeffSource::Dense(coeffs)
+    }
+}
+
+/// Returns true if the coefficient is the constant zero, namely the term can be skipped
+fn is_known_zero<F: ScalarField>(coeff: &QuantumCell<F>) -> bool {
+    matches!(coeff, QuantumCell::Constant(c) if *c == F::zero())
+}
+
 /// Build the product of the polynomials a and b as dot product of the coefficients of a and b
 ///
 /// * Compared to `poly_mul_equal_deg`, this function doesn't assume that the polynomials have the same degree. Therefore the computation is less efficient.
 /// * Input polynomials are parsed as a vector of assigned coefficients [a_n, a_n-1, ..., a_1, a_0] where a_0 is the constant term and n is the degree of the polynomial
+/// * `a` and `b` are `CoeffSource`s, therefore either dense vectors of assigned coefficients or closures. Products involving a coefficient that is the constant zero are skipped
 /// * It assumes that the coefficients are constrained such to overflow during the polynomial multiplication
-pub fn poly_mul_diff_deg<F: ScalarField>(
+pub fn poly_mul_diff_deg<'a, F: ScalarField>(
     ctx: &mut Context<F>,
-    a: Vec<AssignedValue<F>>,
-    b: Vec<AssignedValue<F>>,
+    a: impl Into<CoeffSource<'a, F>>,
+    b: impl Into<CoeffSource<'a, F>>,
     gate: &GateChip<F>,
 ) -> Vec<AssignedValue<F>> {
-    let a_deg = a.len() - 1;
-    let b_deg = b.len() - 1;
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
+
+    let a = a.into();
+    let b = b.into();
+
+    let a_deg = a.deg();
+    let b_deg = b.deg();
     let c_deg = a_deg + b_deg;
 
     let mut c = vec![];
@@ -106,17 +329,25 @@ pub fn poly_mul_diff_deg<F: ScalarField>(
 
         for j in 0..=i {
             if j <= a_deg && (i - j) <= b_deg {
-                let a_coef = a[j];
-                let b_coef = b[i - j];
+                let a_coef = a.get(j);
+                let b_coef = b.get(i - j);
+
+                // Skip the terms that are known to be zero
+                if is_known_zero(&a_coef) || is_known_zero(&b_coef) {
+                    continue;
+                }
 
                 // Update the accumulator
                 coefficient_accumaltor.push(gate.mul(ctx, a_coef, b_coef));
             }
         }
 
+        // The accumulator starts from the constant zero, such that a coefficient whose terms are all skipped is constrained to zero
         let c_val = coefficient_accumaltor
             .iter()
-            .fold(ctx.load_witness(F::zero()), |acc, x| gate.add(ctx, acc, *x));
+            .fold(ctx.load_constant(F::zero()), |acc, x| {
+                gate.add(ctx, acc, *x)
+            });
 
         c.push(c_val);
     }
@@ -124,247 +355,4860 @@ pub fn poly_mul_diff_deg<F: ScalarField>(
     // assert that the product polynomial has degree c_deg
     assert_eq!(c.len() - 1, c_deg);
 
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("poly_mul_diff_deg", ctx, stats_start);
+
     c
 }
 
-/// Build the scalar multiplication of the polynomials a and the scalar k as scalar multiplication of the coefficients of a and k
+/// Build the square of the polynomial a
 ///
-/// * DEG is the degree of the polynomial
+/// * Compared to `poly_mul_equal_deg(a, a)`, this function exploits the symmetry a[i] * a[j] = a[j] * a[i].
+/// * Each distinct product a[i] * a[j] with i < j is computed once and doubled, while the diagonal product a[i] * a[i] is added once. This roughly halves the number of multiplications
+/// * DEG is the degree of the input polynomial
 /// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
-/// * It assumes that the coefficients are constrained such to overflow during the scalar multiplication
-pub fn poly_scalar_mul<const DEG: usize, F: ScalarField>(
+/// * It assumes that the coefficients are constrained such to overflow during the polynomial multiplication
+pub fn poly_square<const DEG: usize, F: ScalarField>(
     ctx: &mut Context<F>,
     a: Vec<AssignedValue<F>>,
-    b: QuantumCell<F>,
     gate: &GateChip<F>,
 ) -> Vec<AssignedValue<F>> {
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
+
     // assert that the degree of the polynomial a is equal to DEG
     assert_eq!(a.len() - 1, DEG);
 
     let mut c = vec![];
 
-    for i in 0..=DEG {
-        let val = gate.mul(ctx, a[i], b);
-        c.push(val);
+    for k in 0..(2 * DEG + 1) {
+        // Accumulate the products a[i] * a[k - i] for i < k - i
+        let mut acc = QuantumCell::Constant(F::zero());
+        let mut i = k.saturating_sub(DEG);
+        while 2 * i < k {
+            acc = QuantumCell::Existing(gate.mul_add(ctx, a[i], a[k - i], acc));
+            i += 1;
+        }
+
+        // The diagonal product a[k/2] * a[k/2] only appears when k is even
+        let diag = if k % 2 == 0 {
+            QuantumCell::Existing(gate.mul(ctx, a[k / 2], a[k / 2]))
+        } else {
+            QuantumCell::Constant(F::zero())
+        };
+
+        // c_k = 2 * acc + diag
+        let c_val = gate.mul_add(ctx, acc, QuantumCell::Constant(F::from(2)), diag);
+        c.push(c_val);
     }
 
-    // assert that the product polynomial has degree DEG
-    assert_eq!(c.len() - 1, DEG);
+    // assert that the product polynomial has degree 2*DEG
+    assert_eq!(c.len() - 1, 2 * DEG);
+
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("poly_square", ctx, stats_start);
 
     c
 }
 
-/// Takes a polynomial represented by its coefficients in a vector and output a new polynomial reduced by applying modulo Q to each coefficient
+/// Build the difference of squares a^2 - b^2 = (a + b) * (a - b) with coefficients reduced modulo Q
 ///
-/// * DEG is the degree of the polynomial
-/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
-/// * It assumes that the coefficients of the input polynomial can be expressed in at most num_bits bits
-pub fn poly_reduce<const DEG: usize, const Q: u64, F: ScalarField>(
+/// * Computes a^2 and b^2 with `poly_square` instead of multiplying (a + b) by (a - b) with `poly_mul_equal_deg`
+/// * `poly_mul_equal_deg` costs about 2 * (DEG + 1)^2 gates (one `mul` and one `add` per product), while each `poly_square` costs about (DEG + 1)^2 / 2 gates. Therefore this is roughly twice cheaper than the general product, plus the 2 * DEG + 1 subtractions and reductions shared by both approaches
+/// * It also avoids computing a - b, whose coefficients can be negative and would need to be shifted before the multiplication
+/// * DEG is the degree of the input polynomials
+/// * Q is the modulus of the coefficients of the output polynomial, which has degree 2*DEG. It is not reduced modulo the cyclotomic polynomial
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the input polynomials are in the range [0, Q - 1]
+pub fn poly_diff_of_squares<const DEG: usize, const Q: u64, F: ScalarField>(
     ctx: &mut Context<F>,
-    input: Vec<AssignedValue<F>>,
+    a: Vec<AssignedValue<F>>,
+    b: Vec<AssignedValue<F>>,
     range: &RangeChip<F>,
-    num_bits: usize,
 ) -> Vec<AssignedValue<F>> {
-    // Assert that degree of input polynomial is equal to the constant DEG
-    assert_eq!(input.len() - 1, DEG);
+    // assert that the degree of the input polynomials is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+    assert_eq!(b.len() - 1, DEG);
 
-    let mut rem_assigned = vec![];
+    // The coefficients of a^2 and b^2 are in the range [0, (Q-1) * (Q-1) * (DEG+1)]
+    // To keep the difference non negative, a multiple of Q greater than the coefficients of b^2 is added: offset = Q * (Q-1) * (DEG+1)
+    // The coefficients of a^2 + offset - b^2 are in the range [0, (Q-1) * (Q-1) * (DEG+1) + Q * (Q-1) * (DEG+1)]
+    let offset = (Q as u128 * (Q as u128 - 1))
+        .checked_mul(DEG as u128 + 1)
+        .expect("coefficient bound of the difference overflows u128");
+    let bound = (Q as u128 - 1)
+        .checked_mul(Q as u128 - 1)
+        .and_then(|bound| bound.checked_mul(DEG as u128 + 1))
+        .and_then(|bound| bound.checked_add(offset))
+        .expect("coefficient bound of the difference overflows u128");
 
-    // Enforce that in_assigned[i] % Q = rem_assigned[i]
-    for i in 0..=DEG {
-        let rem = range.div_mod(ctx, input[i], Q, num_bits).1;
-        rem_assigned.push(rem);
+    // The bound must be less than p in order for the coefficients not to overflow
+    assert!(fits_in_field::<F>(bound));
+
+    // get the number of bits needed to represent the bound
+    let binary_representation = format!("{:b}", bound);
+    let num_bits = binary_representation.len();
+
+    let offset_fe = F::from(Q) * F::from(Q - 1) * F::from(DEG as u64 + 1);
+
+    let a_square = poly_square::<DEG, F>(ctx, a, range.gate());
+    let b_square = poly_square::<DEG, F>(ctx, b, range.gate());
+
+    let mut c = vec![];
+
+    for i in 0..(2 * DEG + 1) {
+        let shifted = range
+            .gate()
+            .add(ctx, a_square[i], QuantumCell::Constant(offset_fe));
+        let diff = range.gate().sub(ctx, shifted, b_square[i]);
+
+        // Enforce that diff % Q = c[i]
+        let rem = range.div_mod(ctx, diff, Q, num_bits).1;
+        c.push(rem);
     }
 
-    // assert that the reduced polynomial has degree DEG
-    assert_eq!(rem_assigned.len() - 1, DEG);
+    // assert that the output polynomial has degree 2*DEG
+    assert_eq!(c.len() - 1, 2 * DEG);
 
-    rem_assigned
+    c
 }
 
-/// Takes a polynomial `divisor` represented by its coefficients in a vector.
-/// Takes a cyclotomic polynomial `dividend` f(x)=x^m+1 (m is a power of 2) of the form represented by its coefficients in a vector
-/// Output the remainder of the division of `dividend` by `dividend` as a vector of coefficients
+/// Build the product of the polynomial `dense` and a sparse constant polynomial, with coefficients reduced modulo Q
 ///
-/// * DEG_DVD is the degree of the `dividend` polynomial
-/// * DEG_DVS is the degree of the `divisor` polynomial
-/// * Q is the modulus of the Ring
-/// * Input polynomials is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
-/// * Assumes that the degree of dividend is equal to (2 * DEG_DVS) - 2
-/// * Assumes that the coefficients of `dividend` are in the range [0, Q - 1]
-/// * Assumes that divisor is a cyclotomic polynomial with coefficients either 0 or 1
-/// * Assumes that dividend and divisor can be expressed as u64 values
-/// * Assumes that Q is chosen such that (Q-1) * (DEG_DVD - DEG_DVS + 1)] + Q-1 < p where p is the prime field of the circuit in order to avoid overflow during the multiplication
-pub fn poly_divide_by_cyclo<
-    const DEG_DVD: usize,
-    const DEG_DVS: usize,
-    const Q: u64,
-    F: ScalarField,
->(
+/// * The sparse polynomial is given as a list of (exponent, coefficient) pairs, namely `(e, v)` stands for the term v * x^e. Exponents that are not listed have a zero coefficient
+/// * The coefficients of the sparse polynomial are known at synthesis time, therefore a multiplication gate is only emitted for each nonzero term, namely (number of nonzero terms) * (degree of dense + 1) gates instead of the (DEG + 1)^2 of a dense product
+/// * The output coefficients that receive no term are assigned as the constant zero and are not reduced
+/// * Input polynomial `dense` is parsed as a vector of assigned coefficients [a_n, a_n-1, ..., a_1, a_0] where a_0 is the constant term and n is the degree of the polynomial
+/// * The output polynomial has degree n + e_max, where e_max is the largest exponent of the sparse polynomial
+/// * Assumes that the coefficients of `dense` and the coefficients of the sparse polynomial are in the range [0, Q - 1]
+pub fn poly_mul_sparse<const Q: u64, F: ScalarField>(
     ctx: &mut Context<F>,
-    dividend: Vec<AssignedValue<F>>,
-    divisor: Vec<AssignedValue<F>>,
+    dense: Vec<AssignedValue<F>>,
+    sparse_const: &[(usize, F)],
     range: &RangeChip<F>,
 ) -> Vec<AssignedValue<F>> {
-    // Assert that degree of dividend polynomial is equal to the constant DEG_DVD
-    assert_eq!(dividend.len() - 1, DEG_DVD);
-    // Assert that degree of divisor poly is equal to the constant DEG_DVS
-    assert_eq!(divisor.len() - 1, DEG_DVS);
-    // Assert that degree of dividend is equal to (2 * DEG_DVS) - 2
-    assert_eq!(dividend.len() - 1, (2 * DEG_DVS) - 2);
+    // Zero terms are skipped entirely
+    let terms = sparse_const
+        .iter()
+        .filter(|(_, v)| *v != F::zero())
+        .collect::<Vec<_>>();
 
-    // DEG_DVS must be strictly less than DEG_DVD
-    assert!(DEG_DVS < DEG_DVD);
+    assert!(
+        !terms.is_empty(),
+        "poly_mul_sparse: the sparse polynomial is zero"
+    );
 
-    // long division operation performed outside the circuit
-    // Need to convert the dividend and divisor into a vector of u64
-    let dividend_to_u64 = vec_assigned_to_vec_u64(&dividend);
-    let divisor_to_u64 = vec_assigned_to_vec_u64(&divisor);
+    let dense_deg = dense.len() - 1;
+    let sparse_deg = terms.iter().map(|(e, _)| *e).max().unwrap();
+    let c_deg = dense_deg + sparse_deg;
 
-    let (quotient_to_u64, remainder_to_u64) =
-        div_euclid::<DEG_DVD, DEG_DVS, Q>(&dividend_to_u64, &divisor_to_u64);
+    // COEFFICIENTS OVERFLOW ANALYSIS
+    // Each coefficient of the product is the sum of at most `terms.len()` products dense[j] * v, each of them being at most (Q-1) * (Q-1)
+    // Therefore, the coefficients of the product are in the range [0, (Q-1) * (Q-1) * terms.len()]
+    let bound = ((Q as u128 - 1) * (Q as u128 - 1))
+        .checked_mul(terms.len() as u128)
+        .expect("coefficient bound of the product overflows u128");
+    assert!(
+        fits_in_field::<F>(bound),
+        "poly_mul_sparse: the coefficients of the product may overflow the prime field of the circuit"
+    );
+
+    // get the number of bits needed to represent the bound
+    let binary_representation = format!("{:b}", bound);
+    let num_bits = binary_representation.len();
+
+    let mut c = vec![];
 
-    // After the division, the degree of the quotient should be equal to DEG_DVD - DEG_DVS
-    assert_eq!(quotient_to_u64.len() - 1, DEG_DVD - DEG_DVS);
+    // c[idx] is the coefficient of x^(c_deg - idx)
+    for idx in 0..=c_deg {
+        let k = c_deg - idx;
 
-    // Furthermore, the degree of the remainder must be strictly less than the degree of the divisor
-    assert!(remainder_to_u64.len() - 1 < DEG_DVS);
+        let mut acc: Option<AssignedValue<F>> = None;
 
-    // Pad the remainder with 0s to make its degree equal to DEG_DVS - 1
-    let mut remainder_to_u64 = remainder_to_u64;
-    while remainder_to_u64.len() - 1 < DEG_DVS - 1 {
-        remainder_to_u64.push(0);
-    }
+        for (e, v) in &terms {
+            // the term v * x^e contributes dense coefficient of x^(k - e) to x^k
+            if *e <= k && k - e <= dense_deg {
+                let dense_coef = dense[dense_deg - (k - e)];
+                let prev = match acc {
+                    Some(acc) => QuantumCell::Existing(acc),
+                    None => QuantumCell::Constant(F::zero()),
+                };
+                acc = Some(
+                    range
+                        .gate()
+                        .mul_add(ctx, dense_coef, QuantumCell::Constant(*v), prev),
+                );
+            }
+        }
 
-    // Now remainder must be of degree DEG_DVS - 1
-    assert_eq!(remainder_to_u64.len() - 1, DEG_DVS - 1);
+        let c_val = match acc {
+            // Enforce that acc % Q = c_val
+            Some(acc) => range.div_mod(ctx, acc, Q, num_bits).1,
+            None => ctx.load_constant(F::zero()),
+        };
 
-    // Later we need to perform the operation remainder + prod where prod is of degree DEG_DVD
-    // In order to perform the operation inside the circuit we need to pad the remainder with 0s at the beginning to make its degree equal to DEG_DVD
-    let mut remainder_to_u64 = remainder_to_u64;
-    while remainder_to_u64.len() - 1 < DEG_DVD {
-        remainder_to_u64.insert(0, 0);
+        c.push(c_val);
     }
 
-    // Now remainder must be of degree DEG_DVD
-    assert_eq!(remainder_to_u64.len() - 1, DEG_DVD);
+    // assert that the product polynomial has degree c_deg
+    assert_eq!(c.len() - 1, c_deg);
 
-    // Assign the quotient and remainder to the circuit
-    let mut quotient = vec![];
-    let mut remainder = vec![];
+    c
+}
 
-    for i in 0..DEG_DVD - DEG_DVS + 1 {
-        let val = F::from(quotient_to_u64[i]);
-        let assigned_val = ctx.load_witness(val);
-        quotient.push(assigned_val);
-    }
+/// Build the scalar multiplication of the polynomials a and the scalar k as scalar multiplication of the coefficients of a and k
+///
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients are constrained such to overflow during the scalar multiplication
+/// * Panics if a precondition on the inputs is not satisfied. See `try_poly_scalar_mul` for a version returning a `PolyError`
+pub fn poly_scalar_mul<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    b: QuantumCell<F>,
+    gate: &GateChip<F>,
+) -> Vec<AssignedValue<F>> {
+    try_poly_scalar_mul::<DEG, F>(ctx, a, b, gate)
+        .unwrap_or_else(|err| panic!("poly_scalar_mul: {}", err))
+}
 
-    for i in 0..DEG_DVD + 1 {
-        let val = F::from(remainder_to_u64[i]);
-        let assigned_val = ctx.load_witness(val);
-        remainder.push(assigned_val);
-    }
+/// Same as `poly_scalar_mul`, but returns an error instead of panicking if a precondition on the inputs is not satisfied
+///
+/// * Returns `PolyError::EmptyPolynomial` or `PolyError::DegreeMismatch` if the degree of a is not DEG
+pub fn try_poly_scalar_mul<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    b: QuantumCell<F>,
+    gate: &GateChip<F>,
+) -> Result<Vec<AssignedValue<F>>, PolyError> {
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
 
-    // assert that the degree of quotient is DEG_DVD - DEG_DVS
-    assert_eq!(quotient.len() - 1, DEG_DVD - DEG_DVS);
+    // check that the degree of the polynomial a is equal to DEG
+    check_degree(&a, DEG)?;
 
-    // assert that the degree of remainder is DEG_DVD
-    assert_eq!(remainder.len() - 1, DEG_DVD);
+    let mut c = vec![];
 
-    // Quotient is obtained by dividing the coefficients of the dividend by the highest degree coefficient of divisor
-    // The coefficients of dividend are in the range [0, Q - 1] by assumption.
-    // The leading coefficient of divisor is 1 by assumption.
-    // Therefore, the coefficients of quotient have to be in the range [0, Q - 1]
-    // Since the quotient is computed outside the circuit, we need to enforce this constraint
-    for i in 0..(DEG_DVD - DEG_DVS + 1) {
-        range.check_less_than_safe(ctx, quotient[i], Q);
+    for i in 0..=DEG {
+        let val = gate.mul(ctx, a[i], b);
+        c.push(val);
     }
 
-    // Remainder is equal to dividend - (quotient * divisor).
-    // The coefficients of dividend are in the range [0, Q - 1] by assumption.
-    // The coefficients of quotient are in the range [0, Q - 1] by constraint set above.
-    // The coefficients of divisior are either 0, 1 by assumption of the cyclotomic polynomial.
-    // It follows that the coefficients of quotient * divisor are in the range [0, Q - 1]
-    // The remainder (as result dividend - (quotient * divisor)) might have coefficients that are negative. In that case we add Q to them to make them positive.
-    // Therefore, the coefficients of remainder are in the range [0, Q - 1]
-    // Since the remainder is computed outside the circuit, we need to enforce this constraint
-    for i in 0..DEG_DVS {
-        range.check_less_than_safe(ctx, remainder[i], Q);
-    }
+    // assert that the product polynomial has degree DEG
+    assert_eq!(c.len() - 1, DEG);
 
-    // check that quotient * divisor + remainder = dividend
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("poly_scalar_mul", ctx, stats_start);
 
-    // DEGREE ANALYSIS
-    // Quotient is of degree DEG_DVD - DEG_DVS
-    // Divisor is of degree DEG_DVS
-    // Quotient * divisor is of degree DEG_DVD
-    // Remainder is of degree DEG_DVD
-    // Quotient * divisor + rem is of degree DEG_DVD
-    // Dividend is of degree DEG_DVD
+    Ok(c)
+}
 
-    // Perform the polynomial multiplication between quotient and divisor
+/// Build the coefficient-wise (Hadamard) product of the vectors a and b, namely c[i] = a[i] * b[i]
+///
+/// * This is NOT a polynomial multiplication: no convolution is performed and the output has the same degree as the inputs. Use `poly_mul_equal_deg` for the product of polynomials
+/// * This is the pointwise product used on polynomials in evaluation form, or to scale each coefficient by a different scalar (e.g. gadget scaling)
+/// * DEG is the degree of the input vectors, namely they have DEG + 1 elements
+/// * Input vectors are parsed as vectors of assigned values [a_DEG, a_DEG-1, ..., a_1, a_0]
+/// * If the elements of a and b are bounded by a_bound and b_bound, the elements of the output are bounded by a_bound * b_bound
+/// * It assumes that the elements are constrained such to overflow during the multiplication
+pub fn poly_hadamard<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    gate: &GateChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the input vectors have the same length and this is equal to DEG + 1
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.len() - 1, DEG);
+
+    let mut c = vec![];
+
+    for i in 0..=DEG {
+        let val = gate.mul(ctx, a[i], b[i]);
+        c.push(val);
+    }
+
+    // assert that the output has degree DEG
+    assert_eq!(c.len() - 1, DEG);
+
+    c
+}
+
+/// Build the inner product of the coefficient vectors of a and b reduced modulo Q, namely sum_i a[i] * b[i] mod Q
+///
+/// * This is a vector dot product, NOT a polynomial multiplication: no convolution is performed and the output is a scalar. See `poly_hadamard` for the coefficient-wise products and `poly_dot` for the inner product of vectors of polynomials in R_q
+/// * The products are accumulated with a single `inner_product` and the sum is reduced with a single `div_mod`
+/// * DEG is the degree of the input polynomials, namely they have DEG + 1 coefficients
+/// * Input polynomials are parsed as vectors of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Returns an assigned value in the range [0, Q - 1]
+/// * Assumes that the coefficients of a and b are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn poly_coeff_dot_mod<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    range: &RangeChip<F>,
+) -> AssignedValue<F> {
+    // assert that the input polynomials have the same degree and this is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+    assert_eq!(b.len() - 1, DEG);
 
     // COEFFICIENTS OVERFLOW ANALYSIS
-    // The coefficients of quotient are in the range [0, Q - 1] by constraint set above.
-    // The coefficients of divisor are either 0, 1 by assumption.
-    // The coefficients of prod are calculated as $c_{k} = \sum_{i=0}^{k} quotient[i] * divisor[k - i]$. Where k is the index of the coefficient c of prod.
-    // For two polynomials of differents degree n and m (where m < n), the max number of multiplication performed inside the summation is m + 1.
-    // The quotient is of degree DEG_DVD - DEG_DVS
-    // The divisor is of degree DEG_DVS
-    // Since DEG_DVD = (2 * DEG_DVS) - 2 it follows that the degree of the divisor is greater than the degree of quotient.
-    // In that case there are max (degree quotient + 1) multiplications in the sum. Namely DEG_DVD - DEG_DVS + 1 multiplications.
-    // The maximum value of the coffiecient of prod is (Q-1) * (1) * (DEG_DVD - DEG_DVS + 1).
-    // Q needs to be chosen such that (Q-1) * (DEG_DVD - DEG_DVS + 1) < p where p is the prime field of the circuit in order to avoid overflow during the multiplication.
-    // Note that this is a subset of the assumption of the circuit
-    // Therefore, the coefficients of prod are in the range [0, (Q-1) * (DEG_DVD - DEG_DVS + 1)]
+    // The dot product is the sum of DEG + 1 products of two values in the range [0, Q - 1]
+    // Therefore it is in the range [0, (Q-1) * (Q-1) * (DEG+1)]
+    let dot_bound = (Q as u128 - 1)
+        .checked_mul(Q as u128 - 1)
+        .and_then(|bound| bound.checked_mul(DEG as u128 + 1))
+        .expect("poly_coeff_dot_mod: the bound of the dot product overflows u128");
+    assert!(
+        fits_in_field::<F>(dot_bound),
+        "poly_coeff_dot_mod: the dot product may overflow the prime field of the circuit"
+    );
 
-    // We use a polynomial multiplication algorithm that does not require the input polynomials to be of the same degree
+    // get the number of bits needed to represent the value of (Q-1) * (Q-1) * (DEG+1)
+    let binary_representation = format!("{:b}", dot_bound);
+    let num_bits = binary_representation.len();
 
-    let prod = poly_mul_diff_deg(ctx, quotient, divisor, range.gate());
+    let dot = range.gate().inner_product(
+        ctx,
+        a.iter().copied(),
+        b.iter().map(|coeff| QuantumCell::Existing(*coeff)),
+    );
 
-    // The degree of prod is DEG_DVD
-    assert_eq!(prod.len() - 1, DEG_DVD);
+    // The dot product is known to have <= `num_bits` bits, which satisfies the assumption of `div_mod`
+    range.div_mod(ctx, dot, Q, num_bits).1
+}
 
-    // Perform the addition between prod and remainder
+/// Build the sum of the polynomial a and the scalar k, namely k is added to the constant term of a
+///
+/// * Only the constant term a_0 is modified. See `poly_add_scalar_to_all` to add k to every coefficient
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients are constrained such to overflow during the scalar addition
+pub fn poly_scalar_add<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    k: QuantumCell<F>,
+    gate: &GateChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
 
-    // DEGREE ANALYSIS
-    // Prod is of degree DEG_DVD
-    // Remainder is of degree DEG_DVD
-    // Prod + rem is of degree DEG_DVD
+    let mut c = a;
 
-    // COEFFICIENTS OVERFLOW ANALYSIS
-    // The coefficients of prod are in the range [0, (Q-1) * (DEG_DVD - DEG_DVS + 1)] by the constraint above.
-    // The coefficients of remainder are in the range [0, Q - 1] by constraint set above.
-    // Therefore, the coefficients of prod + remainder are in the range [0, [0, (Q-1) * (DEG_DVD - DEG_DVS + 1)] + Q-1].
-    // Q needs to be chosen such that (Q-1) * (DEG_DVD - DEG_DVS + 1)] + Q-1 < p where p is the prime field of the circuit in order to avoid overflow during the addition.
-    // This is true by assumption of the chip.
+    // The constant term is the last coefficient
+    c[DEG] = gate.add(ctx, c[DEG], k);
 
-    let sum = poly_add::<DEG_DVD, F>(ctx, prod, remainder.clone(), range.gate());
+    // assert that the sum polynomial has degree DEG
+    assert_eq!(c.len() - 1, DEG);
 
-    // assert that the degree of sum is DEG_DVD
-    assert_eq!(sum.len() - 1, DEG_DVD);
+    c
+}
 
-    // We can reduce the coefficients of sum modulo Q to make them in the range [0, Q - 1]
+/// Build the sum of the polynomial a and the constant polynomial k + k*x + ... + k*x^DEG, namely k is added to every coefficient of a
+///
+/// * Every coefficient is modified. See `poly_scalar_add` to add k to the constant term only
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients are constrained such to overflow during the scalar addition
+pub fn poly_add_scalar_to_all<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    k: QuantumCell<F>,
+    gate: &GateChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
 
-    // get the number of bits needed to represent the value of (Q-1) * (DEG_DVD - DEG_DVS + 1)] + Q-1
-    let binary_representation = format!(
-        "{:b}",
-        (Q - 1) * (DEG_DVD as u64 - DEG_DVS as u64 + 1) + (Q - 1)
-    ); // Convert to binary (base-2)
-    let num_bits = binary_representation.len();
+    let mut c = vec![];
 
-    // The coefficients of sum are in the range [0, (Q-1) * (DEG_DVD - DEG_DVS + 1)] + Q-1] according to the polynomial addition constraint set above.
-    // Therefore the coefficients of sum are known to have <= `num_bits` bits, therefore they satisfy the assumption of the `poly_reduce` chip
-    let sum_mod = poly_reduce::<DEG_DVD, Q, F>(ctx, sum, range, num_bits);
+    for i in 0..=DEG {
+        let val = gate.add(ctx, a[i], k);
+        c.push(val);
+    }
 
-    // assert that the degree of sum_mod is DEG_DVD
-    assert_eq!(sum_mod.len() - 1, DEG_DVD);
+    // assert that the sum polynomial has degree DEG
+    assert_eq!(c.len() - 1, DEG);
 
-    // Enforce that sum_mod = dividend
-    for i in 0..=DEG_DVD {
-        let bool = range.gate().is_equal(ctx, sum_mod[i], dividend[i]);
-        range.gate().assert_is_const(ctx, &bool, &F::from(1))
+    c
+}
+
+/// Takes a polynomial represented by its coefficients in a vector and output a new polynomial reduced by applying modulo Q to each coefficient
+///
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients of the input polynomial can be expressed in at most num_bits bits
+/// * Q is not required to be prime. `div_mod` only relies on the euclidean division by Q, therefore composite moduli (e.g. the product of RNS moduli) are supported
+/// * Panics if a precondition on the inputs is not satisfied. See `try_poly_reduce` for a version returning a `PolyError`
+pub fn poly_reduce<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> Vec<AssignedValue<F>> {
+    try_poly_reduce::<DEG, Q, F>(ctx, input, range, num_bits)
+        .unwrap_or_else(|err| panic!("poly_reduce: {}", err))
+}
+
+/// Same as `poly_reduce`, but returns an error instead of panicking if a precondition on the inputs is not satisfied
+///
+/// * Returns `PolyError::EmptyPolynomial` or `PolyError::DegreeMismatch` if the degree of input is not DEG
+pub fn try_poly_reduce<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> Result<Vec<AssignedValue<F>>, PolyError> {
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
+
+    // Check that degree of input polynomial is equal to the constant DEG
+    check_degree(&input, DEG)?;
+
+    let mut rem_assigned = vec![];
+
+    // Enforce that in_assigned[i] % Q = rem_assigned[i]
+    for i in 0..=DEG {
+        let rem = range.div_mod(ctx, input[i], Q, num_bits).1;
+        rem_assigned.push(rem);
     }
 
-    remainder
+    // assert that the reduced polynomial has degree DEG
+    assert_eq!(rem_assigned.len() - 1, DEG);
+
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("poly_reduce", ctx, stats_start);
+
+    Ok(rem_assigned)
+}
+
+/// Reduces modulo Q the coefficients of the product of two polynomials of the ring described by a validated parameter set
+///
+/// * Same as `poly_reduce` on the output of `poly_mul_equal_deg`, where `num_bits` is `params.mul_num_bits()`
+/// * `params` must be validated for the ring R_q = Z_Q[x]/(x^(DEG/2+1) + 1) against the field F, namely DEG = 2 * params.deg() and Q = params.q().
+///   The bound (Q-1) * (Q-1) * (DEG/2+1) of the coefficients of the product is then trusted, since `PolyParams::new` checked it against the field
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Panics if `params` does not describe the ring of the chip
+pub fn poly_reduce_with_params<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    params: &PolyParams,
+) -> Vec<AssignedValue<F>> {
+    assert!(
+        DEG == 2 * params.deg(),
+        "poly_reduce_with_params: the input must be the product of two polynomials of degree {}",
+        params.deg()
+    );
+    assert_params::<F>(params, params.deg(), Q, "poly_reduce_with_params");
+
+    poly_reduce::<DEG, Q, F>(ctx, input, range, params.mul_num_bits())
+}
+
+/// Takes a polynomial represented by its coefficients in a vector and output a new polynomial reduced by applying modulo Q = 2^K to each coefficient
+///
+/// * PRECONDITION: the modulus is Q = 2^K. The reduction is then the extraction of the low K bits of each coefficient, which is cheaper than the euclidean division performed by `poly_reduce`
+/// * Each coefficient is split into low + high * 2^K where low and high are witnesses. low is range checked to K bits and high to num_bits - K bits with the bit decomposition of `range`, and the recomposition is constrained
+/// * Compared to `poly_reduce`, the check low < Q is the range check itself, therefore no comparison against Q is needed
+/// * DEG is the degree of the polynomial
+/// * K is the number of bits of the modulus, and must be smaller than 64 and at most num_bits
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients of the input polynomial can be expressed in at most num_bits bits
+pub fn poly_reduce_pow2<const DEG: usize, const K: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> Vec<AssignedValue<F>> {
+    // Assert that degree of input polynomial is equal to the constant DEG
+    assert_eq!(input.len() - 1, DEG);
+    assert!(K < 64, "poly_reduce_pow2: K must be smaller than 64");
+    assert!(
+        K <= num_bits,
+        "poly_reduce_pow2: K must be at most num_bits"
+    );
+
+    let pow2 = F::from(1u64 << K);
+    let pow2_inv = pow2.invert().unwrap();
+
+    let mut rem_assigned = vec![];
+
+    for coeff in input {
+        // Split the coefficient into its low K bits and the remaining high bits
+        let low_val = coeff.value().to_u64_limbs(1, K)[0];
+        let low = ctx.load_witness(F::from(low_val));
+        let high = ctx.load_witness((*coeff.value() - F::from(low_val)) * pow2_inv);
+
+        // Enforce that low < 2^K and high < 2^(num_bits - K). If num_bits = K, high must be zero
+        range.range_check(ctx, low, K);
+        if num_bits > K {
+            range.range_check(ctx, high, num_bits - K);
+        } else {
+            range.gate().assert_is_const(ctx, &high, &F::zero());
+        }
+
+        // Enforce that coeff = high * 2^K + low. Since coeff < 2^num_bits < p, the decomposition is unique
+        let recomposed = range
+            .gate()
+            .mul_add(ctx, high, QuantumCell::Constant(pow2), low);
+        ctx.constrain_equal(&recomposed, &coeff);
+
+        rem_assigned.push(low);
+    }
+
+    // assert that the reduced polynomial has degree DEG
+    assert_eq!(rem_assigned.len() - 1, DEG);
+
+    rem_assigned
+}
+
+/// Takes a polynomial represented by its coefficients in a vector and output its coefficients reduced modulo Q in balanced form, namely as signed digits in the range [-(Q-1)/2, Q/2]
+///
+/// * Each coefficient is first reduced to r in the range [0, Q - 1] with `poly_reduce`, then encoded as the signed digit r if r <= Q/2 and r - Q otherwise
+/// * Returns (magnitudes, signs) where signs[i] is a boolean assigned value equal to 1 if the digit is negative, and magnitudes[i] is the absolute value of the digit, in the range [0, Q/2]
+/// * The relationship with the standard reduction is constrained: r = magnitude if sign = 0 and r = Q - magnitude if sign = 1, where sign = 1 if and only if r > Q/2. Zero is encoded with sign 0
+/// * At the boundary, r = Q/2 is encoded as the positive digit Q/2 and r = Q/2 + 1 as the negative digit -(Q - Q/2 - 1). For an even Q, the digit -Q/2 never appears
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients of the input polynomial can be expressed in at most num_bits bits
+pub fn poly_reduce_to_balanced<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> (Vec<AssignedValue<F>>, Vec<AssignedValue<F>>) {
+    // The coefficients of the reduced polynomial are in the range [0, Q - 1]
+    let reduced = poly_reduce::<DEG, Q, F>(ctx, input, range, num_bits);
+
+    // get the number of bits needed to represent the value of Q
+    let binary_representation = format!("{:b}", Q);
+    let q_num_bits = binary_representation.len();
+
+    let mut magnitudes = vec![];
+    let mut signs = vec![];
+
+    for coeff in reduced {
+        let (magnitude, is_positive) =
+            centered_abs_with_sign::<Q, F>(ctx, coeff, range, q_num_bits);
+        magnitudes.push(magnitude);
+        signs.push(range.gate().not(ctx, is_positive));
+    }
+
+    // assert that the balanced polynomial has degree DEG
+    assert_eq!(magnitudes.len() - 1, DEG);
+
+    (magnitudes, signs)
+}
+
+/// Reduce in place the coefficients of a polynomial by applying modulo Q to each coefficient
+///
+/// * Same constraints as `poly_reduce`, but each coefficient of `input` is overwritten by its remainder instead of allocating a new vector
+/// * After the call, `input` holds the reduced polynomial. The unreduced coefficients are no longer accessible through `input`
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients of the input polynomial can be expressed in at most num_bits bits
+pub fn poly_reduce_into<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: &mut Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) {
+    // Assert that degree of input polynomial is equal to the constant DEG
+    assert_eq!(input.len() - 1, DEG);
+
+    // Enforce that input[i] % Q = rem and overwrite input[i] with rem
+    for coeff in input.iter_mut() {
+        *coeff = range.div_mod(ctx, *coeff, Q, num_bits).1;
+    }
+}
+
+/// Takes a polynomial represented by its coefficients in a vector and output a new polynomial where only the coefficients in the index range [start, end) are reduced modulo Q
+///
+/// * The indices refer to positions in the input vector, namely input[i] is the coefficient of x^(n - i) where n is the degree of the polynomial
+/// * Only end - start `div_mod` gates are emitted, the coefficients outside of [start, end) are returned untouched
+/// * It is the responsibility of the caller to ensure that the untouched coefficients are already in the range [0, Q - 1], e.g. because they were not affected by a negacyclic fold. No constraint is emitted on them
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_n, a_n-1, ..., a_1, a_0] where a_0 is the constant term and n is the degree of the polynomial
+/// * It assumes that the coefficients in [start, end) can be expressed in at most num_bits bits
+/// * Q is not required to be prime
+pub fn poly_reduce_range<const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    start: usize,
+    end: usize,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> Vec<AssignedValue<F>> {
+    // Assert that the index range is contained in the input polynomial
+    assert!(start <= end && end <= input.len());
+
+    let mut out = input;
+
+    // Enforce that input[i] % Q = out[i] for i in [start, end)
+    for coeff in &mut out[start..end] {
+        *coeff = range.div_mod(ctx, *coeff, Q, num_bits).1;
+    }
+
+    out
+}
+
+/// Takes a polynomial represented by its coefficients in a vector and output both the polynomial reduced modulo Q and the quotients of the reduction
+///
+/// * Same constraints as `poly_reduce`. In addition, the quotient q_i = floor(input[i] / Q) computed by `div_mod` is returned for each coefficient, such that input[i] = q_i * Q + rem[i]
+/// * This turns a silent overflow into an explicit, checkable value: if a coefficient exceeds the expected bound, its quotient exceeds the expected maximum
+/// * The expected maximum of the quotients is floor(bound / Q) where bound is the upper bound of the input coefficients, e.g. (Q-1) * (DEG+1) for the product of a polynomial with a cyclotomic polynomial
+/// * If `max_quotient` is `Some(m)`, each quotient is constrained to be in the range [0, m]
+/// * Returns (rem, quotients), both in the order [a_DEG, a_DEG-1, ..., a_1, a_0]
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients of the input polynomial can be expressed in at most num_bits bits
+pub fn poly_reduce_audited<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+    max_quotient: Option<u64>,
+) -> (Vec<AssignedValue<F>>, Vec<AssignedValue<F>>) {
+    // Assert that degree of input polynomial is equal to the constant DEG
+    assert_eq!(input.len() - 1, DEG);
+
+    let mut rem_assigned = vec![];
+    let mut quot_assigned = vec![];
+
+    // Enforce that input[i] = quot_assigned[i] * Q + rem_assigned[i]
+    for coeff in input {
+        let (quot, rem) = range.div_mod(ctx, coeff, Q, num_bits);
+
+        if let Some(max_quotient) = max_quotient {
+            // Enforce that quot <= max_quotient
+            range.check_less_than_safe(ctx, quot, max_quotient + 1);
+        }
+
+        rem_assigned.push(rem);
+        quot_assigned.push(quot);
+    }
+
+    // assert that the reduced polynomial has degree DEG
+    assert_eq!(rem_assigned.len() - 1, DEG);
+
+    (rem_assigned, quot_assigned)
+}
+
+/// Takes a polynomial represented by its coefficients in a vector and output its reduction by each modulus of an RNS basis
+///
+/// * Returns an array of K polynomials where the k-th polynomial is the input polynomial with each coefficient reduced modulo moduli[k]
+/// * This is the entry point from a polynomial with wide coefficients into its RNS representation
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients of the input polynomial can be expressed in at most num_bits bits
+/// * The moduli are not required to be prime nor pairwise coprime. Each of them must be non-zero
+pub fn poly_reduce_rns<const DEG: usize, const K: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: &[AssignedValue<F>],
+    moduli: [u64; K],
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> [Vec<AssignedValue<F>>; K] {
+    // Assert that degree of input polynomial is equal to the constant DEG
+    assert_eq!(input.len() - 1, DEG);
+
+    // Each limb reuses the same input coefficients
+    // Enforce that input[i] % moduli[k] = limbs[k][i]
+    let limbs: [Vec<AssignedValue<F>>; K] = std::array::from_fn(|k| {
+        let q = moduli[k];
+        assert!(q != 0);
+
+        let mut rem_assigned = vec![];
+
+        for coeff in input {
+            let rem = range.div_mod(ctx, *coeff, q, num_bits).1;
+            rem_assigned.push(rem);
+        }
+
+        // assert that the reduced polynomial has degree DEG
+        assert_eq!(rem_assigned.len() - 1, DEG);
+
+        rem_assigned
+    });
+
+    limbs
+}
+
+/// Takes a polynomial represented by its coefficients in a vector and output a new polynomial reduced by applying modulo q to each coefficient
+///
+/// * Compared to `poly_reduce`, the modulus q is not a const generic but a cell of the circuit. This allows to reduce by a different modulus at each iteration of an RNS loop
+/// * q can be either a `QuantumCell::Constant`, which is fixed in the verifying key, or a `QuantumCell::Existing` cell
+/// * When q is an existing cell, the caller is responsible for constraining it to the intended public value (e.g. loading it with `load_constant` or exposing it as a public instance). Otherwise the prover is free to choose the modulus
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients of the input polynomial can be expressed in at most num_bits bits and that q can be expressed in at most q_num_bits bits
+/// * Assumes that q is non-zero and that num_bits + q_num_bits < F::CAPACITY, such that quotient * q + remainder does not overflow the prime field of the circuit
+pub fn poly_reduce_var<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: &[AssignedValue<F>],
+    q: QuantumCell<F>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+    q_num_bits: usize,
+) -> Vec<AssignedValue<F>> {
+    // Assert that degree of input polynomial is equal to the constant DEG
+    assert_eq!(input.len() - 1, DEG);
+
+    // Assert that the euclidean division by q cannot overflow the prime field of the circuit
+    assert!(q_num_bits <= num_bits);
+    assert!(num_bits + q_num_bits < F::CAPACITY as usize);
+
+    let mut rem_assigned = vec![];
+
+    // Enforce that input[i] % q = rem_assigned[i]
+    // `div_mod_var` range checks the quotient on num_bits bits and constrains the remainder to be less than q
+    for coeff in input {
+        let rem = range.div_mod_var(ctx, *coeff, q, num_bits, q_num_bits).1;
+        rem_assigned.push(rem);
+    }
+
+    // assert that the reduced polynomial has degree DEG
+    assert_eq!(rem_assigned.len() - 1, DEG);
+
+    rem_assigned
+}
+
+/// Takes a polynomial whose coefficients may represent negative integers and output a new polynomial reduced by applying modulo Q to each coefficient
+///
+/// * Operations such as subtractions leave field elements close to p for negative values. `poly_reduce` assumes small non negative coefficients and would return wrong results on them
+/// * The coefficients are first shifted by the additive offset Q * ceil(neg_bound / Q), namely the smallest multiple of Q greater than or equal to neg_bound. The shift does not change the coefficients modulo Q and makes them non negative
+/// * The shifted coefficients are then reduced with `poly_reduce`
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that each coefficient represents an integer in the range [-neg_bound, pos_bound]
+/// * It assumes that the shifted coefficients, in the range [0, pos_bound + offset], can be expressed in at most num_bits bits
+pub fn poly_reduce_signed<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    neg_bound: u64,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> Vec<AssignedValue<F>> {
+    // Assert that degree of input polynomial is equal to the constant DEG
+    assert_eq!(input.len() - 1, DEG);
+
+    // offset is the smallest multiple of Q that is greater than or equal to neg_bound
+    let offset = (neg_bound as u128 + Q as u128 - 1) / Q as u128 * Q as u128;
+    let offset = u64::try_from(offset).expect("poly_reduce_signed: the offset overflows u64");
+
+    let mut shifted = vec![];
+
+    for coeff in input {
+        // coeff + offset is congruent to coeff modulo Q and is in the range [0, pos_bound + offset]
+        let val = range
+            .gate()
+            .add(ctx, coeff, QuantumCell::Constant(F::from(offset)));
+        shifted.push(val);
+    }
+
+    poly_reduce::<DEG, Q, F>(ctx, shifted, range, num_bits)
+}
+
+/// Takes a polynomial with coefficients in the range [0, 2Q - 1] and output a new polynomial reduced by applying modulo Q to each coefficient
+///
+/// * Compared to `poly_reduce`, this function does not use `div_mod`. Each coefficient x is reduced with a single conditional subtraction x - Q * (x >= Q)
+/// * This is cheaper than `poly_reduce` and is meant to be used right after the addition of two reduced polynomials
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * STRICT PRECONDITION: the coefficients of the input polynomial must be in the range [0, 2Q - 1]. A coefficient greater than or equal to 2Q is not reduced to [0, Q - 1] by a single subtraction
+pub fn poly_reduce_once<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // Assert that degree of input polynomial is equal to the constant DEG
+    assert_eq!(input.len() - 1, DEG);
+
+    // get the number of bits needed to represent the value of 2Q - 1
+    // The bound is computed on u128 so that it cannot wrap around for Q above 2^63
+    let binary_representation = format!("{:b}", (2 * Q as u128 - 1));
+    let num_bits = binary_representation.len();
+
+    let mut rem_assigned = vec![];
+
+    for coeff in &input {
+        // coeff is known to have <= `num_bits` bits according to the precondition of the chip
+        // Q is known to have <= `num_bits` bits by definition
+        // Therefore it satisfies the assumption of `is_less_than` chip
+        let is_less_than_q =
+            range.is_less_than(ctx, *coeff, QuantumCell::Constant(F::from(Q)), num_bits);
+
+        // If coeff >= Q, subtract Q from it
+        let coeff_minus_q = range
+            .gate()
+            .sub(ctx, *coeff, QuantumCell::Constant(F::from(Q)));
+        let rem = range
+            .gate()
+            .select(ctx, *coeff, coeff_minus_q, is_less_than_q);
+        rem_assigned.push(rem);
+    }
+
+    // assert that the reduced polynomial has degree DEG
+    assert_eq!(rem_assigned.len() - 1, DEG);
+
+    rem_assigned
+}
+
+/// Build the sum of the polynomials a and b with coefficients reduced modulo Q, assuming that both inputs are already reduced
+///
+/// * The coefficients are added with `poly_add` and the sum is reduced with `poly_reduce_once`, namely a single conditional subtraction per coefficient instead of the `div_mod` of `poly_reduce`
+/// * This is the addition of the ring R_q, e.g. for counters that wrap around modulo Q
+/// * DEG is the degree of the input polynomials
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * STRICT PRECONDITION: the coefficients of a and b must be in the range [0, Q - 1], such that the coefficients of the sum are in the range [0, 2Q - 2]. This is not enforced by the chip, use `poly_assert_reduced` on inputs that are not known to be reduced
+pub fn poly_add_mod_reduced<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // The coefficients of the sum are in the range [0, 2Q - 2], which satisfies the precondition of the `poly_reduce_once` chip
+    let sum = poly_add::<DEG, F>(ctx, a, b, range.gate());
+
+    poly_reduce_once::<DEG, Q, F>(ctx, sum, range)
+}
+
+/// Strategy used to reduce each coefficient of a polynomial by the modulus Q
+///
+/// * `DivMod` (default) enforces x = q * Q + r with the `div_mod` chip of halo2-lib. Its cost grows slowly with the number of bits of the input
+/// * `ConditionalSubtraction` enforces the reduction with a restoring division: for j from num_bits - bits(Q) down to 0, Q * 2^j is subtracted from x when x >= Q * 2^j.
+///   It costs one comparison and one selection per step, therefore it is only cheaper than `DivMod` when num_bits is close to the number of bits of Q (e.g. after an addition)
+/// * Both strategies output the same reduced coefficients
+/// * Montgomery reduction is not offered since it outputs x * R^-1 mod Q, which changes the representation of the coefficients
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReductionStrategy {
+    #[default]
+    DivMod,
+    ConditionalSubtraction,
+}
+
+/// Reduce a single coefficient x by Q according to `strategy`
+///
+/// * Assumes that x can be expressed in at most num_bits bits
+/// * `ConditionalSubtraction` requires num_bits to be at least the number of bits of Q, so that Q satisfies the assumption of the `is_less_than` chip
+fn reduce_coeff<const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    x: AssignedValue<F>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+    strategy: ReductionStrategy,
+) -> AssignedValue<F> {
+    match strategy {
+        ReductionStrategy::DivMod => range.div_mod(ctx, x, Q, num_bits).1,
+        ReductionStrategy::ConditionalSubtraction => {
+            // get the number of bits needed to represent the value of Q
+            let binary_representation = format!("{:b}", Q);
+            let q_num_bits = binary_representation.len();
+            assert!(
+                num_bits >= q_num_bits,
+                "reduce_coeff: num_bits must be at least the number of bits of Q"
+            );
+
+            // x < 2^num_bits <= Q * 2^(steps), since Q >= 2^(q_num_bits - 1)
+            let steps = num_bits - q_num_bits + 1;
+
+            // shifts[j] = Q * 2^j
+            let mut shifts = vec![F::from(Q)];
+            for j in 1..steps {
+                shifts.push(shifts[j - 1] + shifts[j - 1]);
+            }
+
+            // Invariant: at the beginning of step j, x < Q * 2^(j+1). At the end of step j, x < Q * 2^j
+            // Q * 2^j < 2^num_bits, therefore both x and Q * 2^j satisfy the assumption of the `is_less_than` chip
+            let mut x = x;
+            for shift in shifts.into_iter().rev() {
+                let is_less_than_shift =
+                    range.is_less_than(ctx, x, QuantumCell::Constant(shift), num_bits);
+                let x_minus_shift = range.gate().sub(ctx, x, QuantumCell::Constant(shift));
+                x = range
+                    .gate()
+                    .select(ctx, x, x_minus_shift, is_less_than_shift);
+            }
+
+            x
+        }
+    }
+}
+
+/// Takes a polynomial represented by its coefficients in a vector and output a new polynomial reduced by applying modulo Q to each coefficient, using the given reduction strategy
+///
+/// * Same as `poly_reduce` when `strategy` is `ReductionStrategy::DivMod`
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients of the input polynomial can be expressed in at most num_bits bits
+pub fn poly_reduce_with<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+    strategy: ReductionStrategy,
+) -> Vec<AssignedValue<F>> {
+    // Assert that degree of input polynomial is equal to the constant DEG
+    assert_eq!(input.len() - 1, DEG);
+
+    let mut rem_assigned = vec![];
+
+    // Enforce that input[i] % Q = rem_assigned[i]
+    for coeff in input {
+        let rem = reduce_coeff::<Q, F>(ctx, coeff, range, num_bits, strategy);
+        rem_assigned.push(rem);
+    }
+
+    // assert that the reduced polynomial has degree DEG
+    assert_eq!(rem_assigned.len() - 1, DEG);
+
+    rem_assigned
+}
+
+/// Enforces that the coefficients of a polynomial are already reduced modulo Q and returns the same polynomial
+///
+/// * Compared to `poly_reduce`, this function does not recompute the division by Q. It only enforces that each coefficient is in the range [0, Q - 1]
+/// * It is safe to use instead of `poly_reduce` when the coefficients are expected to be already reduced, e.g. when they are the output of a previous reduction or an input that must live in R_q.
+/// * If any coefficient is greater than or equal to Q the constraint fails, while `poly_reduce` would silently reduce it
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+pub fn poly_assert_reduced<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // Assert that degree of input polynomial is equal to the constant DEG
+    assert_eq!(input.len() - 1, DEG);
+
+    // Enforce that input[i] < Q
+    for coeff in &input {
+        range.check_less_than_safe(ctx, *coeff, Q);
+    }
+
+    input
+}
+
+/// Enforces that `claimed` is the minimal nonnegative representative of `original` modulo Q, namely claimed = original mod Q
+///
+/// * Enforces claimed < Q, which rules out the representatives claimed + k * Q for k > 0
+/// * Enforces that original - claimed is divisible by Q: the quotient k = (original - claimed) / Q is assigned as a witness, range checked to be a small integer and original = k * Q + claimed is enforced
+/// * Compared to `div_mod`, the remainder is not computed by the chip but supplied by the caller, e.g. as an untrusted witness
+/// * If claimed is not the minimal representative of original, the witness k is not a small integer and the range check fails
+/// * Assumes that original can be expressed in at most num_bits bits
+/// * Assumes that Q * 2^(num_bits - bits(Q) + 1) + Q < p where p is the prime field of the circuit, such that k * Q + claimed does not wrap around the field
+pub fn assert_minimal_representative<const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    original: AssignedValue<F>,
+    claimed: AssignedValue<F>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) {
+    // get the number of bits needed to represent the value of Q
+    let binary_representation = format!("{:b}", Q);
+    let q_num_bits = binary_representation.len();
+
+    // Enforce that claimed < Q
+    range.check_less_than_safe(ctx, claimed, Q);
+
+    // k = (original - claimed) / Q, computed in the field. It is an integer smaller than 2^(num_bits - q_num_bits + 1) if and only if Q divides original - claimed
+    let q_inv = F::from(Q).invert().unwrap();
+    let k = ctx.load_witness((*original.value() - *claimed.value()) * q_inv);
+
+    // original < 2^num_bits and Q >= 2^(q_num_bits - 1), therefore k < 2^(num_bits - q_num_bits + 1)
+    range.range_check(ctx, k, num_bits.saturating_sub(q_num_bits) + 1);
+
+    // Enforce that original = k * Q + claimed
+    let recomposed = range
+        .gate()
+        .mul_add(ctx, k, QuantumCell::Constant(F::from(Q)), claimed);
+    ctx.constrain_equal(&recomposed, &original);
+}
+
+/// Enforces that the high coefficients of a polynomial are zero and returns the polynomial truncated to degree NEW_DEG
+///
+/// * This makes a degree drop explicit and checked, e.g. after an exact division where the high coefficients of the result are known to be zero
+/// * The FULL_DEG - NEW_DEG leading coefficients a[0..(FULL_DEG - NEW_DEG)] are enforced to be zero, and the NEW_DEG + 1 lower coefficients are returned
+/// * FULL_DEG is the degree of the input polynomial and NEW_DEG the degree of the output polynomial. Assumes that NEW_DEG <= FULL_DEG
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_FULL_DEG, a_FULL_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+pub fn poly_truncate<const FULL_DEG: usize, const NEW_DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    gate: &GateChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // Assert that degree of input polynomial is equal to the constant FULL_DEG
+    assert_eq!(a.len() - 1, FULL_DEG);
+
+    // The output degree cannot be greater than the input degree
+    assert!(NEW_DEG <= FULL_DEG);
+
+    // Enforce that the dropped coefficients are zero
+    for coeff in &a[0..(FULL_DEG - NEW_DEG)] {
+        let bool = gate.is_zero(ctx, *coeff);
+        gate.assert_is_const(ctx, &bool, &F::from(1));
+    }
+
+    let truncated = a[(FULL_DEG - NEW_DEG)..].to_vec();
+
+    // assert that the truncated polynomial has degree NEW_DEG
+    assert_eq!(truncated.len() - 1, NEW_DEG);
+
+    truncated
+}
+
+/// Splits the polynomial a into its low and high halves, namely a = low + x^HALF * high
+///
+/// * This is a pure restructuring of the coefficients, no constraint is emitted
+/// * Returns (low, high) where low contains the HALF coefficients of x^0, ..., x^(HALF-1) and high the coefficients of x^HALF, ..., x^n, where n is the degree of a
+/// * Assumes that 0 < HALF <= n, such that both halves are non empty
+/// * Input and output polynomials are parsed as vectors of assigned coefficients [a_n, a_n-1, ..., a_1, a_0] where a_0 is the constant term, therefore low is the tail of a and high its head
+/// * `poly_concat` is the inverse operation
+pub fn poly_split<const HALF: usize, F: ScalarField>(
+    a: &[AssignedValue<F>],
+) -> (Vec<AssignedValue<F>>, Vec<AssignedValue<F>>) {
+    // assert that both halves are non empty
+    assert!(HALF > 0);
+    assert!(HALF < a.len());
+
+    let (high, low) = a.split_at(a.len() - HALF);
+
+    // assert that the low half has degree HALF - 1
+    assert_eq!(low.len() - 1, HALF - 1);
+
+    (low.to_vec(), high.to_vec())
+}
+
+/// Joins the low and high halves of a polynomial, namely returns low + x^HALF * high
+///
+/// * This is a pure restructuring of the coefficients, no constraint is emitted
+/// * `low` must contain exactly HALF coefficients, namely it is padded with leading zeroes to degree HALF - 1
+/// * Input and output polynomials are parsed as vectors of assigned coefficients [a_n, a_n-1, ..., a_1, a_0] where a_0 is the constant term
+/// * `poly_concat::<HALF>(low, high)` is the inverse of `poly_split::<HALF>`
+pub fn poly_concat<const HALF: usize, F: ScalarField>(
+    low: &[AssignedValue<F>],
+    high: &[AssignedValue<F>],
+) -> Vec<AssignedValue<F>> {
+    // assert that the low half has degree HALF - 1
+    assert!(HALF > 0);
+    assert_eq!(low.len(), HALF);
+
+    // The coefficients of high are the leading coefficients of the output
+    let mut c = high.to_vec();
+    c.extend_from_slice(low);
+
+    // assert that the output polynomial has degree deg(high) + HALF
+    assert_eq!(c.len() - 1, high.len() - 1 + HALF);
+
+    c
+}
+
+/// Build the reciprocal polynomial x^DEG * a(1/x) of the polynomial a, namely the polynomial with the coefficients of a in reversed order
+///
+/// * This is a pure restructuring of the coefficients, no constraint is emitted
+/// * The coefficient of x^j of the output is the coefficient of x^(DEG - j) of a. If a has leading zeroes, the output has trailing zeroes and is divisible by a power of x
+/// * Reversing twice is the identity
+/// * DEG is the degree of the polynomial
+/// * Input and output polynomials are parsed as vectors of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+pub fn poly_reverse<const DEG: usize, F: ScalarField>(
+    a: &[AssignedValue<F>],
+) -> Vec<AssignedValue<F>> {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    let reversed = a.iter().rev().copied().collect::<Vec<_>>();
+
+    // assert that the reversed polynomial has degree DEG
+    assert_eq!(reversed.len() - 1, DEG);
+
+    reversed
+}
+
+/// Build the inverse of the polynomial a as a power series modulo x^K, namely b of degree K - 1 such that a * b = 1 mod (x^K, Q)
+///
+/// * This is the building block of the fast division of high degree polynomials with Newton iteration, as an alternative to the long division of `poly_divide_by_cyclo`
+/// * The inverse is computed outside the circuit with Newton iteration by `utils::inverse_mod_xk` and assigned as a witness. The chip then enforces that b is reduced and that the K low coefficients of a * b are [0, ..., 0, 1] modulo Q
+/// * Verifying the witness needs a single truncated product, while performing the Newton iterations in the circuit would need 2 * ceil(log2(K)) products
+/// * REQUIREMENT: the constant term a_0 must be a unit modulo Q, otherwise the inverse does not exist. The witness generation computes a_0^-1 with `utils::mod_inverse`, therefore Q must be prime and a_0 must not be zero
+/// * Only the coefficients of a of degree smaller than K are used
+/// * Input and output polynomials are parsed as vectors of assigned coefficients [a_n, a_n-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of a are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * K < p where p is the prime field of the circuit
+pub fn poly_inverse_mod_xk<const K: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    assert!(K > 0);
+
+    // Each coefficient of the truncated product is the sum of at most K products of two values in the range [0, Q - 1]
+    let prod_bound = (Q as u128 - 1)
+        .checked_mul(Q as u128 - 1)
+        .and_then(|bound| bound.checked_mul(K as u128))
+        .expect("poly_inverse_mod_xk: the bound of the product overflows u128");
+    assert!(
+        fits_in_field::<F>(prod_bound),
+        "poly_inverse_mod_xk: the coefficients of a * b may overflow the prime field of the circuit"
+    );
+
+    // get the number of bits needed to represent the value of (Q-1) * (Q-1) * K
+    let binary_representation = format!("{:b}", prod_bound);
+    let num_bits = binary_representation.len();
+
+    // Compute the inverse outside the circuit and assign it as a witness
+    let b_u64 = inverse_mod_xk::<K, Q>(&vec_assigned_to_vec_u64(a));
+    let b = b_u64
+        .iter()
+        .map(|&coeff| ctx.load_witness(F::from(coeff)))
+        .collect::<Vec<_>>();
+
+    // Enforce that the coefficients of b are in the range [0, Q - 1]
+    for coeff in &b {
+        range.check_less_than_safe(ctx, *coeff, Q);
+    }
+
+    // a[a.len() - 1 - j] is the coefficient of x^j of a, b[K - 1 - j] is the coefficient of x^j of b
+    let a_coeff = |j: usize| a[a.len() - 1 - j];
+    let b_coeff = |j: usize| b[K - 1 - j];
+
+    for e in 0..K {
+        // The coefficient of x^e of a * b is the sum of a_j * b_(e-j) for j in [0, min(e, deg(a))]
+        let terms = (0..=e.min(a.len() - 1)).collect::<Vec<_>>();
+        let prod = range.gate().inner_product(
+            ctx,
+            terms.iter().map(|&j| a_coeff(j)),
+            terms.iter().map(|&j| QuantumCell::Existing(b_coeff(e - j))),
+        );
+
+        // Enforce that the coefficient is 1 for e = 0 and 0 otherwise modulo Q
+        let rem = range.div_mod(ctx, prod, Q, num_bits).1;
+        let expected = if e == 0 { F::one() } else { F::zero() };
+        range.gate().assert_is_const(ctx, &rem, &expected);
+    }
+
+    // assert that the inverse has degree K - 1
+    assert_eq!(b.len() - 1, K - 1);
+
+    b
+}
+
+/// Build a polynomial from public constant coefficients
+///
+/// * `coeffs` are parsed as a vector of coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Each coefficient is loaded with `load_constant`, therefore it is fixed in the verifying key and cannot be changed by the prover
+/// * Use it for public polynomials known at setup time (e.g. a cyclotomic polynomial or a public key). Private inputs must be assigned as witnesses instead
+pub fn poly_from_constants<F: ScalarField>(
+    ctx: &mut Context<F>,
+    coeffs: &[F],
+) -> Vec<AssignedValue<F>> {
+    coeffs
+        .iter()
+        .map(|&coeff| ctx.load_constant(coeff))
+        .collect()
+}
+
+/// Assign a polynomial of degree DEG to the circuit from an iterator of coefficients
+///
+/// * The coefficients are assigned as private witnesses one at a time, without collecting them in an intermediate vector. This reduces the peak memory when loading large polynomials
+/// * `coeffs` yields the coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * The iterator must yield exactly DEG + 1 coefficients. Panics otherwise
+pub fn poly_assign_from_iter<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    coeffs: impl IntoIterator<Item = u64>,
+) -> Vec<AssignedValue<F>> {
+    let mut coeffs = coeffs.into_iter();
+    let mut poly = Vec::with_capacity(DEG + 1);
+
+    for _ in 0..=DEG {
+        let coeff = coeffs
+            .next()
+            .expect("the iterator yields less than DEG + 1 coefficients");
+        poly.push(ctx.load_witness(F::from(coeff)));
+    }
+
+    assert!(
+        coeffs.next().is_none(),
+        "the iterator yields more than DEG + 1 coefficients"
+    );
+
+    // assert that the assigned polynomial has degree DEG
+    assert_eq!(poly.len() - 1, DEG);
+
+    poly
+}
+
+/// Build the cyclotomic polynomial x^N + 1 as a vector of constant coefficients
+///
+/// * The output is parsed as a vector of assigned coefficients [1, 0, ..., 0, 1] of length N + 1 where the last element is the constant term
+/// * The coefficients are loaded with `load_constant`, therefore they are fixed in the circuit and the divisor is guaranteed to be well formed
+/// * The output can be used as `divisor` of `poly_divide_by_cyclo` with DEG_DVS = N
+pub fn cyclotomic_divisor<const N: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+) -> Vec<AssignedValue<F>> {
+    let mut coeffs = vec![F::zero(); N + 1];
+    coeffs[0] = F::one();
+    coeffs[N] = F::one();
+
+    let cyclo = poly_from_constants(ctx, &coeffs);
+
+    // assert that the cyclotomic polynomial has degree N
+    assert_eq!(cyclo.len() - 1, N);
+
+    cyclo
+}
+
+/// Build the monic polynomial (x - r_1) * (x - r_2) * ... * (x - r_n) from its roots, with coefficients in the field representation
+///
+/// * This is handy to build vanishing or selector polynomials over an evaluation domain inside the circuit
+/// * The output degree is equal to the number of roots n. With no roots, the output is the constant polynomial 1
+/// * The output is parsed as a vector of assigned coefficients [1, a_n-1, ..., a_1, a_0] where a_0 is the constant term. Negative coefficients are represented modulo the prime p of the field, e.g. (x - 1) * (x - 2) is [1, p - 3, 2]
+/// * Each factor (x - r_i) is represented as [1, -r_i] and multiplied with `poly_mul_diff_deg`. The coefficients are not reduced modulo Q, therefore no division is constrained
+/// * Assumes that the roots are in the range [0, Q - 1]
+/// * Assumes that Q^n < p / 2 where p is the prime field of the circuit, so that the integer coefficients of the product are represented without wrapping around
+pub fn poly_from_roots<const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    roots: &[AssignedValue<F>],
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // COEFFICIENTS OVERFLOW ANALYSIS
+    // The absolute values of the coefficients of the product sum up to (1 + r_1) * (1 + r_2) * ... * (1 + r_n) <= Q^n
+    // A coefficient c in [-(Q^n), Q^n] is represented as c or p - |c|, which is unambiguous as long as Q^n < p / 2
+    // Q^n < 2^(n * bits(Q)) and p / 2 > 2^(CAPACITY - 1), therefore it is enough that n * bits(Q) <= CAPACITY - 1
+    let q_num_bits = 64 - Q.leading_zeros();
+    let bound_bits = (roots.len() as u64).checked_mul(q_num_bits as u64);
+    assert!(
+        matches!(bound_bits, Some(bits) if bits < F::CAPACITY as u64),
+        "poly_from_roots: the coefficients of the product may overflow the prime field of the circuit"
+    );
+
+    let one = ctx.load_constant(F::one());
+    let mut poly = vec![one];
+
+    for root in roots {
+        let neg_root = range.gate().neg(ctx, *root);
+        poly = poly_mul_diff_deg(ctx, &poly, &[one, neg_root][..], range.gate());
+    }
+
+    // assert that the output polynomial has degree equal to the number of roots
+    assert_eq!(poly.len() - 1, roots.len());
+
+    poly
+}
+
+/// Takes a polynomial `divisor` represented by its coefficients in a vector.
+/// Takes a cyclotomic polynomial `dividend` f(x)=x^m+1 (m is a power of 2) of the form represented by its coefficients in a vector
+/// Output the remainder of the division of `dividend` by `dividend` as a vector of coefficients
+///
+/// * DEG_DVD is the degree of the `dividend` polynomial
+/// * DEG_DVS is the degree of the `divisor` polynomial
+/// * Q is the modulus of the Ring. Q is not required to be prime since the divisor is monic and the division never needs to invert a coefficient
+/// * Input polynomials is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the degree of dividend is either equal to (2 * DEG_DVS) - 2 or strictly less than DEG_DVS
+/// * If DEG_DVD < DEG_DVS, the quotient is zero and the remainder is the dividend itself. In that case no division is performed and the dividend is returned unchanged after enforcing that its coefficients are in the range [0, Q - 1]
+/// * Assumes that the coefficients of `dividend` are in the range [0, Q - 1]
+/// * Assumes that divisor is a cyclotomic polynomial with coefficients either 0 or 1
+/// * Constant divisors (DEG_DVS = 0) are rejected with a panic: they are not cyclotomic polynomials and dividing by them would require inverting their coefficient modulo Q
+/// * Assumes that dividend and divisor can be expressed as u64 values
+/// * Assumes that Q is chosen such that (Q-1) * (DEG_DVD - DEG_DVS + 1)] + Q-1 < p where p is the prime field of the circuit in order to avoid overflow during the multiplication
+/// * Panics if a precondition on the inputs is not satisfied. See `try_poly_divide_by_cyclo` for a version returning a `PolyError`
+pub fn poly_divide_by_cyclo<
+    const DEG_DVD: usize,
+    const DEG_DVS: usize,
+    const Q: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    dividend: Vec<AssignedValue<F>>,
+    divisor: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    try_poly_divide_by_cyclo::<DEG_DVD, DEG_DVS, Q, F>(ctx, dividend, divisor, range)
+        .unwrap_or_else(|err| panic!("poly_divide_by_cyclo: {}", err))
+}
+
+/// Same as `poly_divide_by_cyclo`, but returns an error instead of panicking if a precondition on the inputs is not satisfied
+///
+/// * Returns `PolyError::ConstantDivisor` if DEG_DVS = 0
+/// * Returns `PolyError::EmptyPolynomial` or `PolyError::DegreeMismatch` if the degree of dividend is not DEG_DVD or the degree of divisor is not DEG_DVS
+/// * Returns `PolyError::InvalidDividendDegree` if DEG_DVD is neither 2 * DEG_DVS - 2 (with DEG_DVS < DEG_DVD) nor less than DEG_DVS
+/// * Returns `PolyError::FieldOverflow` if the bound (Q-1) * (DEG_DVD - DEG_DVS + 1) + Q-1 of the coefficients of quotient * divisor + remainder does not fit in the capacity of the field
+pub fn try_poly_divide_by_cyclo<
+    const DEG_DVD: usize,
+    const DEG_DVS: usize,
+    const Q: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    dividend: Vec<AssignedValue<F>>,
+    divisor: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Result<Vec<AssignedValue<F>>, PolyError> {
+    // Reject constant divisors, which are not cyclotomic polynomials
+    if DEG_DVS == 0 {
+        return Err(PolyError::ConstantDivisor);
+    }
+    // Check that degree of dividend polynomial is equal to the constant DEG_DVD
+    check_degree(&dividend, DEG_DVD)?;
+    // Check that degree of divisor poly is equal to the constant DEG_DVS
+    check_degree(&divisor, DEG_DVS)?;
+    // If the degree of the dividend is less than the degree of the divisor, the dividend is already reduced.
+    // The quotient is zero and the remainder is the dividend itself
+    if DEG_DVD < DEG_DVS {
+        // The coefficients of dividend are in the range [0, Q - 1] by assumption.
+        // Since the dividend is returned as remainder, we enforce this constraint as it would be for a remainder computed outside the circuit
+        for coeff in &dividend {
+            range.check_less_than_safe(ctx, *coeff, Q);
+        }
+
+        return Ok(dividend);
+    }
+
+    // Check that degree of dividend is equal to (2 * DEG_DVS) - 2 and that DEG_DVS is strictly less than DEG_DVD
+    if DEG_DVD != (2 * DEG_DVS) - 2 || DEG_DVS >= DEG_DVD {
+        return Err(PolyError::InvalidDividendDegree {
+            deg_dvd: DEG_DVD,
+            deg_dvs: DEG_DVS,
+        });
+    }
+
+    // Check that the coefficients of quotient * divisor + remainder cannot overflow the prime field of the circuit
+    check_division_by_cyclo_bounds::<DEG_DVD, DEG_DVS, Q>(F::CAPACITY)?;
+
+    let (quotient, remainder) =
+        assign_division_by_cyclo_witness::<DEG_DVD, DEG_DVS, Q, F>(ctx, &dividend, &divisor);
+
+    Ok(poly_divide_by_cyclo_with_witness::<DEG_DVD, DEG_DVS, Q, F>(
+        ctx, dividend, divisor, quotient, remainder, range,
+    ))
+}
+
+/// Performs the long division of `dividend` by `divisor` outside the circuit and assigns the quotient and the remainder as witnesses
+///
+/// * The quotient is of degree DEG_DVD - DEG_DVS and the remainder is padded with leading zeroes to degree DEG_DVD
+/// * The witnesses are not constrained, `poly_divide_by_cyclo_with_witness` enforces that they are the result of the division
+fn assign_division_by_cyclo_witness<
+    const DEG_DVD: usize,
+    const DEG_DVS: usize,
+    const Q: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    dividend: &[AssignedValue<F>],
+    divisor: &[AssignedValue<F>],
+) -> (Vec<AssignedValue<F>>, Vec<AssignedValue<F>>) {
+    // long division operation performed outside the circuit
+    // Need to convert the dividend and divisor into a vector of u64
+    let dividend_to_u64 = vec_assigned_to_vec_u64(dividend);
+    let divisor_to_u64 = vec_assigned_to_vec_u64(divisor);
+
+    let (quotient_to_u64, remainder_to_u64) =
+        div_by_cyclo_witness::<DEG_DVD, DEG_DVS, Q>(&dividend_to_u64, &divisor_to_u64);
+
+    // Assign the quotient and remainder to the circuit
+    let mut quotient = vec![];
+    let mut remainder = vec![];
+
+    for i in 0..DEG_DVD - DEG_DVS + 1 {
+        let val = F::from(quotient_to_u64[i]);
+        let assigned_val = ctx.load_witness(val);
+        quotient.push(assigned_val);
+    }
+
+    for i in 0..DEG_DVD + 1 {
+        let val = F::from(remainder_to_u64[i]);
+        let assigned_val = ctx.load_witness(val);
+        remainder.push(assigned_val);
+    }
+
+    label_poly(&quotient, "quotient");
+    label_poly(&remainder, "remainder");
+
+    (quotient, remainder)
+}
+
+/// Same as `poly_divide_by_cyclo` for the product of two polynomials of the ring described by a validated parameter set
+///
+/// * `params` must be validated for the ring R_q = Z_Q[x]/(x^DEG_DVS + 1) against the field F, namely DEG_DVS = params.deg() + 1, DEG_DVD = 2 * params.deg() and Q = params.q()
+/// * The bound (Q-1) * (DEG_DVD - DEG_DVS + 1) + Q-1 of the chip is smaller than the bound (Q-1) * (Q-1) * DEG_DVS checked by `PolyParams::new`, therefore it is trusted
+/// * Panics if `params` does not describe the ring of the chip
+pub fn poly_divide_by_cyclo_with_params<
+    const DEG_DVD: usize,
+    const DEG_DVS: usize,
+    const Q: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    dividend: Vec<AssignedValue<F>>,
+    divisor: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    params: &PolyParams,
+) -> Vec<AssignedValue<F>> {
+    assert!(
+        DEG_DVS == params.deg() + 1 && DEG_DVD == 2 * params.deg(),
+        "poly_divide_by_cyclo_with_params: the dividend must be the product of two polynomials of degree {}",
+        params.deg()
+    );
+    assert_params::<F>(params, params.deg(), Q, "poly_divide_by_cyclo_with_params");
+
+    poly_divide_by_cyclo::<DEG_DVD, DEG_DVS, Q, F>(ctx, dividend, divisor, range)
+}
+
+/// Computes the bounds of the coefficients of quotient * divisor and of quotient * divisor + remainder in `enforce_division_by_cyclo`
+///
+/// * Returns ((Q-1) * (DEG_DVD - DEG_DVS + 1), (Q-1) * (DEG_DVD - DEG_DVS + 1) + Q-1)
+/// * The bounds are computed on u128 so that they cannot wrap around
+/// * Panics if a bound does not fit in `field_capacity` bits, namely if the coefficients may overflow a prime field of that capacity
+fn division_by_cyclo_bounds<const DEG_DVD: usize, const DEG_DVS: usize, const Q: u64>(
+    field_capacity: u32,
+) -> (u128, u128) {
+    let prod_bound = (Q as u128 - 1) * (DEG_DVD - DEG_DVS + 1) as u128;
+    assert!(
+        fits_in_capacity(prod_bound, field_capacity),
+        "poly_divide_by_cyclo: the coefficients of quotient * divisor may overflow the prime field of the circuit"
+    );
+
+    let sum_bound = prod_bound + (Q as u128 - 1);
+    assert!(
+        fits_in_capacity(sum_bound, field_capacity),
+        "poly_divide_by_cyclo: the coefficients of quotient * divisor + remainder may overflow the prime field of the circuit"
+    );
+
+    (prod_bound, sum_bound)
+}
+
+/// Enforces that `remainder` is the remainder of the division of `dividend` by the cyclotomic polynomial `divisor`, given the quotient and remainder as witnesses
+///
+/// * Compared to `poly_divide_by_cyclo`, this function does not perform the long division outside the circuit. It only emits the constraints that verify the supplied witnesses
+/// * This is useful when the same division result is reused, or when the witnesses are generated separately, e.g. with `utils::div_by_cyclo_witness`
+/// * The leading coefficient of the quotient is only constrained through the reconstruction identity. `assert_leading_quotient_coeff` can be used to pin it down exactly
+/// * DEG_DVD is the degree of the `dividend` polynomial
+/// * DEG_DVS is the degree of the `divisor` polynomial
+/// * Q is the modulus of the Ring
+/// * Input polynomials is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * `quotient` must be of degree DEG_DVD - DEG_DVS
+/// * `remainder` must be padded with leading zeroes to degree DEG_DVD
+/// * Assumes that the degree of dividend is equal to (2 * DEG_DVS) - 2
+/// * Assumes that the coefficients of `dividend` are in the range [0, Q - 1]
+/// * Assumes that divisor is a cyclotomic polynomial with coefficients either 0 or 1. Constant divisors (DEG_DVS = 0) are rejected with a panic
+/// * Assumes that Q is chosen such that (Q-1) * (DEG_DVD - DEG_DVS + 1)] + Q-1 < p where p is the prime field of the circuit in order to avoid overflow during the multiplication
+pub fn poly_divide_by_cyclo_with_witness<
+    const DEG_DVD: usize,
+    const DEG_DVS: usize,
+    const Q: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    dividend: Vec<AssignedValue<F>>,
+    divisor: Vec<AssignedValue<F>>,
+    quotient: Vec<AssignedValue<F>>,
+    remainder: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
+
+    enforce_division_by_cyclo::<DEG_DVD, DEG_DVS, Q, F>(
+        ctx, &dividend, &divisor, &quotient, &remainder, range,
+    );
+
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("poly_divide_by_cyclo_with_witness", ctx, stats_start);
+
+    remainder
+}
+
+/// Same check as `division_by_cyclo_bounds`, but returns `PolyError::FieldOverflow` instead of panicking
+///
+/// * The bound (Q-1) * (DEG_DVD - DEG_DVS + 1) + Q-1 of quotient * divisor + remainder is also a bound of quotient * divisor, therefore it is the only one to check
+fn check_division_by_cyclo_bounds<const DEG_DVD: usize, const DEG_DVS: usize, const Q: u64>(
+    field_capacity: u32,
+) -> Result<(), PolyError> {
+    let sum_bound = (Q as u128 - 1) * (DEG_DVD - DEG_DVS + 1) as u128 + (Q as u128 - 1);
+
+    check_bound(sum_bound, field_capacity)
+}
+
+/// Enforces that the polynomial a is monic, namely that its leading coefficient is equal to 1
+///
+/// * Under the coefficient ordering of this crate, the leading coefficient is the first element a[0], namely the coefficient of x^DEG
+/// * This is a precondition of the division by a monic polynomial, e.g. the cyclotomic divisor of `poly_divide_by_cyclo`
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+pub fn assert_monic<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    gate: &GateChip<F>,
+) {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    gate.assert_is_const(ctx, &a[0], &F::one());
+}
+
+/// Enforces that the leading coefficient of the quotient of the division of `dividend` by the cyclotomic polynomial `divisor` is the leading coefficient of the dividend divided by the leading coefficient of the divisor
+///
+/// * This is an optional constraint on top of `poly_divide_by_cyclo_with_witness`, which only range checks the quotient and relies on the reconstruction identity quotient * divisor + remainder = dividend
+/// * The leading coefficient of the divisor is enforced to be 1, as for any cyclotomic polynomial. The leading coefficient of the quotient is then enforced to be equal to the leading coefficient of the dividend, which pins it down exactly
+/// * DEG_DVD is the degree of the `dividend` polynomial and DEG_DVS the degree of the `divisor` polynomial
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * `quotient` must be of degree DEG_DVD - DEG_DVS
+pub fn assert_leading_quotient_coeff<const DEG_DVD: usize, const DEG_DVS: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    dividend: &[AssignedValue<F>],
+    divisor: &[AssignedValue<F>],
+    quotient: &[AssignedValue<F>],
+    gate: &GateChip<F>,
+) {
+    // Assert that degree of dividend polynomial is equal to the constant DEG_DVD
+    assert_eq!(dividend.len() - 1, DEG_DVD);
+    // Assert that degree of divisor poly is equal to the constant DEG_DVS
+    assert_eq!(divisor.len() - 1, DEG_DVS);
+    // assert that the degree of quotient is DEG_DVD - DEG_DVS
+    assert_eq!(quotient.len() - 1, DEG_DVD - DEG_DVS);
+
+    // The leading coefficient of a cyclotomic polynomial is 1
+    assert_monic::<DEG_DVS, F>(ctx, divisor, gate);
+
+    // The leading coefficient of quotient * divisor is quotient[0] * divisor[0] = quotient[0], and the remainder has a zero leading coefficient
+    // Therefore quotient[0] must be equal to dividend[0]
+    ctx.constrain_equal(&quotient[0], &dividend[0]);
+}
+
+/// Emits the constraints of `poly_divide_by_cyclo_with_witness` and returns the product quotient * divisor
+///
+/// * The product is of degree DEG_DVD and its coefficients are in the range [0, (Q-1) * (DEG_DVD - DEG_DVS + 1)]
+fn enforce_division_by_cyclo<
+    const DEG_DVD: usize,
+    const DEG_DVS: usize,
+    const Q: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    dividend: &[AssignedValue<F>],
+    divisor: &[AssignedValue<F>],
+    quotient: &[AssignedValue<F>],
+    remainder: &[AssignedValue<F>],
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // Reject constant divisors, which are not cyclotomic polynomials
+    assert!(
+        DEG_DVS > 0,
+        "poly_divide_by_cyclo_with_witness: the divisor must be a cyclotomic polynomial of degree at least 1, got a constant divisor"
+    );
+
+    // Assert that degree of dividend polynomial is equal to the constant DEG_DVD
+    assert_eq!(dividend.len() - 1, DEG_DVD);
+    // Assert that degree of divisor poly is equal to the constant DEG_DVS
+    assert_eq!(divisor.len() - 1, DEG_DVS);
+    // Assert that degree of dividend is equal to (2 * DEG_DVS) - 2
+    assert_eq!(dividend.len() - 1, (2 * DEG_DVS) - 2);
+
+    // DEG_DVS must be strictly less than DEG_DVD
+    assert!(DEG_DVS < DEG_DVD);
+
+    // assert that the degree of quotient is DEG_DVD - DEG_DVS
+    assert_eq!(quotient.len() - 1, DEG_DVD - DEG_DVS);
+
+    // assert that the degree of remainder is DEG_DVD
+    assert_eq!(remainder.len() - 1, DEG_DVD);
+
+    // Quotient is obtained by dividing the coefficients of the dividend by the highest degree coefficient of divisor
+    // The coefficients of dividend are in the range [0, Q - 1] by assumption.
+    // The leading coefficient of divisor is 1 by assumption.
+    // Therefore, the coefficients of quotient have to be in the range [0, Q - 1]
+
+    // Remainder is equal to dividend - (quotient * divisor).
+    // The coefficients of dividend are in the range [0, Q - 1] by assumption.
+    // The coefficients of quotient are in the range [0, Q - 1] by constraint set below.
+    // The coefficients of divisior are either 0, 1 by assumption of the cyclotomic polynomial.
+    // It follows that the coefficients of quotient * divisor are in the range [0, Q - 1]
+    // The remainder (as result dividend - (quotient * divisor)) might have coefficients that are negative. In that case we add Q to them to make them positive.
+    // Therefore, the coefficients of remainder are in the range [0, Q - 1]
+    // The remainder has degree strictly less than DEG_DVS, namely it is padded with DEG_DVD - DEG_DVS + 1 leading zeroes and its DEG_DVS lower coefficients are remainder[(DEG_DVD - DEG_DVS + 1)..]
+
+    // Since the quotient and the remainder are computed outside the circuit, we need to enforce these constraints
+    // The checks against Q cover the quotient and the lower coefficients of the remainder
+    let padding = DEG_DVD - DEG_DVS + 1;
+    for coeff in quotient.iter().chain(&remainder[padding..]) {
+        range.check_less_than_safe(ctx, *coeff, Q);
+    }
+
+    // The padding of the remainder is enforced to be zero. This does not require any lookup
+    for coeff in &remainder[..padding] {
+        range.gate().assert_is_const(ctx, coeff, &F::zero());
+    }
+
+    // check that quotient * divisor + remainder = dividend
+
+    // DEGREE ANALYSIS
+    // Quotient is of degree DEG_DVD - DEG_DVS
+    // Divisor is of degree DEG_DVS
+    // Quotient * divisor is of degree DEG_DVD
+    // Remainder is of degree DEG_DVD
+    // Quotient * divisor + rem is of degree DEG_DVD
+    // Dividend is of degree DEG_DVD
+
+    // Perform the polynomial multiplication between quotient and divisor
+
+    // COEFFICIENTS OVERFLOW ANALYSIS
+    // The coefficients of quotient are in the range [0, Q - 1] by constraint set above.
+    // The coefficients of divisor are either 0, 1 by assumption.
+    // The coefficients of prod are calculated as $c_{k} = \sum_{i=0}^{k} quotient[i] * divisor[k - i]$. Where k is the index of the coefficient c of prod.
+    // For two polynomials of differents degree n and m (where m < n), the max number of multiplication performed inside the summation is m + 1.
+    // The quotient is of degree DEG_DVD - DEG_DVS
+    // The divisor is of degree DEG_DVS
+    // Since DEG_DVD = (2 * DEG_DVS) - 2 it follows that the degree of the divisor is greater than the degree of quotient.
+    // In that case there are max (degree quotient + 1) multiplications in the sum. Namely DEG_DVD - DEG_DVS + 1 multiplications.
+    // The maximum value of the coffiecient of prod is (Q-1) * (1) * (DEG_DVD - DEG_DVS + 1).
+    // Q needs to be chosen such that (Q-1) * (DEG_DVD - DEG_DVS + 1) < p where p is the prime field of the circuit in order to avoid overflow during the multiplication.
+    // Note that this is a subset of the assumption of the circuit
+    // Therefore, the coefficients of prod are in the range [0, (Q-1) * (DEG_DVD - DEG_DVS + 1)]
+
+    // We use a polynomial multiplication algorithm that does not require the input polynomials to be of the same degree
+
+    // Turn the overflow analysis above and below into an executable invariant
+    let (_, sum_bound) = division_by_cyclo_bounds::<DEG_DVD, DEG_DVS, Q>(F::CAPACITY);
+
+    let prod = poly_mul_diff_deg(ctx, quotient, divisor, range.gate());
+
+    // The degree of prod is DEG_DVD
+    assert_eq!(prod.len() - 1, DEG_DVD);
+
+    // Perform the addition between prod and remainder
+
+    // DEGREE ANALYSIS
+    // Prod is of degree DEG_DVD
+    // Remainder is of degree DEG_DVD
+    // Prod + rem is of degree DEG_DVD
+
+    // COEFFICIENTS OVERFLOW ANALYSIS
+    // The coefficients of prod are in the range [0, (Q-1) * (DEG_DVD - DEG_DVS + 1)] by the constraint above.
+    // The coefficients of remainder are in the range [0, Q - 1] by constraint set above.
+    // Therefore, the coefficients of prod + remainder are in the range [0, [0, (Q-1) * (DEG_DVD - DEG_DVS + 1)] + Q-1].
+    // Q needs to be chosen such that (Q-1) * (DEG_DVD - DEG_DVS + 1)] + Q-1 < p where p is the prime field of the circuit in order to avoid overflow during the addition.
+    // This is true by assumption of the chip.
+
+    let sum = poly_add::<DEG_DVD, F>(ctx, &prod, remainder, range.gate());
+
+    // assert that the degree of sum is DEG_DVD
+    assert_eq!(sum.len() - 1, DEG_DVD);
+
+    // We can reduce the coefficients of sum modulo Q to make them in the range [0, Q - 1]
+
+    // get the number of bits needed to represent the value of (Q-1) * (DEG_DVD - DEG_DVS + 1)] + Q-1
+    let binary_representation = format!("{:b}", sum_bound); // Convert to binary (base-2)
+    let num_bits = binary_representation.len();
+
+    // The coefficients of sum are in the range [0, (Q-1) * (DEG_DVD - DEG_DVS + 1)] + Q-1] according to the polynomial addition constraint set above.
+    // Therefore the coefficients of sum are known to have <= `num_bits` bits, therefore they satisfy the assumption of the `poly_reduce` chip
+    let sum_mod = poly_reduce::<DEG_DVD, Q, F>(ctx, sum, range, num_bits);
+
+    // assert that the degree of sum_mod is DEG_DVD
+    assert_eq!(sum_mod.len() - 1, DEG_DVD);
+
+    // Enforce that sum_mod = dividend
+    for i in 0..=DEG_DVD {
+        let bool = range.gate().is_equal(ctx, sum_mod[i], dividend[i]);
+        range.gate().assert_is_const(ctx, &bool, &F::from(1))
+    }
+
+    prod
+}
+
+/// Same as `poly_divide_by_cyclo`, but the remainder is returned trimmed to its true degree DEG = DEG_DVS - 1 instead of being padded to degree DEG_DVD
+///
+/// * The remainder of the division by a polynomial of degree DEG_DVS has degree at most DEG_DVS - 1. `poly_divide_by_cyclo` pads it with DEG_DVD - DEG leading zeroes to compute remainder + prod
+/// * The leading DEG_DVD - DEG coefficients of the padded remainder are enforced to be zero inside the division, therefore dropping them does not require any additional constraint
+/// * Returns the DEG + 1 coefficients [r_DEG, r_DEG-1, ..., r_1, r_0] where r_0 is the constant term, namely the lower coefficients of the padded remainder
+/// * DEG is passed as a const generic since DEG_DVS - 1 cannot be computed from the const generics. It must be equal to DEG_DVS - 1
+/// * Same assumptions as `poly_divide_by_cyclo`
+pub fn poly_divide_by_cyclo_trimmed<
+    const DEG_DVD: usize,
+    const DEG_DVS: usize,
+    const DEG: usize,
+    const Q: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    dividend: Vec<AssignedValue<F>>,
+    divisor: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // Assert that DEG is the degree of the remainder
+    assert_eq!(DEG + 1, DEG_DVS);
+
+    // Assert that degree of dividend is equal to (2 * DEG_DVS) - 2
+    assert_eq!(dividend.len() - 1, (2 * DEG_DVS) - 2);
+
+    let remainder = poly_divide_by_cyclo::<DEG_DVD, DEG_DVS, Q, F>(ctx, dividend, divisor, range);
+
+    // remainder[..(DEG_DVD - DEG)] is the padding, which is enforced to be zero by the division
+    let trimmed = remainder[(DEG_DVD - DEG)..].to_vec();
+
+    // assert that the trimmed remainder has degree DEG
+    assert_eq!(trimmed.len() - 1, DEG);
+
+    trimmed
+}
+
+/// Same as `poly_divide_by_cyclo`, but the quotient, the remainder and the product quotient * divisor are exposed as public instance values
+///
+/// * This allows another proof to bind to the result of the division. The values are pushed to `make_public`, which is wired to the instance column by the circuit builder
+/// * The layout of the public values is stable: first the DEG_DVD - DEG_DVS + 1 coefficients of the quotient, then the DEG_DVD + 1 coefficients of the remainder, then the DEG_DVD + 1 coefficients of the product. Each polynomial is in the order [a_DEG, a_DEG-1, ..., a_1, a_0]
+/// * The dividend and the divisor are not made public. The caller can push them to `make_public` as well if needed
+/// * Returns the remainder
+/// * Same assumptions as `poly_divide_by_cyclo_with_witness`. In particular the degree of dividend must be equal to (2 * DEG_DVS) - 2
+pub fn poly_divide_by_cyclo_public<
+    const DEG_DVD: usize,
+    const DEG_DVS: usize,
+    const Q: u64,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    dividend: Vec<AssignedValue<F>>,
+    divisor: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    make_public: &mut Vec<AssignedValue<F>>,
+) -> Vec<AssignedValue<F>> {
+    // Assert that degree of dividend is equal to (2 * DEG_DVS) - 2
+    assert_eq!(dividend.len() - 1, (2 * DEG_DVS) - 2);
+
+    let (quotient, remainder) =
+        assign_division_by_cyclo_witness::<DEG_DVD, DEG_DVS, Q, F>(ctx, &dividend, &divisor);
+
+    let prod = enforce_division_by_cyclo::<DEG_DVD, DEG_DVS, Q, F>(
+        ctx, &dividend, &divisor, &quotient, &remainder, range,
+    );
+
+    make_public.extend(quotient);
+    make_public.extend(remainder.iter().copied());
+    make_public.extend(prod);
+
+    remainder
+}
+
+/// Takes a polynomial of degree at most 2*DEG + 1 and output its reduction by the cyclotomic polynomial x^(DEG+1) + 1
+///
+/// * The reduction is performed by folding the coefficient of x^(k + DEG + 1) onto the coefficient of x^k with a sign flip, namely x^(DEG+1) = -1 in the ring
+/// * DEG is the degree of the output polynomial. The ring is Z_Q[x]/(x^(DEG+1) + 1)
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_D, a_D-1, ..., a_1, a_0] where a_0 is the constant term and DEG <= D <= 2*DEG + 1
+/// * Assumes that the coefficients of the input polynomial are in the range [0, Q - 1]
+/// * Assumes that 2Q - 1 < p where p is the prime field of the circuit
+pub fn poly_reduce_negacyclic<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    let n = DEG + 1;
+    let d = input.len() - 1;
+
+    // Assert that a single fold is enough to reduce the input polynomial
+    assert!(d >= DEG && d < 2 * n);
+
+    // get the number of bits needed to represent the value of 2Q - 1
+    // The bound is computed on u128 so that it cannot wrap around for Q above 2^63
+    let binary_representation = format!("{:b}", (2 * Q as u128 - 1));
+    let num_bits = binary_representation.len();
+
+    let mut out = vec![];
+
+    // out[i] is the coefficient of x^k where k = DEG - i
+    // input[d - e] is the coefficient of x^e
+    for i in 0..=DEG {
+        let k = DEG - i;
+        let low = input[d - k];
+
+        if k + n <= d {
+            let high = input[d - k - n];
+
+            // The coefficients of low and high are in the range [0, Q - 1] by assumption
+            // Therefore low - high + Q is in the range [1, 2Q - 1] and is known to have <= `num_bits` bits
+            let diff = range.gate().sub(ctx, low, high);
+            let shifted = range
+                .gate()
+                .add(ctx, diff, QuantumCell::Constant(F::from(Q)));
+            let rem = range.div_mod(ctx, shifted, Q, num_bits).1;
+            out.push(rem);
+        } else {
+            out.push(low);
+        }
+    }
+
+    // assert that the reduced polynomial has degree DEG
+    assert_eq!(out.len() - 1, DEG);
+
+    out
+}
+
+/// Takes a polynomial of degree at most 2*DEG + 1 and output its reduction by the polynomial x^(DEG+1) - 1
+///
+/// * This is the reduction of the cyclic ring Z_Q[x]/(x^(DEG+1) - 1). Since x^(DEG+1) = 1 in the ring, the coefficient of x^(k + DEG + 1) is added to the coefficient of x^k
+/// * Compared to `poly_reduce_negacyclic`, where x^(DEG+1) = -1 and the folded coefficient is subtracted, there is no sign flip
+/// * DEG is the degree of the output polynomial. The ring is Z_Q[x]/(x^(DEG+1) - 1)
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_D, a_D-1, ..., a_1, a_0] where a_0 is the constant term and DEG <= D <= 2*DEG + 1
+/// * Assumes that the coefficients of the input polynomial are in the range [0, Q - 1]
+/// * Assumes that 2Q - 2 < p where p is the prime field of the circuit
+pub fn poly_reduce_cyclic<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    input: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    let n = DEG + 1;
+    let d = input.len() - 1;
+
+    // Assert that a single fold is enough to reduce the input polynomial
+    assert!(d >= DEG && d < 2 * n);
+
+    // get the number of bits needed to represent the value of 2Q - 2
+    // The bound is computed on u128 so that it cannot wrap around for Q above 2^63
+    let binary_representation = format!("{:b}", (2 * Q as u128 - 2));
+    let num_bits = binary_representation.len();
+
+    let mut out = vec![];
+
+    // out[i] is the coefficient of x^k where k = DEG - i
+    // input[d - e] is the coefficient of x^e
+    for i in 0..=DEG {
+        let k = DEG - i;
+        let low = input[d - k];
+
+        if k + n <= d {
+            let high = input[d - k - n];
+
+            // The coefficients of low and high are in the range [0, Q - 1] by assumption
+            // Therefore low + high is in the range [0, 2Q - 2] and is known to have <= `num_bits` bits
+            let sum = range.gate().add(ctx, low, high);
+            let rem = range.div_mod(ctx, sum, Q, num_bits).1;
+            out.push(rem);
+        } else {
+            out.push(low);
+        }
+    }
+
+    // assert that the reduced polynomial has degree DEG
+    assert_eq!(out.len() - 1, DEG);
+
+    out
+}
+
+/// Enforces that the polynomials a and b are equal, namely a[i] = b[i] for each coefficient
+///
+/// * The coefficients are constrained to be equal as field elements, therefore the polynomials must be given in the same representation, e.g. both reduced modulo Q. See `poly_assert_congruent` to compare elements of R_q
+/// * DEG is the degree of the input polynomials
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+pub fn poly_assert_equal<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+) {
+    // assert that the input polynomials have degree DEG
+    assert_eq!(a.len() - 1, DEG);
+    assert_eq!(b.len() - 1, DEG);
+
+    for i in 0..=DEG {
+        ctx.constrain_equal(&a[i], &b[i]);
+    }
+}
+
+/// Enforces that the polynomials a and b represent the same element of the ring R_q = Z_Q[x]/(x^(DEG+1) + 1), namely a ≡ b (mod x^(DEG+1) + 1, Q)
+///
+/// * Both operands are reduced with `poly_reduce_negacyclic` and the reduced coefficients are constrained to be equal
+/// * a and b do not need to have the same degree. Each of them is of degree D with DEG <= D <= 2*DEG + 1
+/// * DEG is the degree of the reduced polynomials. The ring is Z_Q[x]/(x^(DEG+1) + 1)
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_D, a_D-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the input polynomials are in the range [0, Q - 1]
+/// * Assumes that 2Q - 1 < p where p is the prime field of the circuit
+pub fn poly_assert_congruent<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    b: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) {
+    let a_reduced = poly_reduce_negacyclic::<DEG, Q, F>(ctx, a, range);
+    let b_reduced = poly_reduce_negacyclic::<DEG, Q, F>(ctx, b, range);
+
+    // The reduced coefficients are in the range [0, Q - 1], therefore they are equal if and only if the ring elements are equal
+    for i in 0..=DEG {
+        ctx.constrain_equal(&a_reduced[i], &b_reduced[i]);
+    }
+}
+
+/// Build the product of the polynomials a and b in the ring R_q = Z_Q[x]/(x^(DEG+1) + 1)
+///
+/// * DEG is the degree of the input polynomials. DEG + 1 must be a power of 2
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the input polynomials are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit in order to avoid overflow during the multiplication
+pub fn poly_mul_mod<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    b: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    poly_mul_mod_with::<DEG, Q, F>(ctx, a, b, range, ReductionStrategy::DivMod)
+}
+
+/// Build the product of the polynomials a and b in the ring R_q = Z_Q[x]/(x^(DEG+1) + 1), using the given reduction strategy for the coefficients of the product
+///
+/// * Same as `poly_mul_mod` when `strategy` is `ReductionStrategy::DivMod`
+/// * DEG is the degree of the input polynomials. DEG + 1 must be a power of 2
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the input polynomials are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit in order to avoid overflow during the multiplication
+pub fn poly_mul_mod_with<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    b: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    strategy: ReductionStrategy,
+) -> Vec<AssignedValue<F>> {
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
+
+    // The degree of the product is constrained to be 2*DEG according to the logic of the `poly_mul_equal_deg` chip
+    // The coefficients of the product are in the range [0, (Q-1) * (Q-1) * (DEG+1)]
+    let prod = poly_mul_equal_deg::<DEG, F>(ctx, &a, &b, range.gate());
+
+    // The bound is computed on u128 so that it cannot wrap around for Q above 32 bits
+    let prod_bound = (Q as u128 - 1)
+        .checked_mul(Q as u128 - 1)
+        .and_then(|bound| bound.checked_mul(DEG as u128 + 1))
+        .expect("poly_mul_mod: the bound of the product overflows u128");
+    assert!(
+        fits_in_field::<F>(prod_bound),
+        "poly_mul_mod: the coefficients of the product may overflow the prime field of the circuit"
+    );
+
+    // get the number of bits needed to represent the value of (Q-1) * (Q-1) * (DEG+1)
+    let binary_representation = format!("{:b}", prod_bound);
+    let num_bits = binary_representation.len();
+
+    // Reduce the coefficients of prod by modulo `Q`
+    let mut prod_mod = vec![];
+    for coeff in prod {
+        let rem = reduce_coeff::<Q, F>(ctx, coeff, range, num_bits, strategy);
+        prod_mod.push(rem);
+    }
+
+    // The coefficients of prod_mod are in the range [0, Q - 1], which satisfies the assumption of the `poly_reduce_negacyclic` chip
+    let out = poly_reduce_negacyclic::<DEG, Q, F>(ctx, prod_mod, range);
+
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("poly_mul_mod", ctx, stats_start);
+
+    out
+}
+
+/// Build the product of the polynomials a and b in the ring described by a validated parameter set
+///
+/// * Same as `poly_mul_mod`, where `params` must be validated for the ring R_q = Z_Q[x]/(x^(DEG+1) + 1) against the field F, namely DEG = params.deg() and Q = params.q()
+/// * The bound (Q-1) * (Q-1) * (DEG+1) of the coefficients of the product was checked against the field by `PolyParams::new`, therefore it is trusted and `params.mul_num_bits()` is used as `num_bits`
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the input polynomials are in the range [0, Q - 1]
+/// * Panics if `params` does not describe the ring of the chip
+pub fn poly_mul_mod_with_params<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    b: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    params: &PolyParams,
+) -> Vec<AssignedValue<F>> {
+    assert_params::<F>(params, DEG, Q, "poly_mul_mod_with_params");
+
+    #[cfg(feature = "stats")]
+    let stats_start = crate::chips::stats::start(ctx);
+
+    // The degree of the product is constrained to be 2*DEG according to the logic of the `poly_mul_equal_deg` chip
+    let prod = poly_mul_equal_deg::<DEG, F>(ctx, &a, &b, range.gate());
+
+    // Reduce the coefficients of prod by modulo `Q`. Their bound is covered by the validated parameter set
+    let mut prod_mod = vec![];
+    for coeff in prod {
+        let rem = range.div_mod(ctx, coeff, Q, params.mul_num_bits()).1;
+        prod_mod.push(rem);
+    }
+
+    // The coefficients of prod_mod are in the range [0, Q - 1], which satisfies the assumption of the `poly_reduce_negacyclic` chip
+    let out = poly_reduce_negacyclic::<DEG, Q, F>(ctx, prod_mod, range);
+
+    #[cfg(feature = "stats")]
+    crate::chips::stats::record("poly_mul_mod_with_params", ctx, stats_start);
+
+    out
+}
+
+/// Panics if `params` was not validated for the ring of degree `deg` and modulus `q` against the field F
+///
+/// * `params` may have been validated against a field of smaller capacity than F, since the bounds then also hold in F
+fn assert_params<F: ScalarField>(params: &PolyParams, deg: usize, q: u64, chip: &str) {
+    assert!(
+        params.deg() == deg && params.q() == q,
+        "{}: the parameter set (deg = {}, q = {}) does not match the chip (DEG = {}, Q = {})",
+        chip,
+        params.deg(),
+        params.q(),
+        deg,
+        q
+    );
+    assert!(
+        params.field_capacity() <= F::CAPACITY,
+        "{}: the parameter set was validated against a larger field than the field of the circuit",
+        chip
+    );
+}
+
+/// Build the square of the polynomial a in the ring R_q = Z_Q[x]/(x^(DEG+1) + 1), e.g. s^2 for the relinearization key
+///
+/// * Fuses the symmetric squaring of `poly_square`, the negacyclic fold of `poly_reduce_negacyclic` and the reduction modulo Q into a single pass
+/// * For each exponent e in [0, DEG], the products a_i * a_j with i + j = e are accumulated into pos and the products with i + j = e + DEG + 1 into neg, since x^(DEG+1) = -1 in the ring.
+///   Each distinct product with i < j is computed once and doubled, the diagonal product is added once. Then pos + offset - neg is reduced modulo Q with a single `div_mod`
+/// * Only the DEG + 1 output coefficients are materialized, the product of degree 2*DEG is never assigned
+/// * GATE COUNT: about (DEG + 1)^2 / 2 `mul_add` gates, 4 * (DEG + 1) gates to combine pos and neg and DEG + 1 `div_mod`.
+///   `poly_mul_mod(a, a)` takes about 2 * (DEG + 1)^2 gates for the product, 2*DEG + 1 `div_mod` for the coefficients of the product and DEG `div_mod` for the negacyclic fold, namely roughly 4 times more multiplication gates and 3 times more reductions
+/// * DEG is the degree of the input polynomial. DEG + 1 must be a power of 2
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Outputs the same polynomial as `poly_mul_mod(a, a)`
+/// * Assumes that the coefficients of a are in the range [0, Q - 1]
+pub fn poly_square_mod<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    let n = DEG + 1;
+    let gate = range.gate();
+
+    // For each exponent e, pos and neg together are the sum of exactly DEG + 1 products of values in the range [0, Q - 1], therefore both are in the range [0, (Q-1) * (Q-1) * (DEG+1)]
+    // To keep pos - neg non negative, a multiple of Q greater than neg is added: offset = Q * (Q-1) * (DEG+1)
+    // The coefficients of pos + offset - neg are in the range [0, (Q-1) * (Q-1) * (DEG+1) + Q * (Q-1) * (DEG+1)]
+    let offset = (Q as u128 * (Q as u128 - 1))
+        .checked_mul(DEG as u128 + 1)
+        .expect("coefficient bound of the square overflows u128");
+    let bound = (Q as u128 - 1)
+        .checked_mul(Q as u128 - 1)
+        .and_then(|bound| bound.checked_mul(DEG as u128 + 1))
+        .and_then(|bound| bound.checked_add(offset))
+        .expect("coefficient bound of the square overflows u128");
+
+    // The bound must be less than p in order for the coefficients not to overflow
+    assert!(fits_in_field::<F>(bound));
+
+    // get the number of bits needed to represent the bound
+    let binary_representation = format!("{:b}", bound);
+    let num_bits = binary_representation.len();
+
+    let offset_fe = F::from(Q) * F::from(Q - 1) * F::from(DEG as u64 + 1);
+
+    // a[DEG - i] is the coefficient of x^i
+    let coeff = |i: usize| a[DEG - i];
+
+    // Returns the sum of the products coeff(i) * coeff(t - i) over all i in [0, DEG] with t - i in [0, DEG], computing each symmetric product once
+    let symmetric_sum = |ctx: &mut Context<F>, t: usize| {
+        // Accumulate the products coeff(i) * coeff(t - i) for i < t - i
+        let mut acc = QuantumCell::Constant(F::zero());
+        let mut i = t.saturating_sub(DEG);
+        while 2 * i < t {
+            acc = QuantumCell::Existing(gate.mul_add(ctx, coeff(i), coeff(t - i), acc));
+            i += 1;
+        }
+
+        // The diagonal product coeff(t/2) * coeff(t/2) only appears when t is even and t/2 <= DEG
+        let diag = if t % 2 == 0 && t / 2 <= DEG {
+            QuantumCell::Existing(gate.mul(ctx, coeff(t / 2), coeff(t / 2)))
+        } else {
+            QuantumCell::Constant(F::zero())
+        };
+
+        // 2 * acc + diag
+        gate.mul_add(ctx, acc, QuantumCell::Constant(F::from(2)), diag)
+    };
+
+    let mut out = vec![];
+
+    // out[DEG - e] is the coefficient of x^e
+    for e in (0..n).rev() {
+        let pos = symmetric_sum(ctx, e);
+        let neg = symmetric_sum(ctx, e + n);
+
+        let shifted = gate.add(ctx, pos, QuantumCell::Constant(offset_fe));
+        let diff = gate.sub(ctx, shifted, neg);
+
+        // diff is known to have <= `num_bits` bits, which satisfies the assumption of `div_mod`
+        let rem = range.div_mod(ctx, diff, Q, num_bits).1;
+        out.push(rem);
+    }
+
+    // assert that the squared polynomial has degree DEG
+    assert_eq!(out.len() - 1, DEG);
+
+    out
+}
+
+/// Product of polynomials whose reduction in the ring R_q = Z_Q[x]/(x^(DEG+1) + 1) is deferred to the end of a multiplication chain
+///
+/// * Compared to chaining `poly_mul_mod`, the convolutions are performed without reducing the intermediate products. A single negacyclic and modular reduction is performed by `LazyProduct::reduce`
+/// * The coefficients of the product are tracked by an upper bound on their number of bits, namely every coefficient is smaller than 2^bound_bits
+/// * Multiplying by a polynomial whose coefficients are smaller than 2^b_bits with at most m coefficients in each sum of the convolution gives bound_bits + b_bits + bits(m)
+/// * MAXIMUM CHAIN LENGTH: for k factors with coefficients in the range [0, Q - 1] and degree DEG, the product has bound_bits <= k * bits(Q - 1) + (k - 1) * bits(DEG + 1), and `reduce` needs bits(Q) + bound_bits + bits(k) + 1 bits.
+///   Both must fit in the capacity of the field, e.g. 253 bits for BN254. With Q of 54 bits and DEG + 1 = 4096 (13 bits), a chain of 3 factors needs 54 + 188 + 2 + 1 = 245 bits, while a chain of 4 factors has bound_bits = 255 and exceeds the capacity
+/// * `mul` panics as soon as the tracked bound exceeds the capacity of the field, before any constraint is emitted
+#[derive(Clone, Debug)]
+pub struct LazyProduct<F: ScalarField> {
+    coeffs: Vec<AssignedValue<F>>,
+    bound_bits: u32,
+}
+
+impl<F: ScalarField> LazyProduct<F> {
+    /// Starts a multiplication chain from the polynomial a whose coefficients are smaller than 2^bound_bits
+    ///
+    /// * Input polynomial is parsed as a vector of assigned coefficients [a_n, a_n-1, ..., a_1, a_0] where a_0 is the constant term and n is the degree of the polynomial
+    pub fn new(a: Vec<AssignedValue<F>>, bound_bits: u32) -> Self {
+        assert!(
+            bound_bits <= F::CAPACITY,
+            "LazyProduct: the coefficients may overflow the prime field of the circuit"
+        );
+
+        LazyProduct {
+            coeffs: a,
+            bound_bits,
+        }
+    }
+
+    /// Starts a multiplication chain from a polynomial with coefficients in the range [0, Q - 1]
+    pub fn from_reduced<const Q: u64>(a: Vec<AssignedValue<F>>) -> Self {
+        Self::new(a, 64 - (Q - 1).leading_zeros())
+    }
+
+    /// Multiplies the product by the polynomial b whose coefficients are smaller than 2^b_bits, without reducing the result
+    ///
+    /// * The product is computed with `poly_mul_diff_deg`
+    /// * Panics if the bound of the new product exceeds the capacity of the field
+    pub fn mul(
+        self,
+        ctx: &mut Context<F>,
+        b: &[AssignedValue<F>],
+        b_bits: u32,
+        gate: &GateChip<F>,
+    ) -> Self {
+        // Each coefficient of the product is the sum of at most min(len(a), len(b)) products, each of them being smaller than 2^(bound_bits + b_bits)
+        let terms = self.coeffs.len().min(b.len()) as u64;
+        let bound_bits = self.bound_bits + b_bits + (64 - terms.leading_zeros());
+
+        assert!(
+            bound_bits <= F::CAPACITY,
+            "LazyProduct: the coefficients of the product may overflow the prime field of the circuit"
+        );
+
+        let coeffs = poly_mul_diff_deg(ctx, &self.coeffs, b, gate);
+
+        LazyProduct { coeffs, bound_bits }
+    }
+
+    /// Multiplies the product by the polynomial b with coefficients in the range [0, Q - 1], without reducing the result
+    pub fn mul_reduced<const Q: u64>(
+        self,
+        ctx: &mut Context<F>,
+        b: &[AssignedValue<F>],
+        gate: &GateChip<F>,
+    ) -> Self {
+        self.mul(ctx, b, 64 - (Q - 1).leading_zeros(), gate)
+    }
+
+    /// Degree of the unreduced product
+    pub fn degree(&self) -> usize {
+        self.coeffs.len() - 1
+    }
+
+    /// Upper bound on the number of bits of the coefficients of the unreduced product
+    pub fn bound_bits(&self) -> u32 {
+        self.bound_bits
+    }
+
+    /// Reduces the product in the ring R_q = Z_Q[x]/(x^(DEG+1) + 1) and returns a polynomial of degree DEG with coefficients in the range [0, Q - 1]
+    ///
+    /// * The coefficient of x^(k + j(DEG+1)) is folded onto x^k with sign (-1)^j, since x^(DEG+1) = -1 in the ring
+    /// * To keep each folded sum non negative, the negative terms are compensated by the offset Q * 2^s, a multiple of Q greater than the sum of the negative terms. Then a single `div_mod` by Q is performed per coefficient
+    /// * Output polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+    /// * Panics if the folded sums may exceed the capacity of the field
+    pub fn reduce<const DEG: usize, const Q: u64>(
+        self,
+        ctx: &mut Context<F>,
+        range: &RangeChip<F>,
+    ) -> Vec<AssignedValue<F>> {
+        let n = DEG + 1;
+        let d = self.degree();
+
+        // Number of terms folded onto each coefficient
+        let folds = (d / n + 1) as u64;
+        let folds_bits = 64 - folds.leading_zeros();
+
+        // The sum of the negative terms is smaller than 2^(bound_bits + folds_bits) = 2^s
+        // The sum of the positive terms is smaller than 2^s as well, therefore the shifted sum is in the range [0, 2^s + Q * 2^s) and has at most bits(Q) + s + 1 bits
+        let s = self.bound_bits + folds_bits;
+        let num_bits = ((64 - Q.leading_zeros()) + s + 1) as usize;
+
+        assert!(
+            num_bits <= F::CAPACITY as usize,
+            "LazyProduct: the folded coefficients may overflow the prime field of the circuit"
+        );
+
+        // offset = Q * 2^s
+        let mut offset = F::from(Q);
+        for _ in 0..s {
+            offset = offset + offset;
+        }
+
+        let mut out = vec![];
+
+        // out[i] is the coefficient of x^k where k = DEG - i
+        // coeffs[d - e] is the coefficient of x^e
+        for i in 0..=DEG {
+            let k = DEG - i;
+
+            let mut acc = QuantumCell::Constant(offset);
+            let mut e = k;
+            let mut j = 0;
+            while e <= d {
+                let term = self.coeffs[d - e];
+                acc = if j % 2 == 0 {
+                    QuantumCell::Existing(range.gate().add(ctx, acc, term))
+                } else {
+                    QuantumCell::Existing(range.gate().sub(ctx, acc, term))
+                };
+                e += n;
+                j += 1;
+            }
+
+            // acc is known to have <= `num_bits` bits, which satisfies the assumption of `div_mod`
+            let rem = range.div_mod(ctx, acc, Q, num_bits).1;
+            out.push(rem);
+        }
+
+        // assert that the reduced polynomial has degree DEG
+        assert_eq!(out.len() - 1, DEG);
+
+        out
+    }
+}
+
+/// Decompose each coefficient of the polynomial a into L digits in base T
+///
+/// * Returns L polynomials [d_0, d_1, ..., d_L-1] such that a = d_0 + d_1 * T + ... + d_L-1 * T^(L-1)
+/// * The coefficients of each digit polynomial are in the range [0, T - 1]
+/// * DEG is the degree of the polynomial a and of each digit polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of a are in the range [0, Q - 1]
+/// * Assumes that T^L >= Q so that the decomposition exists
+/// * See `verify_base_decomposition` to verify a decomposition supplied as a witness instead of computing it in the circuit
+pub fn poly_base_decompose<
+    const DEG: usize,
+    const Q: u64,
+    const T: u64,
+    const L: usize,
+    F: ScalarField,
+>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<Vec<AssignedValue<F>>> {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    // assert that L digits in base T are enough to represent any value in [0, Q - 1]
+    assert!(T > 1);
+    assert!((T as u128).pow(L as u32) >= Q as u128);
+
+    let mut digits = vec![vec![]; L];
+
+    for coeff in &a {
+        let mut acc = *coeff;
+        // The value of acc is in the range [0, (Q-1) / T^j] at the j-th iteration
+        let mut bound = Q - 1;
+
+        for digit in digits.iter_mut() {
+            let binary_representation = format!("{:b}", bound);
+            let num_bits = binary_representation.len();
+
+            // Enforce that acc = quotient * T + digit where digit is in the range [0, T - 1]
+            let (quotient, rem) = range.div_mod(ctx, acc, T, num_bits);
+            digit.push(rem);
+
+            acc = quotient;
+            bound /= T;
+        }
+
+        // Since T^L >= Q, after L divisions the quotient must be zero
+        range.gate().assert_is_const(ctx, &acc, &F::from(0));
+    }
+
+    for digit in &digits {
+        assert_eq!(digit.len() - 1, DEG);
+    }
+
+    digits
+}
+
+/// Enforces that the digit polynomials `digits` are a decomposition in base T of the polynomial `original`
+///
+/// * Compared to `poly_base_decompose`, this function does not compute the decomposition. The digits are supplied as witnesses, e.g. generated outside the circuit, and the function only emits the constraints that verify them
+/// * Enforces that the coefficients of each digit polynomial are in the range [0, T - 1] and that original = d_0 + d_1 * T + ... + d_L-1 * T^(L-1) coefficient-wise
+/// * Since the digits are in the range [0, T - 1] and T^L - 1 < p, the recomposition does not overflow and the decomposition is unique
+/// * DEG is the degree of the polynomial `original` and of each digit polynomial
+/// * `digits` is parsed as L polynomials [d_0, d_1, ..., d_L-1], each of them as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+pub fn verify_base_decomposition<const DEG: usize, const T: u64, const L: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    original: &[AssignedValue<F>],
+    digits: &[Vec<AssignedValue<F>>],
+    range: &RangeChip<F>,
+) {
+    // assert that the degree of the polynomial original is equal to DEG
+    assert_eq!(original.len() - 1, DEG);
+
+    // assert that there are L digit polynomials of degree DEG
+    assert_eq!(digits.len(), L);
+    for digit in digits {
+        assert_eq!(digit.len() - 1, DEG);
+    }
+
+    // The recomposition is in the range [0, T^L - 1]
+    assert!(T > 1);
+    let bound = (T as u128)
+        .checked_pow(L as u32)
+        .expect("verify_base_decomposition: T^L overflows u128")
+        - 1;
+    assert!(
+        fits_in_field::<F>(bound),
+        "verify_base_decomposition: the recomposition may overflow the prime field of the circuit"
+    );
+
+    // Enforce that each digit is in the range [0, T - 1]
+    for digit in digits {
+        for coeff in digit {
+            range.check_less_than_safe(ctx, *coeff, T);
+        }
+    }
+
+    for i in 0..=DEG {
+        // Recompose the coefficient with Horner's method starting from the most significant digit
+        let mut acc = digits[L - 1][i];
+        for digit in digits[..L - 1].iter().rev() {
+            acc = range
+                .gate()
+                .mul_add(ctx, acc, QuantumCell::Constant(F::from(T)), digit[i]);
+        }
+
+        ctx.constrain_equal(&acc, &original[i]);
+    }
+}
+
+/// Build the inner product of two vectors of polynomials in the ring R_q = Z_Q[x]/(x^(DEG+1) + 1)
+///
+/// * Returns sum_i a[i] * b[i] reduced in R_q
+/// * DEG is the degree of each polynomial. DEG + 1 must be a power of 2
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of every input polynomial are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p and (Q-1) * len(a) < p where p is the prime field of the circuit
+pub fn poly_dot<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<Vec<AssignedValue<F>>>,
+    b: Vec<Vec<AssignedValue<F>>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the input vectors have the same non-zero length
+    assert_eq!(a.len(), b.len());
+    assert!(!a.is_empty());
+
+    let len = a.len();
+    let mut acc: Option<Vec<AssignedValue<F>>> = None;
+
+    for (a_i, b_i) in a.into_iter().zip(b) {
+        // The coefficients of prod are in the range [0, Q - 1] according to the logic of the `poly_mul_mod` chip
+        let prod = poly_mul_mod::<DEG, Q, F>(ctx, a_i, b_i, range);
+
+        // After i additions the coefficients of acc are in the range [0, (Q-1) * (i+1)]
+        acc = Some(match acc {
+            None => prod,
+            Some(acc) => poly_add::<DEG, F>(ctx, &acc, &prod, range.gate()),
+        });
+    }
+
+    // The bound is computed on u128 so that it cannot wrap around for Q above 32 bits
+    let sum_bound = (Q as u128 - 1)
+        .checked_mul(len as u128)
+        .expect("poly_dot: the bound of the sum overflows u128");
+    assert!(
+        fits_in_field::<F>(sum_bound),
+        "poly_dot: the sum of the products may overflow the prime field of the circuit"
+    );
+
+    // get the number of bits needed to represent the value of (Q-1) * len
+    let binary_representation = format!("{:b}", sum_bound);
+    let num_bits = binary_representation.len();
+
+    // The coefficients of acc are in the range [0, (Q-1) * len], which satisfies the assumption of the `poly_reduce` chip
+    poly_reduce::<DEG, Q, F>(ctx, acc.unwrap(), range, num_bits)
+}
+
+/// Build the product of a matrix of polynomials and a vector of polynomials in the ring R_q = Z_Q[x]/(x^(DEG+1) + 1)
+///
+/// * Returns the vector [out_0, ..., out_m-1] where out_i = sum_j matrix[i][j] * vec[j] reduced in R_q
+/// * The products matrix[i][j] * vec[j] are computed with `poly_mul_equal_deg` and accumulated without being reduced. Each output row is then reduced modulo Q once and modulo x^(DEG+1) + 1 once with `poly_reduce_negacyclic`,
+///   while `poly_dot` reduces each product of the row in R_q before adding it
+/// * DEG is the degree of each polynomial. DEG + 1 must be a power of 2
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of every input polynomial are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) * len(vec) < p where p is the prime field of the circuit
+pub fn poly_matvec<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    matrix: &[Vec<Vec<AssignedValue<F>>>],
+    vec: &[Vec<AssignedValue<F>>],
+    range: &RangeChip<F>,
+) -> Vec<Vec<AssignedValue<F>>> {
+    // assert that the matrix has at least one row and that the vector is not empty
+    assert!(!matrix.is_empty());
+    assert!(!vec.is_empty());
+
+    // assert that every polynomial of the vector has degree DEG
+    for poly in vec {
+        assert_eq!(poly.len() - 1, DEG);
+    }
+
+    // assert that every row of the matrix has as many polynomials of degree DEG as the vector
+    for row in matrix {
+        assert_eq!(row.len(), vec.len());
+        for poly in row {
+            assert_eq!(poly.len() - 1, DEG);
+        }
+    }
+
+    // Each coefficient of a product is the sum of at most DEG + 1 products of values in the range [0, Q - 1], and each row adds len(vec) products
+    // The bound is computed on u128 so that it cannot wrap around for Q above 32 bits
+    let sum_bound = (Q as u128 - 1)
+        .checked_mul(Q as u128 - 1)
+        .and_then(|bound| bound.checked_mul(DEG as u128 + 1))
+        .and_then(|bound| bound.checked_mul(vec.len() as u128))
+        .expect("poly_matvec: the bound of the accumulated products overflows u128");
+    assert!(
+        fits_in_field::<F>(sum_bound),
+        "poly_matvec: the accumulated products may overflow the prime field of the circuit"
+    );
+
+    // get the number of bits needed to represent the value of (Q-1) * (Q-1) * (DEG+1) * len(vec)
+    let binary_representation = format!("{:b}", sum_bound);
+    let num_bits = binary_representation.len();
+
+    let mut out = vec![];
+
+    for row in matrix {
+        // The degree of each product is constrained to be 2*DEG according to the logic of the `poly_mul_equal_deg` chip
+        let prods = row
+            .iter()
+            .zip(vec)
+            .map(|(poly, v)| poly_mul_equal_deg::<DEG, F>(ctx, poly, v, range.gate()))
+            .collect::<Vec<_>>();
+
+        // The coefficients of the sum are in the range [0, (Q-1) * (Q-1) * (DEG+1) * len(vec)] and are known to have <= `num_bits` bits
+        let mut sum_mod = vec![];
+        for i in 0..=2 * DEG {
+            let sum = range.gate().sum(ctx, prods.iter().map(|prod| prod[i]));
+            let rem = range.div_mod(ctx, sum, Q, num_bits).1;
+            sum_mod.push(rem);
+        }
+
+        // The coefficients of sum_mod are in the range [0, Q - 1], which satisfies the assumption of the `poly_reduce_negacyclic` chip
+        let out_row = poly_reduce_negacyclic::<DEG, Q, F>(ctx, sum_mod, range);
+        out.push(out_row);
+    }
+
+    // assert that the output vector has as many polynomials as the rows of the matrix
+    assert_eq!(out.len(), matrix.len());
+
+    out
+}
+
+/// Enforces the lexicographic comparison between the polynomials a and b and returns a boolean assigned value equal to 1 if a < b
+///
+/// * The coefficient vectors are compared from the leading coefficient a_DEG down to the constant term a_0, as if they were the digits of a big number
+/// * a < b if there is an index i such that a[j] == b[j] for all j < i and a[i] < b[i]
+/// * DEG is the degree of the input polynomials
+/// * Input polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients of the input polynomials can be expressed in at most num_bits bits
+pub fn poly_less_than<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    b: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> AssignedValue<F> {
+    // assert that the input polynomials have the same degree and this is equal to DEG
+    assert_eq!(a.len() - 1, b.len() - 1);
+    assert_eq!(a.len() - 1, DEG);
+
+    // `less_than` is 1 if a[0..=i] < b[0..=i] lexicographically
+    // `prefix_equal` is 1 if a[0..=i] == b[0..=i]
+    let mut less_than = range.is_less_than(ctx, a[0], b[0], num_bits);
+    let mut prefix_equal = range.gate().is_equal(ctx, a[0], b[0]);
+
+    for i in 1..=DEG {
+        // The coefficients are known to have <= `num_bits` bits according to the assumption of the chip
+        // Therefore they satisfy the assumption of the `is_less_than` chip
+        let coeff_less_than = range.is_less_than(ctx, a[i], b[i], num_bits);
+
+        // less_than = less_than OR (prefix_equal AND coeff_less_than)
+        less_than = range
+            .gate()
+            .or_and(ctx, less_than, prefix_equal, coeff_less_than);
+
+        let coeff_equal = range.gate().is_equal(ctx, a[i], b[i]);
+        prefix_equal = range.gate().and(ctx, prefix_equal, coeff_equal);
+    }
+
+    less_than
+}
+
+/// Enforces that the coefficients of the polynomial a are sorted in non-decreasing order, namely a[i] <= a[i+1] for each pair of adjacent elements
+///
+/// * The order is the order of the vector: a[0] is the leading coefficient a_DEG and a[DEG] the constant term a_0, therefore the coefficients are non-decreasing from a_DEG down to a_0
+/// * a[i] <= a[i+1] is enforced as NOT(a[i+1] < a[i]) with the `is_less_than` chip, which costs one comparison per adjacent pair
+/// * This is a building block of set-based arguments over sorted coefficients, e.g. uniqueness (combined with strict inequalities) or membership
+/// * DEG is the degree of the input polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * It assumes that the coefficients of the input polynomial can be expressed in at most num_bits bits
+pub fn assert_coeffs_sorted<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    range: &RangeChip<F>,
+    num_bits: usize,
+) {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    for pair in a.windows(2) {
+        // The coefficients are known to have <= `num_bits` bits according to the assumption of the chip
+        // Therefore they satisfy the assumption of the `is_less_than` chip
+        let is_decreasing = range.is_less_than(ctx, pair[1], pair[0], num_bits);
+        range
+            .gate()
+            .assert_is_const(ctx, &is_decreasing, &F::zero());
+    }
+}
+
+/// Enforces whether the polynomial a is the zero polynomial and returns a boolean assigned value equal to 1 if all its coefficients are zero
+///
+/// * DEG is the degree of the input polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * The coefficients are compared to zero in the prime field of the circuit, not modulo Q
+pub fn poly_is_zero<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    gate: &GateChip<F>,
+) -> AssignedValue<F> {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    let mut is_zero = gate.is_zero(ctx, a[0]);
+
+    for coeff in &a[1..] {
+        let coeff_is_zero = gate.is_zero(ctx, *coeff);
+        is_zero = gate.and(ctx, is_zero, coeff_is_zero);
+    }
+
+    is_zero
+}
+
+/// Enforces that the polynomial a is equal to one of the public polynomials of `allowed`
+///
+/// * For each allowed polynomial allowed_i, the chip computes the boolean is_zero_i = `poly_is_zero`(a - allowed_i). The product of the (1 - is_zero_i) is the vanishing polynomial of the set evaluated at a: it is enforced to be zero, namely at least one is_zero_i is 1
+/// * The gate count grows linearly with the size of the set: each allowed polynomial costs DEG + 1 subtractions, DEG + 1 `is_zero` and DEG `and` gates, and one multiplication for the product
+/// * DEG is the degree of a and of each allowed polynomial
+/// * Polynomials are parsed as a vector of coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * The polynomials are compared in the prime field of the circuit, therefore a and the allowed polynomials must use the same representation of their coefficients, e.g. reduced in the range [0, Q - 1]
+/// * Panics if `allowed` is empty
+pub fn assert_poly_in_set<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    allowed: &[&[F]],
+    gate: &GateChip<F>,
+) {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+    assert!(
+        !allowed.is_empty(),
+        "the set of allowed polynomials is empty"
+    );
+
+    // `not_in_set` is the product of the (1 - is_zero_i) over the allowed polynomials seen so far
+    let mut not_in_set = ctx.load_constant(F::one());
+
+    for allowed_i in allowed {
+        // assert that the degree of the allowed polynomial is equal to DEG
+        assert_eq!(allowed_i.len() - 1, DEG);
+
+        let diff = a
+            .iter()
+            .zip(allowed_i.iter())
+            .map(|(a_j, allowed_j)| gate.sub(ctx, *a_j, QuantumCell::Constant(*allowed_j)))
+            .collect::<Vec<_>>();
+
+        let is_zero = poly_is_zero::<DEG, F>(ctx, &diff, gate);
+        let is_not_zero = gate.not(ctx, is_zero);
+        not_in_set = gate.mul(ctx, not_in_set, is_not_zero);
+    }
+
+    // Enforce that a is equal to at least one of the allowed polynomials
+    gate.assert_is_const(ctx, &not_in_set, &F::zero());
+}
+
+/// Apply the automorphism x -> x^K to a polynomial in the ring R_q = Z_Q[x]/(x^(DEG+1) + 1)
+///
+/// * DEG is the degree of the input polynomial. DEG + 1 must be a power of 2
+/// * K is the Galois element. It must be odd so that the map is a permutation of the coefficients up to sign
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * The coefficient of x^e is moved to x^(e*K mod 2(DEG+1)). Since x^(DEG+1) = -1 in the ring, it is negated when e*K mod 2(DEG+1) > DEG
+/// * Assumes that the coefficients of the input polynomial are in the range [0, Q - 1]. The coefficients of the output polynomial are in the same range
+pub fn poly_automorphism<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    k: usize,
+    gate: &GateChip<F>,
+) -> Vec<AssignedValue<F>> {
+    let n = DEG + 1;
+
+    // assert that the input polynomial has degree DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    // assert that the ring is a power of 2 cyclotomic ring and that K is a valid Galois element
+    assert!(n.is_power_of_two());
+    assert!(k % 2 == 1);
+
+    let mut out = a.clone();
+
+    // a[DEG - e] is the coefficient of x^e
+    for e in 0..n {
+        let coeff = a[DEG - e];
+        let target = (e * k) % (2 * n);
+
+        if target < n {
+            out[DEG - target] = coeff;
+        } else {
+            // -coeff mod Q is Q - coeff when coeff != 0 and 0 when coeff = 0
+            // The coefficient is in the range [0, Q - 1] by assumption, therefore Q - coeff is in the range [1, Q] and never underflows
+            let neg = gate.sub(ctx, QuantumCell::Constant(F::from(Q)), coeff);
+            let is_zero = gate.is_zero(ctx, coeff);
+            out[DEG - (target - n)] = gate.select(ctx, coeff, neg, is_zero);
+        }
+    }
+
+    // assert that the output polynomial has degree DEG
+    assert_eq!(out.len() - 1, DEG);
+
+    out
+}
+
+/// Apply the trace map Tr: R -> R' to a polynomial of the ring R = Z_Q[x]/(x^N + 1) with N = DEG + 1, where R' is the subring generated by y = x^(N/sub_n), isomorphic to Z_Q[y]/(y^sub_n + 1)
+///
+/// * The trace is the sum of the images of a under the automorphisms of R that fix R', namely Tr(a) = sum_j `poly_automorphism`(a, 1 + 2 * sub_n * j) for j in [0, N/sub_n)
+/// * The automorphism set: the Galois group of R over R' is {x -> x^k : k odd, k = 1 mod 2 * sub_n}, which has N/sub_n elements. For sub_n = N the set is the identity and the trace is a itself. For sub_n = 1 the trace projects onto the constant polynomials
+/// * The coefficient of x^e is multiplied by N/sub_n when N/sub_n divides e and cancels out otherwise, therefore the output lives in R'
+/// * DEG is the degree of the input polynomial. DEG + 1 must be a power of 2
+/// * sub_n must be a power of 2 dividing DEG + 1
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the input polynomial are in the range [0, Q - 1]. The coefficients of the output polynomial are in the same range
+/// * Assumes that (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn poly_trace<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    sub_n: usize,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    let n = DEG + 1;
+
+    // assert that the input polynomial has degree DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    // assert that R' is a subring of R
+    assert!(sub_n.is_power_of_two());
+    assert!(n % sub_n == 0);
+
+    // The images of a under the automorphisms x -> x^(1 + 2 * sub_n * j)
+    let images = (0..n / sub_n)
+        .map(|j| poly_automorphism::<DEG, Q, F>(ctx, a.clone(), 1 + 2 * sub_n * j, range.gate()))
+        .collect::<Vec<_>>();
+
+    // The bound is computed on u128 so that it cannot wrap around for Q above 32 bits
+    let sum_bound = (Q as u128 - 1)
+        .checked_mul((n / sub_n) as u128)
+        .expect("poly_trace: the bound of the sum overflows u128");
+    assert!(
+        fits_in_field::<F>(sum_bound),
+        "poly_trace: the sum of the images may overflow the prime field of the circuit"
+    );
+
+    // get the number of bits needed to represent the value of (Q-1) * N/sub_n
+    let binary_representation = format!("{:b}", sum_bound);
+    let num_bits = binary_representation.len();
+
+    // The coefficients of the sum are the sum of N/sub_n values in the range [0, Q - 1], therefore they are in the range [0, (Q-1) * N/sub_n]
+    let sum = (0..=DEG)
+        .map(|i| range.gate().sum(ctx, images.iter().map(|image| image[i])))
+        .collect();
+
+    poly_reduce::<DEG, Q, F>(ctx, sum, range, num_bits)
+}
+
+/// Apply the conjugation automorphism x -> x^(-1) to a polynomial in the ring R_q = Z_Q[x]/(x^(DEG+1) + 1)
+///
+/// * This is the complex conjugation in the canonical embedding. It is the automorphism x -> x^K with K = 2(DEG+1) - 1, computed directly as a fixed permutation of the coefficients with sign flips
+/// * Since x^(DEG+1) = -1 in the ring, x^(-1) = -x^DEG and x^(-e) = -x^(DEG+1-e) for e in [1, DEG]. Therefore the constant term is kept, and the coefficient of x^e is negated and moved to x^(DEG+1-e)
+/// * DEG is the degree of the input polynomial. DEG + 1 must be a power of 2
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the input polynomial are in the range [0, Q - 1]. The coefficients of the output polynomial are in the same range
+pub fn poly_conjugate<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    gate: &GateChip<F>,
+) -> Vec<AssignedValue<F>> {
+    let n = DEG + 1;
+
+    // assert that the input polynomial has degree DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    // assert that the ring is a power of 2 cyclotomic ring
+    assert!(n.is_power_of_two());
+
+    let mut out = a.clone();
+
+    // a[DEG - e] is the coefficient of x^e. The constant term a[DEG] is left in place
+    for e in 1..n {
+        let coeff = a[DEG - e];
+
+        // -coeff mod Q is Q - coeff when coeff != 0 and 0 when coeff = 0
+        // The coefficient is in the range [0, Q - 1] by assumption, therefore Q - coeff is in the range [1, Q] and never underflows
+        let neg = gate.sub(ctx, QuantumCell::Constant(F::from(Q)), coeff);
+        let is_zero = gate.is_zero(ctx, coeff);
+        out[DEG - (n - e)] = gate.select(ctx, coeff, neg, is_zero);
+    }
+
+    // assert that the output polynomial has degree DEG
+    assert_eq!(out.len() - 1, DEG);
+
+    out
+}
+
+/// Enforce that the polynomial a is invertible in the ring R_q = Z_Q[x]/(x^(DEG+1) + 1), namely that it is coprime to the cyclotomic polynomial x^(DEG+1) + 1 over Z_Q
+///
+/// * The Bezout cofactors u and v such that u * a + v * (x^(DEG+1) + 1) = 1 are computed outside the circuit. Only u is passed to the chip
+/// * Since x^(DEG+1) + 1 is zero in the ring, the identity reduces to u * a = 1 in R_q, which is enforced with the `poly_mul_mod` chip. u is then the inverse of a
+/// * If a is not invertible, no u satisfies the identity and the constraints cannot be satisfied
+/// * DEG is the degree of the polynomials. DEG + 1 must be a power of 2
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of a and u are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn prove_ring_invertible<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    u: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) {
+    // assert that the input polynomials have degree DEG
+    assert_eq!(a.len() - 1, DEG);
+    assert_eq!(u.len() - 1, DEG);
+
+    // The coefficients of a and u are in the range [0, Q - 1] by assumption, which satisfies the assumption of the `poly_mul_mod` chip
+    let prod = poly_mul_mod::<DEG, Q, F>(ctx, u, a, range);
+
+    // Enforce that prod is the constant polynomial 1
+    for coeff in &prod[..DEG] {
+        range.gate().assert_is_const(ctx, coeff, &F::zero());
+    }
+    range.gate().assert_is_const(ctx, &prod[DEG], &F::one());
+}
+
+/// Build the composition a(b(x)) of the polynomials a and b with coefficients reduced modulo Q
+///
+/// * DEG_A is the degree of a and DEG_B is the degree of b
+/// * Polynomials are parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * The composition is computed with Horner's method over the polynomial b: acc = a_DEG_A, then acc = acc * b + a_i for i = DEG_A - 1, ..., 0
+/// * The degree of acc grows by DEG_B at each step, therefore the output has degree DEG_A * DEG_B. The output is not reduced by any cyclotomic polynomial
+/// * To work in the ring Z_Q[x]/(x^(DEG+1) + 1), the output can be reduced with `poly_reduce_negacyclic` (if DEG_A * DEG_B <= 2*DEG + 1) or with `poly_divide_by_cyclo`
+/// * Assumes that the coefficients of a and b are in the range [0, Q - 1]
+/// * Assumes that (Q-1) * (Q-1) * (DEG_B+1) + (Q-1) < p where p is the prime field of the circuit
+pub fn poly_compose<const DEG_A: usize, const DEG_B: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    b: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the input polynomials have degree DEG_A and DEG_B
+    assert_eq!(a.len() - 1, DEG_A);
+    assert_eq!(b.len() - 1, DEG_B);
+
+    // The bound is computed on u128 so that it cannot wrap around for Q above 32 bits
+    let bound = (Q as u128 - 1)
+        .checked_mul(Q as u128 - 1)
+        .and_then(|bound| bound.checked_mul(DEG_B as u128 + 1))
+        .and_then(|bound| bound.checked_add(Q as u128 - 1))
+        .expect("poly_compose: the coefficient bound overflows u128");
+    assert!(
+        fits_in_field::<F>(bound),
+        "poly_compose: the coefficients of acc * b + a_i may overflow the prime field of the circuit"
+    );
+
+    // get the number of bits needed to represent the value of (Q-1) * (Q-1) * (DEG_B+1) + (Q-1)
+    let binary_representation = format!("{:b}", bound);
+    let num_bits = binary_representation.len();
+
+    let mut acc = vec![a[0]];
+
+    for a_i in &a[1..] {
+        // The degree of acc * b is deg(acc) + DEG_B
+        // Each coefficient of acc * b is the sum of at most DEG_B + 1 products of two values in the range [0, Q - 1]
+        // Therefore the coefficients of acc * b are in the range [0, (Q-1) * (Q-1) * (DEG_B+1)]
+        let mut prod = poly_mul_diff_deg(ctx, &acc, &b, range.gate());
+
+        // Add a_i to the constant term, which is now in the range [0, (Q-1) * (Q-1) * (DEG_B+1) + (Q-1)]
+        let last = prod.len() - 1;
+        prod[last] = range.gate().add(ctx, prod[last], *a_i);
+
+        // The coefficients of prod are known to have <= `num_bits` bits
+        acc = prod
+            .into_iter()
+            .map(|coeff| range.div_mod(ctx, coeff, Q, num_bits).1)
+            .collect();
+    }
+
+    // assert that the composition has degree DEG_A * DEG_B
+    assert_eq!(acc.len() - 1, DEG_A * DEG_B);
+
+    acc
+}
+
+/// Build the evaluation a(point) of the polynomial a at a constant point, in the prime field of the circuit
+///
+/// * DEG is the degree of a
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * The evaluation is computed with Horner's method: acc = a_DEG, then acc = acc * point + a_i for i = DEG - 1, ..., 0. It takes DEG `mul_add` gates
+/// * The evaluation is performed modulo the prime p of the circuit and is not reduced modulo Q
+pub fn poly_eval_at_constant<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    point: F,
+    gate: &GateChip<F>,
+) -> AssignedValue<F> {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    let mut acc = a[0];
+
+    for a_i in &a[1..] {
+        acc = gate.mul_add(ctx, acc, QuantumCell::Constant(point), *a_i);
+    }
+
+    acc
+}
+
+/// Build the rounded division of the polynomial a by the constant d, namely each coefficient c is mapped to c/d rounded to the nearest integer, with ties rounded up
+///
+/// * The rounded quotient is floor((c + floor(d/2)) / d), which is enforced with the euclidean division of c + floor(d/2) by d
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that d is non zero and that c + floor(d/2) can be expressed in at most num_bits bits for every coefficient c
+pub fn poly_div_round<const DEG: usize, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    d: u64,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> Vec<AssignedValue<F>> {
+    // assert that the input polynomial has degree DEG
+    assert_eq!(a.len() - 1, DEG);
+    assert!(d != 0);
+
+    let mut out = vec![];
+
+    for coeff in a {
+        // c + floor(d/2) is known to have <= `num_bits` bits, which satisfies the assumption of `div_mod`
+        let shifted = range
+            .gate()
+            .add(ctx, *coeff, QuantumCell::Constant(F::from(d / 2)));
+        let quot = range.div_mod(ctx, shifted, d, num_bits).0;
+        out.push(quot);
+    }
+
+    // assert that the output polynomial has degree DEG
+    assert_eq!(out.len() - 1, DEG);
+
+    out
+}
+
+/// Method used by `poly_div2` to halve the coefficients of a polynomial
+///
+/// * `Exact` outputs c/2 and requires every coefficient c to be even
+/// * `Rounded` outputs c/2 rounded to the nearest integer, with ties rounded up, namely floor((c + 1) / 2)
+/// * `ModInverse` outputs c * 2^-1 mod Q. It requires Q to be odd, such that 2 is invertible modulo Q
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Div2Mode {
+    Exact,
+    Rounded,
+    ModInverse,
+}
+
+/// Build the halving of the polynomial a by dividing each coefficient by 2 according to `mode`
+///
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the input polynomial are in the range [0, Q - 1]
+/// * With `Div2Mode::Exact` the constraints cannot be satisfied if a coefficient is odd
+/// * With `Div2Mode::ModInverse` Q must be odd. Assumes that (Q-1) * (Q+1)/2 < p where p is the prime field of the circuit
+pub fn poly_div2<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    mode: Div2Mode,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the input polynomial has degree DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    let mut out = vec![];
+
+    match mode {
+        Div2Mode::Exact => {
+            let a_to_u64 = vec_assigned_to_vec_u64(&a);
+
+            for (coeff, coeff_u64) in a.into_iter().zip(a_to_u64) {
+                // Witness h = c/2 and enforce 2 * h = c
+                // In the field, 2 * h = c has the unique solution h = c * 2^-1 mod p. It is equal to c/2 if c is even and to (c + p)/2 > Q if c is odd
+                // Therefore the range check h < Q enforces that c is even
+                let h = ctx.load_witness(F::from(coeff_u64 / 2));
+                let double_h = range.gate().add(ctx, h, h);
+                ctx.constrain_equal(&double_h, &coeff);
+                range.check_less_than_safe(ctx, h, Q);
+                out.push(h);
+            }
+        }
+        Div2Mode::Rounded => {
+            // get the number of bits needed to represent the value of Q
+            let binary_representation = format!("{:b}", Q);
+            let num_bits = binary_representation.len();
+
+            // c + 2/2 is in the range [1, Q] and is known to have <= `num_bits` bits
+            out = poly_div_round::<DEG, F>(ctx, &a, 2, range, num_bits);
+        }
+        Div2Mode::ModInverse => {
+            // 2 is invertible modulo Q if and only if Q is odd. Its inverse is (Q + 1)/2
+            assert!(Q % 2 == 1, "2 is not invertible modulo an even Q");
+            let inv_2 = (Q + 1) / 2;
+
+            // get the number of bits needed to represent the value of (Q-1) * (Q+1)/2
+            let binary_representation = format!("{:b}", (Q as u128 - 1) * inv_2 as u128);
+            let num_bits = binary_representation.len();
+
+            for coeff in a {
+                // c * (Q+1)/2 is in the range [0, (Q-1) * (Q+1)/2] and is known to have <= `num_bits` bits
+                let prod = range
+                    .gate()
+                    .mul(ctx, coeff, QuantumCell::Constant(F::from(inv_2)));
+                let h = range.div_mod(ctx, prod, Q, num_bits).1;
+                out.push(h);
+            }
+        }
+    }
+
+    // assert that the output polynomial has degree DEG
+    assert_eq!(out.len() - 1, DEG);
+
+    out
+}
+
+/// Returns the absolute value of the coefficient c in centered representation, namely min(c, Q - c)
+///
+/// * Assumes that c is in the range [0, Q - 1] and that Q can be expressed in at most num_bits bits
+fn centered_abs<const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    coeff: AssignedValue<F>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> AssignedValue<F> {
+    centered_abs_with_sign::<Q, F>(ctx, coeff, range, num_bits).0
+}
+
+/// Returns (abs, is_positive) where abs is the absolute value of the coefficient c in centered representation and is_positive is a boolean equal to 1 if c <= Q/2, namely if c represents the non negative integer c
+///
+/// * Assumes that c is in the range [0, Q - 1] and that Q can be expressed in at most num_bits bits
+fn centered_abs_with_sign<const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    coeff: AssignedValue<F>,
+    range: &RangeChip<F>,
+    num_bits: usize,
+) -> (AssignedValue<F>, AssignedValue<F>) {
+    // coeff and Q/2 + 1 are known to have <= `num_bits` bits, which satisfies the assumption of the `is_less_than` chip
+    let is_positive = range.is_less_than(
+        ctx,
+        coeff,
+        QuantumCell::Constant(F::from(Q / 2 + 1)),
+        num_bits,
+    );
+    let neg = range
+        .gate()
+        .sub(ctx, QuantumCell::Constant(F::from(Q)), coeff);
+    let abs = range.gate().select(ctx, coeff, neg, is_positive);
+
+    (abs, is_positive)
+}
+
+/// Build the infinity norm of the polynomial a, namely the maximum absolute value of its coefficients in centered representation
+///
+/// * Each coefficient c in the range [0, Q - 1] represents the integer c if c <= Q/2 and c - Q otherwise. Its absolute value is min(c, Q - c)
+/// * Returns an assigned value in the range [0, Q/2]
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the input polynomial are in the range [0, Q - 1]
+pub fn poly_inf_norm<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> AssignedValue<F> {
+    // assert that the input polynomial has degree DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    // get the number of bits needed to represent the value of Q
+    let binary_representation = format!("{:b}", Q);
+    let num_bits = binary_representation.len();
+
+    let mut norm: Option<AssignedValue<F>> = None;
+
+    for coeff in a {
+        let abs = centered_abs::<Q, F>(ctx, coeff, range, num_bits);
+
+        // abs is in the range [0, Q/2], therefore it satisfies the assumption of the `is_less_than` chip
+        norm = Some(match norm {
+            None => abs,
+            Some(norm) => {
+                let is_greater = range.is_less_than(ctx, norm, abs, num_bits);
+                range.gate().select(ctx, abs, norm, is_greater)
+            }
+        });
+    }
+
+    norm.unwrap()
+}
+
+/// Selects, for each pair of coefficients of a and b, the greatest one (if `take_max`) or the smallest one in centered representation
+fn poly_coeff_select_centered<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    range: &RangeChip<F>,
+    take_max: bool,
+) -> Vec<AssignedValue<F>> {
+    // assert that the input polynomials have the same degree and this is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+    assert_eq!(b.len() - 1, DEG);
+
+    // get the number of bits needed to represent the value of Q
+    let binary_representation = format!("{:b}", Q);
+    let num_bits = binary_representation.len();
+
+    // The centered integers are in the range [-(Q - Q/2 - 1), Q/2]. Adding Q - Q/2 - 1 maps them to [0, Q - 1] while preserving their order
+    // c <= Q/2 represents c and is mapped to c + (Q - Q/2 - 1), c > Q/2 represents c - Q and is mapped to c - (Q/2 + 1)
+    let to_ordered = |ctx: &mut Context<F>, coeff: AssignedValue<F>| {
+        // coeff and Q/2 + 1 are known to have <= `num_bits` bits, which satisfies the assumption of the `is_less_than` chip
+        let is_positive = range.is_less_than(
+            ctx,
+            coeff,
+            QuantumCell::Constant(F::from(Q / 2 + 1)),
+            num_bits,
+        );
+        let pos = range
+            .gate()
+            .add(ctx, coeff, QuantumCell::Constant(F::from(Q - Q / 2 - 1)));
+        let neg = range
+            .gate()
+            .sub(ctx, coeff, QuantumCell::Constant(F::from(Q / 2 + 1)));
+        range.gate().select(ctx, pos, neg, is_positive)
+    };
+
+    let mut c = vec![];
+
+    for i in 0..=DEG {
+        let a_ordered = to_ordered(ctx, a[i]);
+        let b_ordered = to_ordered(ctx, b[i]);
+
+        // The mapped values are in the range [0, Q - 1], therefore they satisfy the assumption of the `is_less_than` chip
+        let a_is_less = range.is_less_than(ctx, a_ordered, b_ordered, num_bits);
+
+        let val = if take_max {
+            range.gate().select(ctx, b[i], a[i], a_is_less)
+        } else {
+            range.gate().select(ctx, a[i], b[i], a_is_less)
+        };
+        c.push(val);
+    }
+
+    // assert that the output polynomial has degree DEG
+    assert_eq!(c.len() - 1, DEG);
+
+    c
+}
+
+/// Build the coefficient-wise maximum of the polynomials a and b in centered representation, namely c[i] = max(a[i], b[i])
+///
+/// * Each coefficient c in the range [0, Q - 1] represents the integer c if c <= Q/2 and c - Q otherwise, as in `poly_inf_norm`. The coefficients are compared as such integers
+/// * This is NOT a norm: the output is a polynomial of degree DEG, while `poly_inf_norm` reduces the polynomial to a scalar
+/// * The output coefficients are copied from the inputs, therefore they are in the range [0, Q - 1]
+/// * DEG is the degree of the input polynomials
+/// * Input polynomials are parsed as vectors of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of the input polynomials are in the range [0, Q - 1]
+pub fn poly_coeff_max<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    poly_coeff_select_centered::<DEG, Q, F>(ctx, a, b, range, true)
+}
+
+/// Build the coefficient-wise minimum of the polynomials a and b in centered representation, namely c[i] = min(a[i], b[i])
+///
+/// * See `poly_coeff_max` for the representation of the coefficients and the assumptions
+pub fn poly_coeff_min<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: &[AssignedValue<F>],
+    b: &[AssignedValue<F>],
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    poly_coeff_select_centered::<DEG, Q, F>(ctx, a, b, range, false)
+}
+
+/// Build the canonical embedding norm of the polynomial a, namely the maximum magnitude of its evaluations at the primitive 2(DEG+1)-th roots of unity
+///
+/// * The roots of unity are taken modulo Q: the evaluations a(psi^(2i+1)) mod Q are computed with the negacyclic NTT `ntt::to_eval`, and the chip returns their `poly_inf_norm`, namely the maximum of their absolute values in centered representation
+/// * Relationship to the infinity norm: over the complex roots of unity ζ, the canonical embedding norm max |a(ζ)| satisfies ||a||_inf <= ||a||_can <= sum_i |a_i|, and it is sub-multiplicative, which gives tighter noise bounds than the infinity norm.
+///   Both norms are equal for a constant polynomial c, whose evaluations are all equal to c. Modulo Q, the magnitudes of the evaluations are not ordered with the infinity norm: e.g. for Q = 97 and DEG = 7, x has infinity norm 1 and canonical embedding norm 27
+/// * Returns an assigned value in the range [0, Q/2]
+/// * DEG is the degree of the polynomial. DEG + 1 must be a power of 2
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Same assumptions as `to_eval`: Q is prime, 2(DEG+1) divides Q - 1, the coefficients of a are in the range [0, Q - 1] and (Q-1) * (Q-1) * (DEG+1) < p where p is the prime field of the circuit
+pub fn poly_canonical_norm<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> AssignedValue<F> {
+    // assert that the input polynomial has degree DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    // The evaluations are in the range [0, Q - 1], which satisfies the assumption of `poly_inf_norm`
+    let evals = to_eval::<DEG, Q, F>(ctx, a, range);
+
+    poly_inf_norm::<DEG, Q, F>(ctx, evals.evals, range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::params::{field_capacity, ParamsError};
+    use crate::chips::poly_distribution::{
+        try_check_poly_from_distribution_chi_error, try_check_poly_from_distribution_chi_key,
+    };
+    use crate::chips::test_utils::{load_poly, mock_run, ring_add, ring_automorphism, ring_mul};
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn poly_reduce_composite_modulus() {
+        // 15 = 3 * 5 is not prime, poly_reduce only relies on the euclidean division by Q
+        let input = [16, 15, 29, 7, 0, 44, 225, 14];
+
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &input);
+            let out = poly_reduce::<7, 15, Fr>(ctx, input, range, 8);
+            vec_assigned_to_vec_u64(&out)
+        });
+
+        assert!(satisfied);
+        assert_eq!(out, vec![1, 0, 14, 7, 0, 14, 0, 14]);
+    }
+
+    #[test]
+    fn poly_matvec_matches_ring_arithmetic() {
+        let matrix = [
+            [[96, 0, 5, 42], [1, 77, 96, 3]],
+            [[13, 96, 96, 0], [2, 0, 0, 95]],
+        ];
+        let vec = [[4, 96, 20, 1], [96, 96, 96, 96]];
+
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let matrix = matrix
+                .iter()
+                .map(|row| row.iter().map(|poly| load_poly(ctx, poly)).collect())
+                .collect::<Vec<Vec<_>>>();
+            let vec = vec
+                .iter()
+                .map(|poly| load_poly(ctx, poly))
+                .collect::<Vec<_>>();
+
+            poly_matvec::<3, 97, Fr>(ctx, &matrix, &vec, range)
+                .iter()
+                .map(|row| vec_assigned_to_vec_u64(row))
+                .collect::<Vec<_>>()
+        });
+
+        assert!(satisfied);
+        for (row, out_row) in matrix.iter().zip(out) {
+            let expected = ring_add(
+                &ring_mul(&row[0], &vec[0], 97),
+                &ring_mul(&row[1], &vec[1], 97),
+                97,
+            );
+            assert_eq!(out_row, expected);
+        }
+    }
+
+    #[test]
+    fn poly_divide_by_cyclo_returns_short_dividend() {
+        // DEG_DVD < DEG_DVS, therefore the quotient is zero and the dividend is the remainder
+        let ((remainder, same_cells), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let dividend = load_poly(ctx, &[3, 96, 1]);
+            let divisor = load_poly(ctx, &[1, 0, 0, 0, 1]);
+            let remainder =
+                poly_divide_by_cyclo::<2, 4, 97, Fr>(ctx, dividend.clone(), divisor, range);
+            let same_cells = remainder
+                .iter()
+                .zip(dividend.iter())
+                .all(|(r, d)| r.cell.unwrap().offset == d.cell.unwrap().offset);
+            (vec_assigned_to_vec_u64(&remainder), same_cells)
+        });
+        assert!(satisfied);
+        assert_eq!(remainder, vec![3, 96, 1]);
+        assert!(same_cells);
+    }
+
+    #[test]
+    fn poly_mul_diff_deg_lazy_matches_dense() {
+        let a = [7, 0, 96, 3, 41, 1];
+        let b = [5, 12, 0, 9];
+
+        let ((lazy, dense, lazy_cells, dense_cells), satisfied) =
+            mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                let b = load_poly(ctx, &b);
+                let cyclo = load_poly(ctx, &[1, 0, 0, 0, 1]);
+
+                let before = ctx.advice.len();
+                let lazy = vec![
+                    poly_mul_diff_deg(ctx, &a, CoeffSource::cyclotomic::<4>(), range.gate()),
+                    poly_mul_diff_deg(ctx, CoeffSource::cyclotomic::<4>(), &a, range.gate()),
+                ];
+                let lazy_cells = ctx.advice.len() - before;
+
+                let before = ctx.advice.len();
+                let dense = vec![
+                    poly_mul_diff_deg(ctx, &a, &cyclo, range.gate()),
+                    poly_mul_diff_deg(ctx, &cyclo, &a, range.gate()),
+                ];
+                let dense_cells = ctx.advice.len() - before;
+
+                // A closure returning existing cells behaves as the dense vector of these cells
+                let b_fn = CoeffSource::from_fn(3, |i| QuantumCell::Existing(b[i]));
+                let lazy = lazy
+                    .into_iter()
+                    .chain([poly_mul_diff_deg(ctx, &a, b_fn, range.gate())])
+                    .collect::<Vec<_>>();
+                let dense = dense
+                    .into_iter()
+                    .chain([poly_mul_diff_deg(ctx, &a, &b, range.gate())])
+                    .collect::<Vec<_>>();
+
+                let to_u64 = |polys: Vec<Vec<AssignedValue<Fr>>>| {
+                    polys
+                        .iter()
+                        .map(|poly| vec_assigned_to_vec_u64(poly))
+                        .collect::<Vec<Vec<u64>>>()
+                };
+                (to_u64(lazy), to_u64(dense), lazy_cells, dense_cells)
+            });
+
+        assert!(satisfied);
+        assert_eq!(lazy, dense);
+        // (7x^5 + 96x^3 + 3x^2 + 41x + 1) * (x^4 + 1)
+        assert_eq!(lazy[0], vec![7, 0, 96, 3, 48, 1, 96, 3, 41, 1]);
+        // The N - 1 zero coefficients of the cyclotomic polynomial are skipped
+        assert!(lazy_cells < dense_cells);
+    }
+
+    #[test]
+    fn poly_reduce_pow2_mod_256() {
+        let (rem, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[255, 256, 1000, 65535]);
+            let rem = poly_reduce_pow2::<3, 8, Fr>(ctx, input, range, 16);
+            vec_assigned_to_vec_u64(&rem)
+        });
+        assert!(satisfied);
+        assert_eq!(rem, vec![255, 0, 232, 255]);
+
+        // With num_bits = K the coefficients are already reduced and the high part must be zero
+        let (rem, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[0, 255, 17, 128]);
+            let rem = poly_reduce_pow2::<3, 8, Fr>(ctx, input, range, 8);
+            vec_assigned_to_vec_u64(&rem)
+        });
+        assert!(satisfied);
+        assert_eq!(rem, vec![0, 255, 17, 128]);
+
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[0, 256, 17, 128]);
+            poly_reduce_pow2::<3, 8, Fr>(ctx, input, range, 8);
+        });
+        assert!(!satisfied);
+    }
+
+    #[test]
+    fn try_chips_report_degree_errors() {
+        let (errors, _) = mock_run::<Fr, _>(|ctx, range| {
+            let empty = load_poly(ctx, &[]);
+            let deg_2 = load_poly(ctx, &[1, 2, 3]);
+            let deg_3 = load_poly(ctx, &[1, 2, 3, 4]);
+
+            vec![
+                try_poly_add::<3, Fr>(ctx, &empty, &deg_3, range.gate()).unwrap_err(),
+                try_poly_add::<3, Fr>(ctx, &deg_3, &deg_2, range.gate()).unwrap_err(),
+                try_poly_mul_equal_deg::<3, Fr>(ctx, &deg_3, &empty, range.gate()).unwrap_err(),
+                try_poly_mul_equal_deg::<3, Fr>(ctx, &deg_2, &deg_3, range.gate()).unwrap_err(),
+                try_poly_reduce::<3, 97, Fr>(ctx, empty, range, 8).unwrap_err(),
+                try_poly_reduce::<3, 97, Fr>(ctx, deg_2.clone(), range, 8).unwrap_err(),
+                try_poly_scalar_mul::<3, Fr>(
+                    ctx,
+                    vec![],
+                    QuantumCell::Constant(Fr::from(2)),
+                    range.gate(),
+                )
+                .unwrap_err(),
+                try_poly_scalar_mul::<3, Fr>(
+                    ctx,
+                    deg_2.clone(),
+                    QuantumCell::Constant(Fr::from(2)),
+                    range.gate(),
+                )
+                .unwrap_err(),
+                try_check_poly_from_distribution_chi_error::<3, 97, 19, Fr>(ctx, vec![], range)
+                    .unwrap_err(),
+                try_check_poly_from_distribution_chi_error::<3, 97, 19, Fr>(
+                    ctx,
+                    deg_2.clone(),
+                    range,
+                )
+                .unwrap_err(),
+                try_check_poly_from_distribution_chi_key::<3, 97, Fr>(ctx, vec![], range.gate())
+                    .unwrap_err(),
+                try_check_poly_from_distribution_chi_key::<3, 97, Fr>(ctx, deg_2, range.gate())
+                    .unwrap_err(),
+            ]
+        });
+
+        let mismatch = PolyError::DegreeMismatch {
+            expected: 3,
+            found: 2,
+        };
+        assert_eq!(
+            errors,
+            vec![
+                PolyError::EmptyPolynomial,
+                mismatch.clone(),
+                PolyError::EmptyPolynomial,
+                mismatch.clone(),
+                PolyError::EmptyPolynomial,
+                mismatch.clone(),
+                PolyError::EmptyPolynomial,
+                mismatch.clone(),
+                PolyError::EmptyPolynomial,
+                mismatch.clone(),
+                PolyError::EmptyPolynomial,
+                mismatch,
+            ]
+        );
+    }
+
+    #[test]
+    fn try_chips_accept_valid_inputs() {
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[96, 0, 5, 1]);
+            let key = load_poly(ctx, &[1, 0, 96, 1]);
+            try_poly_scalar_mul::<3, Fr>(
+                ctx,
+                a.clone(),
+                QuantumCell::Constant(Fr::from(2)),
+                range.gate(),
+            )
+            .unwrap();
+            try_check_poly_from_distribution_chi_error::<3, 97, 5, Fr>(ctx, a, range).unwrap();
+            try_check_poly_from_distribution_chi_key::<3, 97, Fr>(ctx, key, range.gate()).unwrap();
+        });
+        assert!(satisfied);
+    }
+
+    #[test]
+    fn check_division_by_cyclo_bounds_reports_field_overflow() {
+        // 4 * (Q-1) = 2^65 + 112 needs 66 bits
+        assert_eq!(
+            check_division_by_cyclo_bounds::<6, 4, { (1 << 63) + 29 }>(64),
+            Err(PolyError::FieldOverflow {
+                bound_bits: 66,
+                field_capacity: 64,
+            })
+        );
+        assert_eq!(
+            check_division_by_cyclo_bounds::<6, 4, { (1 << 63) + 29 }>(66),
+            Ok(())
+        );
+        assert_eq!(check_division_by_cyclo_bounds::<6, 4, 97>(64), Ok(()));
+    }
+
+    #[test]
+    fn params_error_converts_into_poly_error() {
+        fn build(deg: usize, q: u64) -> Result<PolyParams, PolyError> {
+            Ok(PolyParams::new::<Fr>(deg, q)?)
+        }
+
+        assert_eq!(
+            build(6, 97),
+            Err(PolyError::Params(ParamsError::InvalidDegree(6)))
+        );
+        assert_eq!(
+            build(7, 1),
+            Err(PolyError::Params(ParamsError::InvalidModulus(1)))
+        );
+        assert!(build(7, 97).is_ok());
+    }
+
+    #[test]
+    fn poly_error_display() {
+        assert_eq!(
+            PolyError::EmptyPolynomial.to_string(),
+            "the polynomial has no coefficient"
+        );
+        assert_eq!(
+            PolyError::DegreeMismatch {
+                expected: 3,
+                found: 2,
+            }
+            .to_string(),
+            "expected a polynomial of degree 3, found degree 2"
+        );
+        assert_eq!(
+            PolyError::ConstantDivisor.to_string(),
+            "the divisor must be a cyclotomic polynomial of degree at least 1, got a constant divisor"
+        );
+        assert_eq!(
+            PolyError::InvalidDividendDegree {
+                deg_dvd: 5,
+                deg_dvs: 4,
+            }
+            .to_string(),
+            "the degree 5 of the dividend must be 2 * 4 - 2 or less than 4"
+        );
+        assert_eq!(
+            PolyError::Params(ParamsError::InvalidModulus(1)).to_string(),
+            "the modulus 1 must be at least 2"
+        );
+        assert_eq!(
+            PolyError::FieldOverflow {
+                bound_bits: 66,
+                field_capacity: 64,
+            }
+            .to_string(),
+            "the coefficients may need 66 bits, which exceeds the 64 bits of capacity of the field"
+        );
+    }
+
+    #[test]
+    fn try_poly_divide_by_cyclo_reports_errors() {
+        let (errors, _) = mock_run::<Fr, _>(|ctx, range| {
+            let constant = load_poly(ctx, &[1]);
+            let cyclo = load_poly(ctx, &[1, 0, 0, 0, 1]);
+            let deg_6 = load_poly(ctx, &[1, 2, 3, 4, 5, 6, 7]);
+            let deg_5 = load_poly(ctx, &[1, 2, 3, 4, 5, 6]);
+
+            vec![
+                try_poly_divide_by_cyclo::<0, 0, 97, Fr>(ctx, constant.clone(), constant, range)
+                    .unwrap_err(),
+                try_poly_divide_by_cyclo::<6, 4, 97, Fr>(ctx, deg_5.clone(), cyclo.clone(), range)
+                    .unwrap_err(),
+                try_poly_divide_by_cyclo::<6, 4, 97, Fr>(ctx, deg_6, vec![], range).unwrap_err(),
+                try_poly_divide_by_cyclo::<5, 4, 97, Fr>(ctx, deg_5, cyclo, range).unwrap_err(),
+            ]
+        });
+
+        assert_eq!(
+            errors,
+            vec![
+                PolyError::ConstantDivisor,
+                PolyError::DegreeMismatch {
+                    expected: 6,
+                    found: 5,
+                },
+                PolyError::EmptyPolynomial,
+                PolyError::InvalidDividendDegree {
+                    deg_dvd: 5,
+                    deg_dvs: 4,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn poly_assert_reduced_rejects_unreduced_coefficient() {
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[96, 0, 5, 96]);
+            poly_assert_reduced::<3, 97, Fr>(ctx, input, range);
+        });
+        assert!(satisfied);
+
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[96, 0, 97, 3]);
+            poly_assert_reduced::<3, 97, Fr>(ctx, input, range);
+        });
+        assert!(!satisfied);
+    }
+
+    #[test]
+    fn poly_divide_by_cyclo_rejects_unreduced_short_dividend() {
+        // The dividend has a lower degree than x^4 + 1 and is returned as the remainder
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let dividend = load_poly(ctx, &[3, 96, 1]);
+            let divisor = load_poly(ctx, &[1, 0, 0, 0, 1]);
+            poly_divide_by_cyclo::<2, 4, 97, Fr>(ctx, dividend, divisor, range);
+        });
+        assert!(satisfied);
+
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let dividend = load_poly(ctx, &[3, 97, 1]);
+            let divisor = load_poly(ctx, &[1, 0, 0, 0, 1]);
+            poly_divide_by_cyclo::<2, 4, 97, Fr>(ctx, dividend, divisor, range);
+        });
+        assert!(!satisfied);
+    }
+
+    #[test]
+    fn poly_square_matches_poly_mul_equal_deg() {
+        let a = [7, 0, 96, 3, 41, 1, 0, 88];
+
+        let ((square, prod, square_cells, prod_cells), satisfied) =
+            mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+
+                let before = ctx.advice.len();
+                let square = poly_square::<7, Fr>(ctx, a.clone(), range.gate());
+                let square_cells = ctx.advice.len() - before;
+
+                let before = ctx.advice.len();
+                let prod = poly_mul_equal_deg::<7, Fr>(ctx, &a, &a, range.gate());
+                let prod_cells = ctx.advice.len() - before;
+
+                (
+                    vec_assigned_to_vec_u64(&square),
+                    vec_assigned_to_vec_u64(&prod),
+                    square_cells,
+                    prod_cells,
+                )
+            });
+
+        assert!(satisfied);
+        assert_eq!(square, prod);
+        assert!(square_cells < prod_cells);
+    }
+
+    #[test]
+    fn poly_add_scalar_to_all_adds_k_to_every_coefficient() {
+        let ((to_all, to_constant), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[4, 0, 7, 1]);
+            let k = ctx.load_witness(Fr::from(5));
+            let to_all = poly_add_scalar_to_all::<3, Fr>(
+                ctx,
+                a.clone(),
+                QuantumCell::Existing(k),
+                range.gate(),
+            );
+            let to_constant =
+                poly_scalar_add::<3, Fr>(ctx, a, QuantumCell::Constant(Fr::from(5)), range.gate());
+            (
+                vec_assigned_to_vec_u64(&to_all),
+                vec_assigned_to_vec_u64(&to_constant),
+            )
+        });
+        assert!(satisfied);
+        assert_eq!(to_all, vec![9, 5, 12, 6]);
+        assert_eq!(to_constant, vec![4, 0, 7, 6]);
+    }
+
+    #[test]
+    fn poly_less_than_orders_lexicographically() {
+        let cases: [([u64; 4], [u64; 4], u64); 5] = [
+            // equal
+            ([3, 0, 96, 5], [3, 0, 96, 5], 0),
+            // less, decided by the constant term
+            ([3, 0, 96, 4], [3, 0, 96, 5], 1),
+            // greater, decided by the constant term
+            ([3, 0, 96, 6], [3, 0, 96, 5], 0),
+            // less, decided by the leading coefficient although the lower coefficients are greater
+            ([2, 96, 96, 96], [3, 0, 0, 0], 1),
+            // greater, decided by the leading coefficient although the lower coefficients are smaller
+            ([4, 0, 0, 0], [3, 96, 96, 96], 0),
+        ];
+
+        for (a, b, expected) in cases {
+            let (less_than, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                let b = load_poly(ctx, &b);
+                *poly_less_than::<3, Fr>(ctx, a, b, range, 7).value()
+            });
+            assert!(satisfied);
+            assert_eq!(less_than, Fr::from(expected));
+        }
+    }
+
+    #[test]
+    fn poly_divide_by_cyclo_with_witness_checks_precomputed_witnesses() {
+        let dividend = vec![5, 96, 3, 0, 7, 1, 2];
+        let divisor = vec![1, 0, 0, 0, 1];
+        let (quotient, remainder) = div_by_cyclo_witness::<6, 4, 97>(&dividend, &divisor);
+        assert_eq!(quotient, vec![5, 96, 3]);
+        assert_eq!(remainder, vec![0, 0, 0, 0, 2, 2, 96]);
+
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let dividend = load_poly(ctx, &dividend);
+            let divisor = load_poly(ctx, &divisor);
+            let quotient = load_poly(ctx, &quotient);
+            let remainder = load_poly(ctx, &remainder);
+            let out = poly_divide_by_cyclo_with_witness::<6, 4, 97, Fr>(
+                ctx, dividend, divisor, quotient, remainder, range,
+            );
+            vec_assigned_to_vec_u64(&out)
+        });
+        assert!(satisfied);
+        assert_eq!(out, remainder);
+
+        // A remainder that does not match the quotient is rejected
+        let mut tampered = remainder.clone();
+        tampered[6] = 95;
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let dividend = load_poly(ctx, &dividend);
+            let divisor = load_poly(ctx, &divisor);
+            let quotient = load_poly(ctx, &quotient);
+            let remainder = load_poly(ctx, &tampered);
+            poly_divide_by_cyclo_with_witness::<6, 4, 97, Fr>(
+                ctx, dividend, divisor, quotient, remainder, range,
+            );
+        });
+        assert!(!satisfied);
+    }
+
+    #[test]
+    fn poly_mul_bounded_bound_is_tight_on_worst_case_input() {
+        // Every coefficient of a is a_bound and every coefficient of b is b_bound
+        let ((c, c_bound), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[96; 4]);
+            let b = load_poly(ctx, &[1; 4]);
+            let (c, c_bound) = poly_mul_bounded::<3, Fr>(ctx, &a, &b, 96, 1, range.gate());
+            (vec_assigned_to_vec_u64(&c), c_bound)
+        });
+        assert!(satisfied);
+        assert_eq!(c_bound, 96 * 4);
+        assert_eq!(c, vec![96, 192, 288, 384, 288, 192, 96]);
+        // The middle coefficient is the sum of DEG + 1 products and reaches the bound
+        assert_eq!(c.iter().max(), Some(&c_bound));
+    }
+
+    #[test]
+    fn poly_reduce_once_boundaries() {
+        // Q - 1 is kept, Q and 2Q - 1 are reduced by a single subtraction
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[96, 97, 193, 0]);
+            let out = poly_reduce_once::<3, 97, Fr>(ctx, input, range);
+            vec_assigned_to_vec_u64(&out)
+        });
+        assert!(satisfied);
+        assert_eq!(out, vec![96, 0, 96, 0]);
+
+        // 2^64 - 59 is the largest prime below 2^64, therefore 2Q - 1 does not fit in a u64
+        const Q: u64 = 18446744073709551557;
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = [
+                Fr::from(Q - 1),
+                Fr::from(Q),
+                Fr::from(Q) + Fr::from(Q - 1),
+                Fr::from(0),
+            ]
+            .map(|coeff| ctx.load_witness(coeff))
+            .to_vec();
+            let out = poly_reduce_once::<3, Q, Fr>(ctx, input, range);
+            vec_assigned_to_vec_u64(&out)
+        });
+        assert!(satisfied);
+        assert_eq!(out, vec![Q - 1, 0, Q - 1, 0]);
+    }
+
+    #[test]
+    fn cyclotomic_divisor_is_x4_plus_1() {
+        let (cyclo, satisfied) =
+            mock_run::<Fr, _>(|ctx, _| vec_assigned_to_vec_u64(&cyclotomic_divisor::<4, Fr>(ctx)));
+        assert!(satisfied);
+        assert_eq!(cyclo, vec![1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn poly_from_constants_divisor_in_poly_divide_by_cyclo() {
+        let dividend = [5, 96, 3, 0, 7, 1, 2];
+
+        let ((divisor, remainder), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let divisor = poly_from_constants(
+                ctx,
+                &[
+                    Fr::from(1),
+                    Fr::from(0),
+                    Fr::from(0),
+                    Fr::from(0),
+                    Fr::from(1),
+                ],
+            );
+            let dividend = load_poly(ctx, &dividend);
+            let divisor_to_u64 = vec_assigned_to_vec_u64(&divisor);
+            let remainder = poly_divide_by_cyclo::<6, 4, 97, Fr>(ctx, dividend, divisor, range);
+            (divisor_to_u64, vec_assigned_to_vec_u64(&remainder))
+        });
+        assert!(satisfied);
+        assert_eq!(divisor, vec![1, 0, 0, 0, 1]);
+        assert_eq!(remainder, vec![0, 0, 0, 0, 2, 2, 96]);
+    }
+
+    #[test]
+    fn poly_add_and_mul_borrow_their_inputs() {
+        let ((sum, prod, inputs), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[1, 2, 3]);
+            let b = load_poly(ctx, &[4, 5, 6]);
+
+            // a and b are borrowed, therefore they are still usable after each call without being cloned
+            let sum = poly_add::<2, Fr>(ctx, &a, &b, range.gate());
+            let prod = poly_mul_equal_deg::<2, Fr>(ctx, &a, &b, range.gate());
+            let sum_again = poly_add::<2, Fr>(ctx, &a, &b, range.gate());
+            poly_assert_equal::<2, Fr>(ctx, &sum, &sum_again);
+
+            (
+                vec_assigned_to_vec_u64(&sum),
+                vec_assigned_to_vec_u64(&prod),
+                [vec_assigned_to_vec_u64(&a), vec_assigned_to_vec_u64(&b)],
+            )
+        });
+
+        assert!(satisfied);
+        assert_eq!(sum, vec![5, 7, 9]);
+        assert_eq!(prod, vec![4, 13, 28, 27, 18]);
+        assert_eq!(inputs, [vec![1, 2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn poly_reduce_rns_small_moduli() {
+        let (limbs, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[100, 34, 0, 104]);
+            let limbs = poly_reduce_rns::<3, 3, Fr>(ctx, &input, [3, 5, 7], range, 7);
+            limbs.map(|limb| vec_assigned_to_vec_u64(&limb))
+        });
+        assert!(satisfied);
+        assert_eq!(
+            limbs,
+            [vec![1, 1, 0, 2], vec![0, 4, 0, 4], vec![2, 6, 0, 6]]
+        );
+    }
+
+    #[test]
+    fn poly_reduce_var_three_runtime_moduli() {
+        let (limbs, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[1000, 250, 0, 96]);
+
+            // The moduli are only known when the circuit is built, as in an RNS loop
+            let mut limbs = vec![];
+            for q in [97u64, 12, 257] {
+                let q_num_bits = format!("{:b}", q).len();
+                let q = ctx.load_constant(Fr::from(q));
+                let limb = poly_reduce_var::<3, Fr>(
+                    ctx,
+                    &input,
+                    QuantumCell::Existing(q),
+                    range,
+                    10,
+                    q_num_bits,
+                );
+                limbs.push(vec_assigned_to_vec_u64(&limb));
+            }
+            limbs
+        });
+        assert!(satisfied);
+        assert_eq!(
+            limbs,
+            vec![
+                vec![30, 56, 0, 96],
+                vec![4, 10, 0, 0],
+                vec![229, 250, 0, 96]
+            ]
+        );
+    }
+
+    #[test]
+    fn prove_ring_invertible_invertible_and_non_invertible() {
+        // In Z_97[x]/(x^2 + 1), the inverse of x + 1 is 48x + 49 since (x + 1)(48x + 49) = 48x^2 + 97x + 49 = 1 with x^2 = -1
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[1, 1]);
+            let u = load_poly(ctx, &[48, 49]);
+            prove_ring_invertible::<1, 97, Fr>(ctx, a, u, range);
+        });
+        assert!(satisfied);
+
+        // A wrong inverse is rejected
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[1, 1]);
+            let u = load_poly(ctx, &[49, 48]);
+            prove_ring_invertible::<1, 97, Fr>(ctx, a, u, range);
+        });
+        assert!(!satisfied);
+
+        // 22^2 = -1 mod 97, therefore x - 22 divides x^2 + 1 and has no inverse
+        for u in [[0, 1], [1, 22], [48, 49]] {
+            let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &[1, 75]);
+                let u = load_poly(ctx, &u);
+                prove_ring_invertible::<1, 97, Fr>(ctx, a, u, range);
+            });
+            assert!(!satisfied);
+        }
+    }
+
+    #[test]
+    fn poly_compose_x2_plus_1_with_x_plus_1() {
+        // (x + 1)^2 + 1 = x^2 + 2x + 2
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[1, 0, 1]);
+            let b = load_poly(ctx, &[1, 1]);
+            vec_assigned_to_vec_u64(&poly_compose::<2, 1, 97, Fr>(ctx, a, b, range))
+        });
+        assert!(satisfied);
+        assert_eq!(out, vec![1, 2, 2]);
+
+        // (x + 96)^2 + 1 = x^2 - 2x + 2 mod 97
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[1, 0, 1]);
+            let b = load_poly(ctx, &[1, 96]);
+            vec_assigned_to_vec_u64(&poly_compose::<2, 1, 97, Fr>(ctx, a, b, range))
+        });
+        assert!(satisfied);
+        assert_eq!(out, vec![1, 95, 2]);
+    }
+
+    #[test]
+    fn poly_reduce_into_matches_poly_reduce() {
+        let ((in_place, allocated), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[1000, 97, 0, 250]);
+            let allocated = poly_reduce::<3, 97, Fr>(ctx, input.clone(), range, 10);
+
+            let mut in_place = input;
+            poly_reduce_into::<3, 97, Fr>(ctx, &mut in_place, range, 10);
+            (
+                vec_assigned_to_vec_u64(&in_place),
+                vec_assigned_to_vec_u64(&allocated),
+            )
+        });
+        assert!(satisfied);
+        assert_eq!(in_place, allocated);
+        assert_eq!(in_place, vec![30, 0, 0, 56]);
+    }
+
+    #[test]
+    fn poly_div_round_ties_round_up() {
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[0, 4, 5, 14, 15, 25]);
+            let even = poly_div_round::<5, Fr>(ctx, &a, 10, range, 6);
+            let odd = poly_div_round::<5, Fr>(ctx, &a, 7, range, 6);
+            (
+                vec_assigned_to_vec_u64(&even),
+                vec_assigned_to_vec_u64(&odd),
+            )
+        });
+
+        assert!(satisfied);
+        assert_eq!(out.0, vec![0, 0, 1, 1, 2, 3]);
+        assert_eq!(out.1, vec![0, 1, 1, 2, 2, 4]);
+    }
+
+    #[test]
+    fn poly_canonical_norm_of_known_polynomials() {
+        // For Q = 97 and DEG = 7, the NTT evaluates at the powers psi^(2i+1) of psi = 8
+        let polys = [
+            [0, 0, 0, 0, 0, 0, 0, 5],
+            [0, 0, 0, 0, 0, 0, 0, 96],
+            [0, 0, 0, 0, 0, 0, 1, 0],
+            [3, 0, 96, 0, 0, 2, 0, 1],
+        ];
+
+        let (norms, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            polys
+                .iter()
+                .map(|a| {
+                    let a = load_poly(ctx, a);
+                    let can = poly_canonical_norm::<7, 97, Fr>(ctx, a.clone(), range);
+                    let inf = poly_inf_norm::<7, 97, Fr>(ctx, a, range);
+                    (
+                        vec_assigned_to_vec_u64(&[can])[0],
+                        vec_assigned_to_vec_u64(&[inf])[0],
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
+
+        assert!(satisfied);
+        // Constant polynomials have the same canonical embedding norm and infinity norm
+        assert_eq!(norms[0], (5, 5));
+        assert_eq!(norms[1], (1, 1));
+        // The evaluations of x are the primitive 16-th roots of unity [8, 27, 79, 12, 89, 70, 18, 85]
+        assert_eq!(norms[2], (27, 1));
+        // The evaluations are [86, 59, 59, 92, 75, 46, 75, 1]
+        assert_eq!(norms[3], (46, 3));
+    }
+
+    #[test]
+    fn poly_from_roots_field_representation() {
+        let (polys, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            [vec![1, 2], vec![0, 96, 5], vec![]]
+                .iter()
+                .map(|roots| {
+                    let roots = load_poly(ctx, roots);
+                    poly_from_roots::<97, Fr>(ctx, &roots, range)
+                        .iter()
+                        .map(|coeff| *coeff.value())
+                        .collect::<Vec<Fr>>()
+                })
+                .collect::<Vec<_>>()
+        });
+
+        assert!(satisfied);
+        // (x - 1) * (x - 2) = x^2 - 3x + 2
+        assert_eq!(polys[0], vec![Fr::from(1), -Fr::from(3), Fr::from(2)]);
+        // x * (x - 96) * (x - 5) = x^3 - 101x^2 + 480x
+        assert_eq!(
+            polys[1],
+            vec![Fr::from(1), -Fr::from(101), Fr::from(480), Fr::from(0)]
+        );
+        assert_eq!(polys[2], vec![Fr::from(1)]);
+    }
+
+    #[test]
+    fn reduction_strategies_output_the_same_coefficients() {
+        for (input, num_bits, expected) in [
+            (
+                vec![0, 96, 97, 194, 65535, 12345],
+                16,
+                vec![0, 96, 0, 0, 60, 26],
+            ),
+            // num_bits equal to the number of bits of Q
+            (vec![0, 96, 97, 127, 1, 50], 7, vec![0, 96, 0, 30, 1, 50]),
+        ] {
+            let ((div_mod, cond_sub, reduced), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+                let input = load_poly(ctx, &input);
+                let div_mod = poly_reduce_with::<5, 97, Fr>(
+                    ctx,
+                    input.clone(),
+                    range,
+                    num_bits,
+                    ReductionStrategy::DivMod,
+                );
+                let cond_sub = poly_reduce_with::<5, 97, Fr>(
+                    ctx,
+                    input.clone(),
+                    range,
+                    num_bits,
+                    ReductionStrategy::ConditionalSubtraction,
+                );
+                let reduced = poly_reduce::<5, 97, Fr>(ctx, input, range, num_bits);
+
+                (
+                    vec_assigned_to_vec_u64(&div_mod),
+                    vec_assigned_to_vec_u64(&cond_sub),
+                    vec_assigned_to_vec_u64(&reduced),
+                )
+            });
+
+            assert!(satisfied);
+            assert_eq!(div_mod, expected);
+            assert_eq!(cond_sub, expected);
+            assert_eq!(reduced, expected);
+        }
+
+        let a = [96, 0, 5, 41];
+        let b = [3, 96, 96, 1];
+        let ((div_mod, cond_sub), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            let b = load_poly(ctx, &b);
+            let div_mod = poly_mul_mod_with::<3, 97, Fr>(
+                ctx,
+                a.clone(),
+                b.clone(),
+                range,
+                ReductionStrategy::DivMod,
+            );
+            let cond_sub = poly_mul_mod_with::<3, 97, Fr>(
+                ctx,
+                a,
+                b,
+                range,
+                ReductionStrategy::ConditionalSubtraction,
+            );
+
+            (
+                vec_assigned_to_vec_u64(&div_mod),
+                vec_assigned_to_vec_u64(&cond_sub),
+            )
+        });
+
+        assert!(satisfied);
+        assert_eq!(div_mod, cond_sub);
+        assert_eq!(div_mod, ring_mul(&a, &b, 97));
+    }
+
+    #[test]
+    #[should_panic(expected = "reduce_coeff: num_bits must be at least the number of bits of Q")]
+    fn conditional_subtraction_rejects_short_num_bits() {
+        mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[1, 2, 3, 4]);
+            poly_reduce_with::<3, 97, Fr>(
+                ctx,
+                input,
+                range,
+                6,
+                ReductionStrategy::ConditionalSubtraction,
+            );
+        });
+    }
+
+    /// Reference remainder of the long division of a by the monic polynomial divisor modulo q, padded to deg(divisor) coefficients
+    fn long_division_remainder(a: &[u64], divisor: &[u64], q: u64) -> Vec<u64> {
+        let mut rem = a.to_vec();
+        while rem.len() >= divisor.len() {
+            let lead = rem[0];
+            for (coeff, d) in rem.iter_mut().zip(divisor) {
+                *coeff = (*coeff + q - lead * d % q) % q;
+            }
+            rem.remove(0);
+        }
+        while rem.len() < divisor.len() - 1 {
+            rem.insert(0, 0);
+        }
+        rem
+    }
+
+    #[test]
+    fn poly_reduce_cyclic_matches_long_division() {
+        // x^4 - 1 modulo 97
+        let divisor = [1, 0, 0, 0, 96];
+
+        let a = [96, 5, 0, 42, 3, 96, 7, 1];
+        let expected = long_division_remainder(&a, &divisor, 97);
+        assert_eq!(expected, vec![2, 4, 7, 43]);
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            vec_assigned_to_vec_u64(&poly_reduce_cyclic::<3, 97, Fr>(ctx, a, range))
+        });
+        assert!(satisfied);
+        assert_eq!(out, expected);
+
+        // A dividend of degree 5 only folds its two highest coefficients
+        let a = [11, 96, 2, 3, 4, 5];
+        let expected = long_division_remainder(&a, &divisor, 97);
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            vec_assigned_to_vec_u64(&poly_reduce_cyclic::<3, 97, Fr>(ctx, a, range))
+        });
+        assert!(satisfied);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn chips_with_params_match_ring_arithmetic() {
+        let params = PolyParams::new::<Fr>(3, 97).unwrap();
+        let a = [96, 0, 5, 42];
+        let b = [1, 77, 96, 3];
+
+        let ((with_params, composed, padding), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            let b = load_poly(ctx, &b);
+
+            let with_params =
+                poly_mul_mod_with_params::<3, 97, Fr>(ctx, a.clone(), b.clone(), range, &params);
+
+            // The same product with the chips composed by hand
+            let prod = poly_mul_equal_deg::<3, Fr>(ctx, &a, &b, range.gate());
+            let prod = poly_reduce_with_params::<6, 97, Fr>(ctx, prod, range, &params);
+            let cyclo = load_poly(ctx, &[1, 0, 0, 0, 1]);
+            // The remainder is padded with leading zeroes to degree 6
+            let remainder =
+                poly_divide_by_cyclo_with_params::<6, 4, 97, Fr>(ctx, prod, cyclo, range, &params);
+
+            (
+                vec_assigned_to_vec_u64(&with_params),
+                vec_assigned_to_vec_u64(&remainder[3..]),
+                vec_assigned_to_vec_u64(&remainder[..3]),
+            )
+        });
+
+        assert!(satisfied);
+        assert_eq!(with_params, ring_mul(&a, &b, 97));
+        assert_eq!(composed, with_params);
+        assert_eq!(padding, vec![0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "poly_mul_mod_with_params: the parameter set (deg = 7, q = 97) does not match the chip"
+    )]
+    fn chips_with_params_reject_other_ring() {
+        let params = PolyParams::new::<Fr>(7, 97).unwrap();
+
+        mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[1, 2, 3, 4]);
+            let b = load_poly(ctx, &[5, 6, 7, 8]);
+            poly_mul_mod_with_params::<3, 97, Fr>(ctx, a, b, range, &params);
+        });
+    }
+
+    #[test]
+    fn division_by_cyclo_bounds_fit_bn254() {
+        let (prod_bound, sum_bound) = division_by_cyclo_bounds::<6, 4, 97>(field_capacity::<Fr>());
+
+        assert_eq!(prod_bound, 96 * 3);
+        assert_eq!(sum_bound, 96 * 4);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "poly_divide_by_cyclo: the coefficients of quotient * divisor may overflow"
+    )]
+    fn division_by_cyclo_bound_exceeding_field_panics() {
+        // (Q-1) * 3 needs 65 bits, which exceeds a field of 64 bits of capacity
+        division_by_cyclo_bounds::<6, 4, { (1 << 63) + 29 }>(64);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "poly_divide_by_cyclo: the coefficients of quotient * divisor + remainder may overflow"
+    )]
+    fn division_by_cyclo_sum_bound_exceeding_field_panics() {
+        // (Q-1) * 3 = 3 * 2^62 needs 64 bits, while (Q-1) * 4 = 2^64 needs 65 bits
+        division_by_cyclo_bounds::<6, 4, { (1 << 62) + 1 }>(64);
+    }
+
+    #[test]
+    fn poly_div2_exact_and_mod_inverse() {
+        let halve = |a: [u64; 4], mode: Div2Mode| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                vec_assigned_to_vec_u64(&poly_div2::<3, 97, Fr>(ctx, a, mode, range))
+            })
+        };
+
+        let (out, satisfied) = halve([0, 2, 96, 40], Div2Mode::Exact);
+        assert!(satisfied);
+        assert_eq!(out, vec![0, 1, 48, 20]);
+
+        // An odd coefficient has no exact half in the range [0, Q - 1]
+        let (_, satisfied) = halve([0, 2, 95, 40], Div2Mode::Exact);
+        assert!(!satisfied);
+
+        // Odd coefficients are multiplied by 2^-1 = 49 mod 97, e.g. 2 * 69 = 41 mod 97
+        let (out, satisfied) = halve([1, 2, 96, 41], Div2Mode::ModInverse);
+        assert!(satisfied);
+        assert_eq!(out, vec![49, 1, 48, 69]);
+
+        let (out, satisfied) = halve([1, 2, 96, 41], Div2Mode::Rounded);
+        assert!(satisfied);
+        assert_eq!(out, vec![1, 1, 48, 21]);
+    }
+
+    #[test]
+    fn poly_assign_from_iter_range() {
+        let ((from_iter, from_slice), satisfied) = mock_run::<Fr, _>(|ctx, _| {
+            let from_iter = poly_assign_from_iter::<4, Fr>(ctx, (10..15).rev());
+            let from_slice = load_poly(ctx, &[14, 13, 12, 11, 10]);
+            (
+                vec_assigned_to_vec_u64(&from_iter),
+                vec_assigned_to_vec_u64(&from_slice),
+            )
+        });
+        assert!(satisfied);
+        assert_eq!(from_iter, from_slice);
+    }
+
+    #[test]
+    #[should_panic(expected = "the iterator yields less than DEG + 1 coefficients")]
+    fn poly_assign_from_iter_rejects_short_range() {
+        mock_run::<Fr, _>(|ctx, _| {
+            poly_assign_from_iter::<4, Fr>(ctx, 0..4);
+        });
+    }
+
+    #[test]
+    fn poly_add3_matches_chained_poly_add() {
+        let ((sum3, chained), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[1, 96, 0, 50]);
+            let b = load_poly(ctx, &[2, 96, 7, 0]);
+            let c = load_poly(ctx, &[3, 96, 0, 49]);
+            let sum3 = poly_add3::<3, Fr>(ctx, &a, &b, &c, range.gate());
+            let ab = poly_add::<3, Fr>(ctx, &a, &b, range.gate());
+            let chained = poly_add::<3, Fr>(ctx, &ab, &c, range.gate());
+            (
+                vec_assigned_to_vec_u64(&sum3),
+                vec_assigned_to_vec_u64(&chained),
+            )
+        });
+        assert!(satisfied);
+        assert_eq!(sum3, chained);
+        assert_eq!(sum3, vec![6, 288, 7, 99]);
+    }
+
+    #[test]
+    fn poly_diff_of_squares_matches_product_of_sum_and_difference() {
+        let a = [5, 96, 0, 42];
+        let b = [90, 3, 17, 42];
+
+        // Reference (a + b) * (a - b) with coefficients reduced modulo 97, of degree 6
+        let sum = a.iter().zip(&b).map(|(x, y)| (x + y) % 97);
+        let diff = a.iter().zip(&b).map(|(x, y)| (x + 97 - y) % 97);
+        let (sum, diff) = (sum.collect::<Vec<u64>>(), diff.collect::<Vec<u64>>());
+        let mut expected = vec![0; 7];
+        for i in 0..4 {
+            for j in 0..4 {
+                expected[i + j] = (expected[i + j] + sum[i] * diff[j]) % 97;
+            }
+        }
+
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            let b = load_poly(ctx, &b);
+            vec_assigned_to_vec_u64(&poly_diff_of_squares::<3, 97, Fr>(ctx, a, b, range))
+        });
+        assert!(satisfied);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn poly_divide_by_cyclo_public_instance_layout() {
+        let ((remainder, public), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let dividend = load_poly(ctx, &[5, 96, 3, 0, 7, 1, 2]);
+            let divisor = load_poly(ctx, &[1, 0, 0, 0, 1]);
+            let mut make_public = vec![];
+            let remainder = poly_divide_by_cyclo_public::<6, 4, 97, Fr>(
+                ctx,
+                dividend,
+                divisor,
+                range,
+                &mut make_public,
+            );
+            (
+                vec_assigned_to_vec_u64(&remainder),
+                vec_assigned_to_vec_u64(&make_public),
+            )
+        });
+        assert!(satisfied);
+        assert_eq!(remainder, vec![0, 0, 0, 0, 2, 2, 96]);
+
+        // The quotient, then the remainder, then the product quotient * divisor
+        assert_eq!(public.len(), 3 + 7 + 7);
+        assert_eq!(public[..3], [5, 96, 3]);
+        assert_eq!(public[3..10], remainder[..]);
+        assert_eq!(public[10..], [5, 96, 3, 0, 5, 96, 3]);
+    }
+
+    #[test]
+    fn poly_mul_sparse_matches_dense_product() {
+        let dense = [5, 96, 0, 42];
+
+        let ((sparse, reference, sparse_cells, dense_cells), satisfied) =
+            mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &dense);
+
+                // 3x^4 + 96 with an explicit zero term, which is skipped
+                let before = ctx.advice.len();
+                let sparse = poly_mul_sparse::<97, Fr>(
+                    ctx,
+                    a.clone(),
+                    &[(4, Fr::from(3)), (2, Fr::from(0)), (0, Fr::from(96))],
+                    range,
+                );
+                let sparse_cells = ctx.advice.len() - before;
+
+                let b = load_poly(ctx, &[3, 0, 0, 0, 96]);
+                let before = ctx.advice.len();
+                let prod = poly_mul_diff_deg(ctx, &a, &b, range.gate());
+                let reference = poly_reduce::<7, 97, Fr>(ctx, prod, range, 15);
+                let dense_cells = ctx.advice.len() - before;
+
+                (
+                    vec_assigned_to_vec_u64(&sparse),
+                    vec_assigned_to_vec_u64(&reference),
+                    sparse_cells,
+                    dense_cells,
+                )
+            });
+        assert!(satisfied);
+        assert_eq!(sparse, reference);
+        assert!(sparse_cells < dense_cells);
+    }
+
+    #[test]
+    fn poly_assert_congruent_two_representatives() {
+        // 5x^5 + x^3 + 2x^2 + 8x + 4 = x^3 + 2x^2 + 3x + 4 in Z_97[x]/(x^4 + 1) since x^5 = -x
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[1, 2, 3, 4]);
+            let b = load_poly(ctx, &[0, 0, 5, 0, 1, 2, 8, 4]);
+            poly_assert_congruent::<3, 97, Fr>(ctx, a, b, range);
+        });
+        assert!(satisfied);
+
+        // Without the compensation of the folded term, the elements differ
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[1, 2, 3, 4]);
+            let b = load_poly(ctx, &[0, 0, 5, 0, 1, 2, 3, 4]);
+            poly_assert_congruent::<3, 97, Fr>(ctx, a, b, range);
+        });
+        assert!(!satisfied);
+    }
+
+    #[test]
+    fn poly_mul_accumulate_matches_mul_then_add() {
+        let ((fused, separate), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let acc = load_poly(ctx, &[9, 0, 96, 1, 2, 0, 50]);
+            let a = load_poly(ctx, &[1, 2, 3, 96]);
+            let b = load_poly(ctx, &[4, 0, 96, 6]);
+
+            let fused = poly_mul_accumulate::<3, Fr>(ctx, &acc, &a, &b, range.gate());
+            let prod = poly_mul_equal_deg::<3, Fr>(ctx, &a, &b, range.gate());
+            let separate = poly_add::<6, Fr>(ctx, &acc, &prod, range.gate());
+            (
+                vec_assigned_to_vec_u64(&fused),
+                vec_assigned_to_vec_u64(&separate),
+            )
+        });
+        assert!(satisfied);
+        assert_eq!(fused, separate);
+    }
+
+    #[test]
+    fn poly_reduce_range_reduces_only_the_sub_range() {
+        let ((out, untouched), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[200, 150, 300, 5, 96]);
+            let out = poly_reduce_range::<97, Fr>(ctx, input.clone(), 1, 3, range, 9);
+            // The coefficients outside of [1, 3) are the input cells themselves
+            let untouched = [0, 3, 4]
+                .iter()
+                .all(|&i| out[i].cell.unwrap().offset == input[i].cell.unwrap().offset);
+            (vec_assigned_to_vec_u64(&out), untouched)
+        });
+        assert!(satisfied);
+        assert_eq!(out, vec![200, 53, 9, 5, 96]);
+        assert!(untouched);
+    }
+
+    #[test]
+    fn poly_hadamard_is_coefficient_wise() {
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[1, 2, 3, 96]);
+            let b = load_poly(ctx, &[4, 0, 96, 96]);
+            vec_assigned_to_vec_u64(&poly_hadamard::<3, Fr>(ctx, &a, &b, range.gate()))
+        });
+        assert!(satisfied);
+        // Not reduced and not a convolution
+        assert_eq!(out, vec![4, 0, 288, 9216]);
+    }
+
+    #[test]
+    fn poly_reduce_audited_quotients_are_floor_division() {
+        let input = [0, 96, 97, 388];
+
+        let ((rem, quot), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &input);
+            let (rem, quot) = poly_reduce_audited::<3, 97, Fr>(ctx, input, range, 9, Some(4));
+            (
+                vec_assigned_to_vec_u64(&rem),
+                vec_assigned_to_vec_u64(&quot),
+            )
+        });
+        assert!(satisfied);
+        assert_eq!(quot, input.iter().map(|c| c / 97).collect::<Vec<u64>>());
+        assert_eq!(rem, input.iter().map(|c| c % 97).collect::<Vec<u64>>());
+
+        // 388 = 4 * 97, therefore a maximum quotient of 3 is exceeded
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &input);
+            poly_reduce_audited::<3, 97, Fr>(ctx, input, range, 9, Some(3));
+        });
+        assert!(!satisfied);
+    }
+
+    #[test]
+    fn poly_conjugate_matches_reference_for_n4() {
+        for a in [[1, 2, 3, 4], [0, 2, 96, 4]] {
+            // x -> x^(-1) = x^7 in Z_97[x]/(x^4 + 1)
+            let expected = ring_automorphism(&a, 7, 97);
+
+            let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                vec_assigned_to_vec_u64(&poly_conjugate::<3, 97, Fr>(ctx, a, range.gate()))
+            });
+            assert!(satisfied);
+            assert_eq!(out, expected);
+        }
+
+        // 4 + 3x^-1 + 2x^-2 + x^-3 = 4 - x - 2x^2 - 3x^3
+        assert_eq!(ring_automorphism(&[1, 2, 3, 4], 7, 97), vec![94, 95, 96, 4]);
+    }
+
+    #[test]
+    fn poly_truncate_pass_and_fail() {
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[0, 0, 5, 0, 7]);
+            vec_assigned_to_vec_u64(&poly_truncate::<4, 2, Fr>(ctx, a, range.gate()))
+        });
+        assert!(satisfied);
+        assert_eq!(out, vec![5, 0, 7]);
+
+        // The coefficient of x^3 is dropped although it is not zero
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[0, 1, 5, 0, 7]);
+            poly_truncate::<4, 2, Fr>(ctx, a, range.gate());
+        });
+        assert!(!satisfied);
+    }
+
+    #[test]
+    fn poly_reduce_signed_negative_coefficients() {
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            // [-5, 3, -200, 250], shifted by 3 * 97 = 291 to the range [0, 541]
+            let input = [-Fr::from(5), Fr::from(3), -Fr::from(200), Fr::from(250)]
+                .map(|coeff| ctx.load_witness(coeff))
+                .to_vec();
+            let out = poly_reduce_signed::<3, 97, Fr>(ctx, input, 200, range, 10);
+            vec_assigned_to_vec_u64(&out)
+        });
+        assert!(satisfied);
+        assert_eq!(out, vec![92, 3, 91, 56]);
+    }
+
+    #[test]
+    fn lazy_product_chain_of_two_multiplications() {
+        let a = [96, 0, 5, 42];
+        let b = [1, 77, 96, 3];
+        let c = [13, 96, 0, 50];
+
+        let ((out, degree, bound_bits), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            let b = load_poly(ctx, &b);
+            let c = load_poly(ctx, &c);
+            let product = LazyProduct::from_reduced::<97>(a)
+                .mul_reduced::<97>(ctx, &b, range.gate())
+                .mul_reduced::<97>(ctx, &c, range.gate());
+            let (degree, bound_bits) = (product.degree(), product.bound_bits());
+            let out = product.reduce::<3, 97>(ctx, range);
+            (vec_assigned_to_vec_u64(&out), degree, bound_bits)
+        });
+        assert!(satisfied);
+        assert_eq!(out, ring_mul(&ring_mul(&a, &b, 97), &c, 97));
+        // The unreduced product has degree 3 * DEG and its coefficients have at most 3 * 7 + 2 * 3 bits
+        assert_eq!(degree, 9);
+        assert_eq!(bound_bits, 27);
+    }
+
+    #[test]
+    fn assert_minimal_representative_pass_and_fail() {
+        // 1000 = 10 * 97 + 30
+        let check = |claimed: u64| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let original = ctx.load_witness(Fr::from(1000));
+                let claimed = ctx.load_witness(Fr::from(claimed));
+                assert_minimal_representative::<97, Fr>(ctx, original, claimed, range, 10);
+            })
+            .1
+        };
+
+        assert!(check(30));
+        // Congruent to 1000 modulo 97 but not reduced
+        assert!(!check(127));
+        // Reduced but not congruent to 1000 modulo 97
+        assert!(!check(31));
+    }
+
+    #[test]
+    fn poly_divide_by_cyclo_trimmed_drops_the_padding() {
+        let dividend = [5, 96, 3, 0, 7, 1, 2];
+        let divisor = [1, 0, 0, 0, 1];
+
+        let ((padded, trimmed), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let dvd = load_poly(ctx, &dividend);
+            let dvs = load_poly(ctx, &divisor);
+            let padded = poly_divide_by_cyclo::<6, 4, 97, Fr>(ctx, dvd, dvs, range);
+            let dvd = load_poly(ctx, &dividend);
+            let dvs = load_poly(ctx, &divisor);
+            let trimmed = poly_divide_by_cyclo_trimmed::<6, 4, 3, 97, Fr>(ctx, dvd, dvs, range);
+            (
+                vec_assigned_to_vec_u64(&padded),
+                vec_assigned_to_vec_u64(&trimmed),
+            )
+        });
+        assert!(satisfied);
+        assert_eq!(padded, vec![0, 0, 0, 0, 2, 2, 96]);
+        // The trimmed remainder is the low DEG + 1 coefficients of the padded remainder
+        assert_eq!(trimmed, vec![0, 2, 2, 96]);
+        assert_eq!(trimmed[..], padded[3..]);
+    }
+
+    #[test]
+    fn assert_poly_in_set_three_element_set() {
+        let p0 = [Fr::from(0), Fr::from(0), Fr::from(1)];
+        let p1 = [Fr::from(1), Fr::from(0), Fr::from(96)];
+        let p2 = [Fr::from(5), Fr::from(7), Fr::from(9)];
+        let allowed: [&[Fr]; 3] = [&p0, &p1, &p2];
+
+        let check = |a: [u64; 3]| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                assert_poly_in_set::<2, Fr>(ctx, &a, &allowed, range.gate());
+            })
+            .1
+        };
+
+        // Every element of the set is accepted
+        assert!(check([0, 0, 1]));
+        assert!(check([1, 0, 96]));
+        assert!(check([5, 7, 9]));
+        // A polynomial outside of the set is rejected, even if it differs from an element by a single coefficient
+        assert!(!check([5, 7, 8]));
+        assert!(!check([0, 0, 0]));
+    }
+
+    #[test]
+    fn poly_square_mod_matches_poly_mul_mod() {
+        for s in [
+            vec![96, 0, 1, 96],
+            vec![96, 96, 96, 96],
+            vec![3, 77, 0, 50, 96, 1, 12, 45],
+        ] {
+            let ((square, product), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &s);
+                let b = load_poly(ctx, &s);
+                let c = load_poly(ctx, &s);
+                let (square, product) = if s.len() == 4 {
+                    (
+                        poly_square_mod::<3, 97, Fr>(ctx, a, range),
+                        poly_mul_mod::<3, 97, Fr>(ctx, b, c, range),
+                    )
+                } else {
+                    (
+                        poly_square_mod::<7, 97, Fr>(ctx, a, range),
+                        poly_mul_mod::<7, 97, Fr>(ctx, b, c, range),
+                    )
+                };
+                (
+                    vec_assigned_to_vec_u64(&square),
+                    vec_assigned_to_vec_u64(&product),
+                )
+            });
+            assert!(satisfied);
+            assert_eq!(square, product);
+            assert_eq!(square, ring_mul(&s, &s, 97));
+        }
+    }
+
+    #[test]
+    fn poly_add_mod_reduced_matches_ring_addition() {
+        // Sums below Q, equal to Q and at the maximum 2Q - 2
+        let a = [10, 50, 96, 96];
+        let b = [20, 47, 1, 96];
+
+        let (sum, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            let b = load_poly(ctx, &b);
+            let sum = poly_add_mod_reduced::<3, 97, Fr>(ctx, &a, &b, range);
+            vec_assigned_to_vec_u64(&sum)
+        });
+        assert!(satisfied);
+        assert_eq!(sum, vec![30, 0, 0, 95]);
+        assert_eq!(sum, ring_add(&a, &b, 97));
+    }
+
+    #[test]
+    fn poly_trace_matches_sum_of_automorphisms() {
+        let a = [7, 0, 96, 3, 41, 1, 0, 88];
+
+        for sub_n in [1, 2, 4, 8] {
+            let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                let out = poly_trace::<7, 97, Fr>(ctx, a, sub_n, range);
+                vec_assigned_to_vec_u64(&out)
+            });
+
+            let expected = (0..8 / sub_n)
+                .map(|j| ring_automorphism(&a, 1 + 2 * sub_n * j, 97))
+                .fold(vec![0; 8], |acc, image| ring_add(&acc, &image, 97));
+
+            assert!(satisfied);
+            assert_eq!(out, expected);
+        }
+
+        // With sub_n = 2, the coefficients of x^0 and x^4 are multiplied by 8/2 and the others cancel out
+        let expected = vec![0, 0, 0, 3 * 4, 0, 0, 0, 88 * 4 % 97];
+        let (out, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            vec_assigned_to_vec_u64(&poly_trace::<7, 97, Fr>(ctx, a, 2, range))
+        });
+        assert!(satisfied);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn poly_reduce_to_balanced_q_over_2_boundary() {
+        // Odd modulus: Q/2 = 48 is positive, Q/2 + 1 = 49 is the digit -48
+        let ((magnitudes, signs), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[48, 49, 0, 96, 145]);
+            let (magnitudes, signs) = poly_reduce_to_balanced::<4, 97, Fr>(ctx, input, range, 8);
+            (
+                vec_assigned_to_vec_u64(&magnitudes),
+                vec_assigned_to_vec_u64(&signs),
+            )
+        });
+        assert!(satisfied);
+        assert_eq!(magnitudes, vec![48, 48, 0, 1, 48]);
+        assert_eq!(signs, vec![0, 1, 0, 1, 0]);
+
+        // Even modulus: Q/2 = 6 is positive, Q/2 + 1 = 7 is the digit -5, the digit -6 never appears
+        let ((magnitudes, signs), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &[6, 7, 0, 11]);
+            let (magnitudes, signs) = poly_reduce_to_balanced::<3, 12, Fr>(ctx, input, range, 4);
+            (
+                vec_assigned_to_vec_u64(&magnitudes),
+                vec_assigned_to_vec_u64(&signs),
+            )
+        });
+        assert!(satisfied);
+        assert_eq!(magnitudes, vec![6, 5, 0, 1]);
+        assert_eq!(signs, vec![0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn assert_leading_quotient_coeff_rejects_tampered_leading_coefficient() {
+        let dividend = [5, 96, 3, 0, 7, 1, 2];
+        let divisor = [1, 0, 0, 0, 1];
+
+        let check = |quotient: [u64; 3]| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let dividend = load_poly(ctx, &dividend);
+                let divisor = load_poly(ctx, &divisor);
+                let quotient = load_poly(ctx, &quotient);
+                assert_leading_quotient_coeff::<6, 4, Fr>(
+                    ctx,
+                    &dividend,
+                    &divisor,
+                    &quotient,
+                    range.gate(),
+                );
+            })
+            .1
+        };
+
+        // The quotient of the division is [5, 96, 3]
+        assert!(check([5, 96, 3]));
+        // A tampered leading coefficient is rejected
+        assert!(!check([6, 96, 3]));
+        assert!(!check([5 + 97, 96, 3]));
+    }
+
+    #[test]
+    fn poly_coeff_max_min_mixed_orderings() {
+        // In centered representation: 3 < 10, -1 < 2, -48 < 48, -7 < -2 and 0 = 0
+        let a = [3, 96, 48, 90, 0];
+        let b = [10, 2, 49, 95, 0];
+
+        let ((max, min), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            let b = load_poly(ctx, &b);
+            let max = poly_coeff_max::<4, 97, Fr>(ctx, &a, &b, range);
+            let min = poly_coeff_min::<4, 97, Fr>(ctx, &a, &b, range);
+            (vec_assigned_to_vec_u64(&max), vec_assigned_to_vec_u64(&min))
+        });
+        assert!(satisfied);
+        assert_eq!(max, vec![10, 2, 48, 95, 0]);
+        assert_eq!(min, vec![3, 96, 49, 90, 0]);
+    }
+
+    #[test]
+    fn verify_base_decomposition_passing_and_tampered_digits() {
+        let original = [0, 5, 63, 17];
+        // Digits in base 4, least significant first
+        let digits = [[0, 1, 3, 1], [0, 1, 3, 0], [0, 0, 3, 1]];
+
+        let check = |digits: [[u64; 4]; 3]| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let original = load_poly(ctx, &original);
+                let digits = digits
+                    .iter()
+                    .map(|digit| load_poly(ctx, digit))
+                    .collect::<Vec<_>>();
+                verify_base_decomposition::<3, 4, 3, Fr>(ctx, &original, &digits, range);
+            })
+            .1
+        };
+
+        assert!(check(digits));
+
+        // A digit that does not recompose the original coefficient is rejected
+        let mut tampered = digits;
+        tampered[2][3] = 2;
+        assert!(!check(tampered));
+
+        // 5 = 5 + 0 * 4 recomposes, but the digit 5 is not in the range [0, 3]
+        let mut tampered = digits;
+        tampered[0][1] = 5;
+        tampered[1][1] = 0;
+        assert!(!check(tampered));
+    }
+
+    #[test]
+    fn poly_split_then_concat_is_the_identity() {
+        let a = [7, 6, 5, 4, 3, 2, 1];
+
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, _| {
+            let a = load_poly(ctx, &a);
+            let cells_before = ctx.advice.len();
+
+            // a = (4x^3 + 3x^2 + 2x + 1) + x^4 * (7x^2 + 6x + 5)
+            let (low, high) = poly_split::<4, Fr>(&a);
+            assert_eq!(vec_assigned_to_vec_u64(&low), vec![4, 3, 2, 1]);
+            assert_eq!(vec_assigned_to_vec_u64(&high), vec![7, 6, 5]);
+
+            let joined = poly_concat::<4, Fr>(&low, &high);
+            assert_eq!(vec_assigned_to_vec_u64(&joined), vec![7, 6, 5, 4, 3, 2, 1]);
+
+            // The coefficients are the same cells as the input and no cell is assigned
+            for (x, y) in joined.iter().zip(a.iter()) {
+                assert_eq!(x.cell.unwrap().offset, y.cell.unwrap().offset);
+            }
+            assert_eq!(ctx.advice.len(), cells_before);
+        });
+        assert!(satisfied);
+    }
+
+    #[test]
+    fn assert_monic_pass_and_fail() {
+        let check = |a: [u64; 5]| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                assert_monic::<4, Fr>(ctx, &a, range.gate());
+            })
+            .1
+        };
+
+        // x^4 + 1 is monic
+        assert!(check([1, 0, 0, 0, 1]));
+        // The constant term is not the leading coefficient
+        assert!(!check([2, 0, 0, 0, 1]));
+        assert!(!check([0, 0, 0, 0, 1]));
+    }
+
+    #[test]
+    fn poly_reverse_twice_is_the_identity() {
+        let (_, satisfied) = mock_run::<Fr, _>(|ctx, _| {
+            // 0x^3 + 5x^2 + 0x + 7 reverses to 7x^3 + 0x^2 + 5x + 0
+            let a = load_poly(ctx, &[0, 5, 0, 7]);
+            let cells_before = ctx.advice.len();
+
+            let reversed = poly_reverse::<3, Fr>(&a);
+            assert_eq!(vec_assigned_to_vec_u64(&reversed), vec![7, 0, 5, 0]);
+
+            let twice = poly_reverse::<3, Fr>(&reversed);
+            for (x, y) in twice.iter().zip(a.iter()) {
+                assert_eq!(x.cell.unwrap().offset, y.cell.unwrap().offset);
+            }
+
+            // No cell is assigned
+            assert_eq!(ctx.advice.len(), cells_before);
+        });
+        assert!(satisfied);
+    }
+
+    /// Reference inverse modulo x^K and q by solving a * b = 1 coefficient by coefficient, b_e = -a_0^-1 * (a_1 * b_(e-1) + ... + a_e * b_0)
+    fn inverse_mod_xk_reference(a: &[u64], k: usize, q: u64) -> Vec<u64> {
+        let a_low = a.iter().rev().copied().collect::<Vec<_>>();
+        let a0_inv = crate::chips::utils::mod_inverse(a_low[0], q);
+        let mut b = vec![a0_inv];
+        for e in 1..k {
+            let sum = (1..=e.min(a_low.len() - 1))
+                .map(|j| a_low[j] * b[e - j] % q)
+                .sum::<u64>()
+                % q;
+            b.push((q - sum) % q * a0_inv % q);
+        }
+        b.into_iter().rev().collect()
+    }
+
+    #[test]
+    fn poly_inverse_mod_xk_matches_reference_inverse() {
+        // 3x^2 + 2x + 5 modulo x^4
+        let a = [3, 2, 5];
+        let (b, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            vec_assigned_to_vec_u64(&poly_inverse_mod_xk::<4, 97, Fr>(ctx, &a, range))
+        });
+        assert!(satisfied);
+        assert_eq!(b, vec![17, 10, 62, 39]);
+        assert_eq!(b, inverse_mod_xk_reference(&a, 4, 97));
+
+        // x^4 - x + 1 modulo x^6, where the inverse is 1 + x + x^2 + x^3 - x^5
+        let a = [1, 0, 0, 96, 1];
+        let (b, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &a);
+            vec_assigned_to_vec_u64(&poly_inverse_mod_xk::<6, 97, Fr>(ctx, &a, range))
+        });
+        assert!(satisfied);
+        assert_eq!(b, vec![96, 0, 1, 1, 1, 1]);
+        assert_eq!(b, inverse_mod_xk_reference(&a, 6, 97));
+    }
+
+    #[test]
+    fn assert_coeffs_sorted_pass_and_fail() {
+        let check = |a: [u64; 5]| {
+            mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                assert_coeffs_sorted::<4, Fr>(ctx, &a, range, 7);
+            })
+            .1
+        };
+
+        // Non-decreasing, with repeated coefficients
+        assert!(check([0, 3, 3, 50, 96]));
+        assert!(check([7, 7, 7, 7, 7]));
+        // A single inversion is rejected
+        assert!(!check([0, 3, 2, 50, 96]));
+        assert!(!check([96, 50, 3, 3, 0]));
+    }
+
+    #[test]
+    fn poly_coeff_dot_mod_matches_native_reference() {
+        for (a, b) in [
+            ([1, 2, 3, 4], [5, 6, 7, 8]),
+            ([96, 96, 96, 96], [96, 96, 96, 96]),
+            ([0, 50, 0, 13], [77, 2, 96, 0]),
+        ] {
+            let (dot, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+                let a = load_poly(ctx, &a);
+                let b = load_poly(ctx, &b);
+                let dot = poly_coeff_dot_mod::<3, 97, Fr>(ctx, &a, &b, range);
+                *dot.value()
+            });
+            assert!(satisfied);
+
+            let expected = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum::<u64>() % 97;
+            assert_eq!(dot, Fr::from(expected));
+        }
+    }
 }
@@ -0,0 +1,140 @@
+use halo2_base::gates::GateChip;
+use halo2_base::gates::GateInstructions;
+use halo2_base::safe_types::RangeChip;
+use halo2_base::safe_types::RangeInstructions;
+use halo2_base::utils::ScalarField;
+use halo2_base::AssignedValue;
+use halo2_base::Context;
+use halo2_base::QuantumCell::Constant;
+
+/// Returns the number of coefficients in the range [0, Q - 1] that can be packed in a single field element
+///
+/// * Each coefficient takes q_bits = bits(Q - 1) bits, therefore Q <= 2^q_bits
+/// * w coefficients are packed as sum_i c_i * Q^i <= Q^w - 1 < 2^(q_bits * w)
+/// * w is the largest integer such that q_bits * w <= F::CAPACITY, so that the packed value never wraps around the prime field
+pub fn pack_width<const Q: u64, F: ScalarField>() -> usize {
+    // get the number of bits needed to represent the value of Q - 1
+    let binary_representation = format!("{:b}", (Q - 1));
+    let q_bits = binary_representation.len();
+
+    F::CAPACITY as usize / q_bits
+}
+
+/// Packs the coefficients of the polynomial a into field elements, `pack_width::<Q, F>()` coefficients per field element
+///
+/// * The coefficients are split into chunks [a_DEG, ..., a_DEG-w+1], [a_DEG-w, ...] and so on. The last chunk may be shorter
+/// * Each chunk [c_0, c_1, ..., c_w-1] is packed as c_0 + c_1 * Q + ... + c_w-1 * Q^(w-1)
+/// * DEG is the degree of the polynomial
+/// * Input polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that the coefficients of a are in the range [0, Q - 1], e.g. a ternary secret key with coefficients 0, 1 or Q - 1
+pub fn poly_pack<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    a: Vec<AssignedValue<F>>,
+    gate: &GateChip<F>,
+) -> Vec<AssignedValue<F>> {
+    // assert that the degree of the polynomial a is equal to DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    let width = pack_width::<Q, F>();
+    assert!(width > 0);
+
+    // powers of Q [1, Q, Q^2, ..., Q^(width-1)]
+    let mut powers = vec![];
+    let mut power = F::one();
+    for _ in 0..width {
+        powers.push(power);
+        power *= F::from(Q);
+    }
+
+    let mut packed = vec![];
+
+    for chunk in a.chunks(width) {
+        let packed_val = gate.inner_product(
+            ctx,
+            chunk.iter().copied(),
+            powers
+                .iter()
+                .take(chunk.len())
+                .map(|power| Constant(*power)),
+        );
+        packed.push(packed_val);
+    }
+
+    // assert that the number of packed elements is ceil((DEG + 1) / width)
+    assert_eq!(packed.len(), (DEG + width) / width);
+
+    packed
+}
+
+/// Unpacks field elements produced by `poly_pack` into the coefficients of a polynomial of degree DEG
+///
+/// * Each packed element is decomposed in base Q with `div_mod`. This enforces that every recovered coefficient is in the range [0, Q - 1]
+/// * After the expected number of digits has been extracted, the quotient is enforced to be zero, therefore the packed element did not hide extra digits
+/// * DEG is the degree of the output polynomial
+/// * Output polynomial is parsed as a vector of assigned coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that each packed element is less than 2^(q_bits * w) where w is `pack_width::<Q, F>()`
+pub fn poly_unpack<const DEG: usize, const Q: u64, F: ScalarField>(
+    ctx: &mut Context<F>,
+    packed: Vec<AssignedValue<F>>,
+    range: &RangeChip<F>,
+) -> Vec<AssignedValue<F>> {
+    let width = pack_width::<Q, F>();
+    assert!(width > 0);
+
+    // assert that the number of packed elements is ceil((DEG + 1) / width)
+    assert_eq!(packed.len(), (DEG + width) / width);
+
+    // get the number of bits needed to represent the value of Q - 1
+    let binary_representation = format!("{:b}", (Q - 1));
+    let q_bits = binary_representation.len();
+
+    let mut a = vec![];
+
+    for (j, packed_val) in packed.iter().enumerate() {
+        let chunk_len = width.min(DEG + 1 - j * width);
+        let mut acc = *packed_val;
+
+        for i in 0..chunk_len {
+            // The value of acc is less than Q^(width - i) <= 2^(q_bits * (width - i))
+            let (quotient, rem) = range.div_mod(ctx, acc, Q, q_bits * (width - i));
+            a.push(rem);
+            acc = quotient;
+        }
+
+        // All the digits of the chunk have been extracted, the quotient must be zero
+        range.gate().assert_is_const(ctx, &acc, &F::zero());
+    }
+
+    // assert that the unpacked polynomial has degree DEG
+    assert_eq!(a.len() - 1, DEG);
+
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::test_utils::{load_poly, mock_run};
+    use crate::chips::utils::vec_assigned_to_vec_u64;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn poly_pack_unpack_round_trip_ternary() {
+        // With Q = 97, 36 coefficients of 7 bits fit in the 253 bits of capacity of Fr
+        assert_eq!(pack_width::<97, Fr>(), 36);
+
+        // A ternary polynomial of degree 40 with coefficients 0, 1 or Q - 1, packed in two elements
+        let a: Vec<u64> = (0..41).map(|i| [0, 1, 96][i % 3]).collect();
+
+        let ((packed_len, unpacked), satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let input = load_poly(ctx, &a);
+            let packed = poly_pack::<40, 97, Fr>(ctx, input, range.gate());
+            let packed_len = packed.len();
+            let unpacked = poly_unpack::<40, 97, Fr>(ctx, packed, range);
+            (packed_len, vec_assigned_to_vec_u64(&unpacked))
+        });
+        assert!(satisfied);
+        assert_eq!(packed_len, 2);
+        assert_eq!(unpacked, a);
+    }
+}
@@ -0,0 +1,131 @@
+use halo2_base::utils::ScalarField;
+use halo2_base::Context;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Resource usage accumulated over all the invocations of a chip
+///
+/// * `calls` is the number of times the chip has been invoked
+/// * `advice_cells` is the number of advice cells assigned by the chip, excluding the cells of its inputs
+/// * `lookup_cells` is the number of cells that the chip sends to the lookup table of the range chip
+///
+/// The counts of a chip include the cells assigned by the chips it calls internally (e.g. `poly_mul_mod` includes `poly_mul_equal_deg`)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChipStats {
+    pub calls: usize,
+    pub advice_cells: usize,
+    pub lookup_cells: usize,
+}
+
+/// Report of the resources used by each instrumented chip, sorted by chip name
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StatsReport(pub Vec<(&'static str, ChipStats)>);
+
+impl StatsReport {
+    /// Returns the stats accumulated by the chip `name`, if it has been invoked
+    pub fn get(&self, name: &str) -> Option<ChipStats> {
+        self.0
+            .iter()
+            .find(|(chip, _)| *chip == name)
+            .map(|(_, stats)| *stats)
+    }
+}
+
+impl fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<40} {:>8} {:>14} {:>14}",
+            "chip", "calls", "advice cells", "lookup cells"
+        )?;
+        for (chip, stats) in &self.0 {
+            writeln!(
+                f,
+                "{:<40} {:>8} {:>14} {:>14}",
+                chip, stats.calls, stats.advice_cells, stats.lookup_cells
+            )?;
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    static STATS: RefCell<BTreeMap<&'static str, ChipStats>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Snapshot of the statistics of a `Context` taken at the beginning of a chip invocation
+#[derive(Clone, Copy, Debug)]
+pub struct StatsStart {
+    advice: usize,
+    lookup: usize,
+}
+
+/// Takes a snapshot of the number of advice cells and lookup cells assigned so far in `ctx`
+pub fn start<F: ScalarField>(ctx: &Context<F>) -> StatsStart {
+    StatsStart {
+        advice: ctx.advice.len(),
+        lookup: ctx.cells_to_lookup.len(),
+    }
+}
+
+/// Attributes to the chip `name` the cells assigned in `ctx` since the snapshot `start`
+pub fn record<F: ScalarField>(name: &'static str, ctx: &Context<F>, start: StatsStart) {
+    STATS.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        let entry = stats.entry(name).or_default();
+        entry.calls += 1;
+        entry.advice_cells += ctx.advice.len() - start.advice;
+        entry.lookup_cells += ctx.cells_to_lookup.len() - start.lookup;
+    });
+}
+
+/// Returns the report accumulated so far on the current thread
+pub fn report() -> StatsReport {
+    STATS.with(|stats| StatsReport(stats.borrow().iter().map(|(k, v)| (*k, *v)).collect()))
+}
+
+/// Clears the report accumulated so far on the current thread
+pub fn reset() {
+    STATS.with(|stats| stats.borrow_mut().clear());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::poly_operations::poly_mul_equal_deg;
+    use crate::chips::test_utils::{load_poly, mock_run};
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+    use halo2_base::safe_types::RangeInstructions;
+
+    #[test]
+    fn report_counts_poly_mul_equal_deg() {
+        reset();
+
+        let (advice_cells, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[1, 2, 3, 4]);
+            let b = load_poly(ctx, &[5, 6, 7, 8]);
+
+            let before = ctx.advice.len();
+            poly_mul_equal_deg::<3, Fr>(ctx, &a, &b, range.gate());
+            poly_mul_equal_deg::<3, Fr>(ctx, &b, &a, range.gate());
+            ctx.advice.len() - before
+        });
+        assert!(satisfied);
+
+        let stats = report();
+        assert_eq!(
+            stats.get("poly_mul_equal_deg"),
+            Some(ChipStats {
+                calls: 2,
+                advice_cells,
+                lookup_cells: 0,
+            })
+        );
+        assert_eq!(stats.get("poly_mul_mod"), None);
+        assert!(stats.to_string().contains("poly_mul_equal_deg"));
+
+        reset();
+        assert_eq!(report(), StatsReport::default());
+    }
+}
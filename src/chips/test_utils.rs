@@ -0,0 +1,112 @@
+//! Helpers shared by the tests of the chips
+
+use halo2_base::gates::builder::{GateThreadBuilder, RangeCircuitBuilder};
+use halo2_base::halo2_proofs::dev::MockProver;
+use halo2_base::safe_types::RangeChip;
+use halo2_base::utils::ScalarField;
+use halo2_base::AssignedValue;
+use halo2_base::Context;
+use std::env::set_var;
+use std::sync::Mutex;
+
+/// The mock circuits have 2^K rows
+pub const K: usize = 14;
+
+/// Number of bits of the lookup table of the range chip
+pub const LOOKUP_BITS: usize = 13;
+
+// The configuration of the mock circuits is passed through environment variables, which are shared by the tests running in parallel
+static MOCK_LOCK: Mutex<()> = Mutex::new(());
+
+/// Assigns the constraints of `f` in a mock circuit and verifies them with the `MockProver`
+///
+/// * Returns the output of `f` together with true if all the constraints are satisfied
+pub fn mock_run<F: ScalarField, T>(
+    f: impl FnOnce(&mut Context<F>, &RangeChip<F>) -> T,
+) -> (T, bool) {
+    let _guard = MOCK_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+    set_var("LOOKUP_BITS", LOOKUP_BITS.to_string());
+    let mut builder = GateThreadBuilder::mock();
+    let range = RangeChip::<F>::default(LOOKUP_BITS);
+
+    let out = f(builder.main(0), &range);
+
+    builder.config(K, Some(20));
+    let circuit = RangeCircuitBuilder::mock(builder);
+    let satisfied = MockProver::<F>::run(K as u32, &circuit, vec![])
+        .unwrap()
+        .verify()
+        .is_ok();
+
+    (out, satisfied)
+}
+
+/// Assigns the coefficients of a polynomial as witnesses
+pub fn load_poly<F: ScalarField>(ctx: &mut Context<F>, coeffs: &[u64]) -> Vec<AssignedValue<F>> {
+    coeffs
+        .iter()
+        .map(|&coeff| ctx.load_witness(F::from(coeff)))
+        .collect()
+}
+
+/// Reference product of a and b in the ring R_q = Z_q[x]/(x^n + 1), computed outside the circuit
+///
+/// * The polynomials are parsed as vectors of n coefficients [a_n-1, a_n-2, ..., a_1, a_0] where a_0 is the constant term
+pub fn ring_mul(a: &[u64], b: &[u64], q: u64) -> Vec<u64> {
+    let n = a.len();
+    assert_eq!(b.len(), n);
+
+    let q = q as u128;
+    let mut out = vec![0u128; n];
+
+    for (i, &a_i) in a.iter().enumerate() {
+        for (j, &b_j) in b.iter().enumerate() {
+            let prod = a_i as u128 * b_j as u128 % q;
+            // a_i and b_j are the coefficients of x^(n-1-i) and x^(n-1-j)
+            let exp = 2 * (n - 1) - i - j;
+            if exp < n {
+                out[n - 1 - exp] = (out[n - 1 - exp] + prod) % q;
+            } else {
+                // x^n = -1 in the ring
+                out[2 * n - 1 - exp] = (out[2 * n - 1 - exp] + q - prod) % q;
+            }
+        }
+    }
+
+    out.iter().map(|&coeff| coeff as u64).collect()
+}
+
+/// Reference sum of a and b in Z_q, coefficient-wise
+pub fn ring_add(a: &[u64], b: &[u64], q: u64) -> Vec<u64> {
+    a.iter()
+        .zip(b)
+        .map(|(&a_i, &b_i)| ((a_i as u128 + b_i as u128) % q as u128) as u64)
+        .collect()
+}
+
+/// Reference negation of a in Z_q, coefficient-wise
+pub fn ring_neg(a: &[u64], q: u64) -> Vec<u64> {
+    a.iter().map(|&a_i| (q - a_i) % q).collect()
+}
+
+/// Reference image of a under the automorphism x -> x^k of the ring R_q = Z_q[x]/(x^n + 1), computed outside the circuit
+///
+/// * The polynomial is parsed as a vector of n coefficients [a_n-1, a_n-2, ..., a_1, a_0] where a_0 is the constant term
+/// * Assumes that k is odd, such that x -> x^k permutes the monomials up to their sign
+pub fn ring_automorphism(a: &[u64], k: usize, q: u64) -> Vec<u64> {
+    let n = a.len();
+    let mut out = vec![0; n];
+
+    for e in 0..n {
+        // x^e is mapped to x^(e * k mod 2n), and x^n = -1 in the ring
+        let target = e * k % (2 * n);
+        if target < n {
+            out[n - 1 - target] = a[n - 1 - e];
+        } else {
+            out[2 * n - 1 - target] = (q - a[n - 1 - e]) % q;
+        }
+    }
+
+    out
+}
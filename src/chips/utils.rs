@@ -1,4 +1,6 @@
 use halo2_base::{utils::ScalarField, AssignedValue};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// Performs long polynomial division on two polynomials
 /// Returns the quotient and remainder
@@ -8,47 +10,96 @@ use halo2_base::{utils::ScalarField, AssignedValue};
 /// * DEG_DVS is the degree of the divisor
 /// * Q is the modulus of the Ring. All the coefficients will be in the range [0, Q-1]
 /// * Assumes that coefficients of the dividend and divisor are u64 values
+/// * The coefficients are processed as i128 values (two 64-bit limbs), therefore Q can be as large as 2^64 - 1 without overflowing
+/// * In debug builds, any wraparound of the intermediate arithmetic or of the conversion back to u64 triggers a panic, since it would produce an invalid witness
 pub fn div_euclid<const DEG_DVD: usize, const DEG_DVS: usize, const Q: u64>(
-    dividend: &Vec<u64>,
-    divisor: &Vec<u64>,
+    dividend: &[u64],
+    divisor: &[u64],
+) -> (Vec<u64>, Vec<u64>) {
+    div_euclid_var(dividend, divisor, DEG_DVD, DEG_DVS, Q)
+}
+
+/// Performs long polynomial division on two polynomials, with the degrees and the modulus passed at runtime
+///
+/// * Same as `div_euclid`, where deg_dvd, deg_dvs and q take the place of the const generics DEG_DVD, DEG_DVS and Q
+/// * Only relies on integer arithmetic, therefore it compiles to any target, including `wasm32-unknown-unknown`
+pub fn div_euclid_var(
+    dividend: &[u64],
+    divisor: &[u64],
+    deg_dvd: usize,
+    deg_dvs: usize,
+    q: u64,
+) -> (Vec<u64>, Vec<u64>) {
+    // The no-op callback is inlined away, therefore the division does not pay for the progress reporting
+    div_euclid_var_with_progress(dividend, divisor, deg_dvd, deg_dvs, q, |_| {})
+}
+
+/// Performs long polynomial division on two polynomials, reporting the progress of the long division loop
+///
+/// * Same as `div_euclid_var`, with an additional callback `progress` that is invoked with the percentage of the loop completed, in the range [0, 100]
+/// * The callback is only invoked when the percentage changes, therefore it is called at most 101 times whatever the degree, with non decreasing values, and the last call reports 100
+/// * Meant for large degrees (e.g. N = 4096), where the division can take long enough to appear to hang
+pub fn div_euclid_var_with_progress<P: FnMut(u8)>(
+    dividend: &[u64],
+    divisor: &[u64],
+    deg_dvd: usize,
+    deg_dvs: usize,
+    q: u64,
+    mut progress: P,
 ) -> (Vec<u64>, Vec<u64>) {
     if divisor.is_empty() || divisor.iter().all(|&x| x == 0) {
         panic!("Cannot divide by a zero polynomial!");
     }
     if dividend.is_empty() || dividend.iter().all(|&x| x == 0) {
-        let quotient = vec![0; DEG_DVD - DEG_DVS + 1];
-        let remainder = vec![0; DEG_DVS];
+        let quotient = vec![0; deg_dvd - deg_dvs + 1];
+        let remainder = vec![0; deg_dvs];
 
         // turn quotient and remainder into u64
         let quotient = quotient.iter().map(|&x| x as u64).collect::<Vec<u64>>();
         let remainder = remainder.iter().map(|&x| x as u64).collect::<Vec<u64>>();
 
+        progress(100);
+
         return (quotient, remainder);
     }
 
-    // assert that the degree of the dividend is equal to DEG_DVD
-    assert_eq!(dividend.len() - 1, DEG_DVD);
+    // assert that the degree of the dividend is equal to deg_dvd
+    assert_eq!(dividend.len() - 1, deg_dvd);
 
-    // assert that the degree of the divisor is equal to DEG_DVS
-    assert_eq!(divisor.len() - 1, DEG_DVS);
+    // assert that the degree of the divisor is equal to deg_dvs
+    assert_eq!(divisor.len() - 1, deg_dvs);
 
-    // transform the dividend and divisor into a vector of i64
-    let mut dividend = dividend.iter().map(|&x| x as i64).collect::<Vec<i64>>();
-    let divisor = divisor.iter().map(|&x| x as i64).collect::<Vec<i64>>();
+    // transform the dividend and divisor into a vector of i128
+    // a u64 coefficient larger than 2^63 would wrap around if cast to i64
+    let mut dividend = dividend.iter().map(|&x| x as i128).collect::<Vec<i128>>();
+    let divisor = divisor.iter().map(|&x| x as i128).collect::<Vec<i128>>();
 
     let mut quotient = Vec::new();
     let mut remainder = Vec::new();
 
+    // The loop runs once per coefficient of the quotient
+    let num_steps = dividend.len() - (divisor.len() - 1);
+    let mut reported = 0u8;
+    progress(reported);
+
     while dividend.len() > divisor.len() - 1 {
         let leading_coefficient_ratio = dividend[0] / divisor[0];
         quotient.push(leading_coefficient_ratio);
 
+        // Each step only updates deg_dvs + 1 coefficients and depends on the previous one, which is too little work to dispatch to a thread pool.
+        // With the `parallel` feature, independent divisions are performed in parallel instead, see `div_by_cyclo_witness_batch`
         for (i, coeff) in divisor.iter().enumerate() {
-            let diff = dividend[i] - leading_coefficient_ratio * *coeff;
+            let diff = mul_sub_i128(dividend[i], leading_coefficient_ratio, *coeff);
             dividend[i] = diff;
         }
 
         dividend.remove(0);
+
+        let percentage = (quotient.len() * 100 / num_steps) as u8;
+        if percentage != reported {
+            reported = percentage;
+            progress(reported);
+        }
     }
 
     for coeff in &dividend {
@@ -67,29 +118,177 @@ pub fn div_euclid<const DEG_DVD: usize, const DEG_DVS: usize, const Q: u64>(
     // Range over remainder. If any element is negative, add Q to it
     for coeff in &mut remainder {
         if *coeff < 0 {
-            *coeff += Q as i64;
+            *coeff += q as i128;
         }
     }
 
     // Convert remainder back to u64
+    debug_assert!(
+        remainder.iter().all(|&x| (0..q as i128).contains(&x)),
+        "div_euclid: remainder coefficient out of [0, Q-1] would wrap around when converted to u64"
+    );
     let remainder = remainder.iter().map(|&x| x as u64).collect::<Vec<u64>>();
 
     // Convert quotient back to u64
+    debug_assert!(
+        quotient.iter().all(|&x| (0..=u64::MAX as i128).contains(&x)),
+        "div_euclid: quotient coefficient would wrap around when converted to u64"
+    );
     let quotient = quotient.iter().map(|&x| x as u64).collect::<Vec<u64>>();
 
-    // pad quotient with zeroes at the beginning to make its degree equal to DEG_DVD - DEG_DVS
+    // pad quotient with zeroes at the beginning to make its degree equal to deg_dvd - deg_dvs
     let mut quotient = quotient;
-    while quotient.len() - 1 < DEG_DVD - DEG_DVS {
+    // The quotient may be empty after trimming (e.g. when the leading deg_dvd - deg_dvs + 1 coefficients of the dividend are zero)
+    while quotient.len() < deg_dvd - deg_dvs + 1 {
         quotient.insert(0, 0);
     }
 
     (quotient, remainder)
 }
 
+/// Computes a - b * c on i128
+///
+/// * In debug builds, panics if any intermediate value wraps around
+fn mul_sub_i128(a: i128, b: i128, c: i128) -> i128 {
+    debug_assert!(
+        b.checked_mul(c).and_then(|prod| a.checked_sub(prod)).is_some(),
+        "div_euclid: {} - {} * {} overflows i128",
+        a,
+        b,
+        c
+    );
+    a.wrapping_sub(b.wrapping_mul(c))
+}
+
+/// Computes outside the circuit the quotient and remainder of the division of `dividend` by the cyclotomic polynomial `divisor`
+/// in the shape expected by `poly_divide_by_cyclo_with_witness`
+///
+/// * Returns the quotient of degree DEG_DVD - DEG_DVS and the remainder padded with leading zeroes to degree DEG_DVD
+/// * DEG_DVD is the degree of the dividend
+/// * DEG_DVS is the degree of the divisor
+/// * Q is the modulus of the Ring. All the coefficients will be in the range [0, Q-1]
+/// * Assumes that DEG_DVS < DEG_DVD
+pub fn div_by_cyclo_witness<const DEG_DVD: usize, const DEG_DVS: usize, const Q: u64>(
+    dividend: &[u64],
+    divisor: &[u64],
+) -> (Vec<u64>, Vec<u64>) {
+    div_by_cyclo_witness_var(dividend, divisor, DEG_DVD, DEG_DVS, Q)
+}
+
+/// Computes outside the circuit the quotient and remainder in the shape expected by `poly_divide_by_cyclo_with_witness`, with the degrees and the modulus passed at runtime
+///
+/// * Same as `div_by_cyclo_witness`, where deg_dvd, deg_dvs and q take the place of the const generics DEG_DVD, DEG_DVS and Q
+pub fn div_by_cyclo_witness_var(
+    dividend: &[u64],
+    divisor: &[u64],
+    deg_dvd: usize,
+    deg_dvs: usize,
+    q: u64,
+) -> (Vec<u64>, Vec<u64>) {
+    div_by_cyclo_witness_var_with_progress(dividend, divisor, deg_dvd, deg_dvs, q, |_| {})
+}
+
+/// Computes outside the circuit the quotient and remainder in the shape expected by `poly_divide_by_cyclo_with_witness`, reporting the progress of the long division
+///
+/// * Same as `div_by_cyclo_witness_var`, where `progress` is invoked as in `div_euclid_var_with_progress`
+pub fn div_by_cyclo_witness_var_with_progress<P: FnMut(u8)>(
+    dividend: &[u64],
+    divisor: &[u64],
+    deg_dvd: usize,
+    deg_dvs: usize,
+    q: u64,
+    progress: P,
+) -> (Vec<u64>, Vec<u64>) {
+    let (quotient, mut remainder) =
+        div_euclid_var_with_progress(dividend, divisor, deg_dvd, deg_dvs, q, progress);
+
+    // After the division, the degree of the quotient should be equal to deg_dvd - deg_dvs
+    assert_eq!(quotient.len() - 1, deg_dvd - deg_dvs);
+
+    // Furthermore, the degree of the remainder must be strictly less than the degree of the divisor
+    assert!(remainder.len() <= deg_dvs);
+
+    // Later we need to perform the operation remainder + prod where prod is of degree deg_dvd
+    // In order to perform the operation inside the circuit we need to pad the remainder with 0s at the beginning to make its degree equal to deg_dvd
+    while remainder.len() < deg_dvd + 1 {
+        remainder.insert(0, 0);
+    }
+
+    // Now remainder must be of degree deg_dvd
+    assert_eq!(remainder.len() - 1, deg_dvd);
+
+    (quotient, remainder)
+}
+
+/// Computes outside the circuit the quotients and remainders of several independent divisions by the cyclotomic polynomial `divisor`
+/// in the shape expected by `poly_divide_by_cyclo_with_witness`
+///
+/// * Same as calling `div_by_cyclo_witness` on each dividend, e.g. on the products pk0 * u and pk1 * u of BFV encryption
+/// * With the `parallel` feature the divisions are performed in parallel. The steps of a long division depend on each other, therefore a whole division is the unit of work dispatched to the thread pool
+pub fn div_by_cyclo_witness_batch<const DEG_DVD: usize, const DEG_DVS: usize, const Q: u64>(
+    dividends: &[Vec<u64>],
+    divisor: &[u64],
+) -> Vec<(Vec<u64>, Vec<u64>)> {
+    #[cfg(feature = "parallel")]
+    let dividends = dividends.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let dividends = dividends.iter();
+
+    dividends
+        .map(|dividend| div_by_cyclo_witness::<DEG_DVD, DEG_DVS, Q>(dividend, divisor))
+        .collect()
+}
+
+/// Reduces outside the circuit a polynomial of degree 2N - 2 by the cyclotomic polynomial x^N + 1, mirroring the reduction performed by `poly_divide_by_cyclo`
+///
+/// * The remainder is computed with `div_by_cyclo_witness_var`, namely the same long division that generates the witnesses of the circuit
+/// * Returns the remainder of degree N - 1 as a vector of N coefficients [a_N-1, a_N-2, ..., a_1, a_0], namely the padded remainder without its leading zeroes
+/// * This is the native reference to compare the reduction of the crate against another FHE library, e.g. a ciphertext polynomial reduced by SEAL
+/// * N is the degree of the cyclotomic polynomial x^N + 1
+/// * Q is the modulus of the Ring. All the coefficients will be in the range [0, Q-1]
+/// * Assumes that `poly` is of degree 2N - 2 and that its coefficients are in the range [0, Q-1]
+pub fn reduce_by_cyclo<const N: usize, const Q: u64>(poly: &[u64]) -> Vec<u64> {
+    let deg_dvd = poly.len() - 1;
+
+    // The dividend of `poly_divide_by_cyclo` is the product of two polynomials of degree N - 1
+    assert_eq!(deg_dvd, 2 * N - 2);
+
+    // x^N + 1 parsed as [1, 0, ..., 0, 1]
+    let mut cyclo = vec![0u64; N + 1];
+    cyclo[0] = 1;
+    cyclo[N] = 1;
+
+    let (_, remainder) = div_by_cyclo_witness_var(poly, &cyclo, deg_dvd, N, Q);
+
+    // The remainder is padded to degree deg_dvd, its leading deg_dvd - (N - 1) coefficients are zero
+    let reduced = remainder[deg_dvd - (N - 1)..].to_vec();
+
+    assert_eq!(reduced.len(), N);
+
+    reduced
+}
+
+/// Convert a vector of AssignedValue to a vector of u64
+/// 
+/// * Assumes that each element of AssignedValue can be represented in 8 bytes
+/// * With the `parallel` feature the conversion is performed in parallel
+#[cfg(feature = "parallel")]
+pub fn vec_assigned_to_vec_u64<F: ScalarField>(vec: &[AssignedValue<F>]) -> Vec<u64> {
+    vec.par_iter()
+        .map(|assigned| {
+            let value_bytes_le = assigned.value().to_bytes_le();
+            let mut array_value_8_bytes_le = [0u8; 8];
+            array_value_8_bytes_le.copy_from_slice(&value_bytes_le[..8]);
+            u64::from_le_bytes(array_value_8_bytes_le)
+        })
+        .collect()
+}
+
 /// Convert a vector of AssignedValue to a vector of u64
 /// 
 /// * Assumes that each element of AssignedValue can be represented in 8 bytes
-pub fn vec_assigned_to_vec_u64<F: ScalarField>(vec: &Vec<AssignedValue<F>>) -> Vec<u64> {
+#[cfg(not(feature = "parallel"))]
+pub fn vec_assigned_to_vec_u64<F: ScalarField>(vec: &[AssignedValue<F>]) -> Vec<u64> {
     let mut vec_u64 = Vec::new();
 
     for i in 0..vec.len() {
@@ -104,3 +303,344 @@ pub fn vec_assigned_to_vec_u64<F: ScalarField>(vec: &Vec<AssignedValue<F>>) -> V
     }
     vec_u64
 }
+
+/// Splits a u128 value into its two 64-bit limbs
+///
+/// * Returns (hi, lo) such that x = hi * 2^64 + lo
+pub fn split_limbs(x: u128) -> (u64, u64) {
+    ((x >> 64) as u64, x as u64)
+}
+
+/// Reduces a value represented across two 64-bit limbs by Q outside the circuit
+///
+/// * Returns (hi * 2^64 + lo) mod Q
+/// * The value is recomposed on u128, therefore it does not overflow for any Q up to 2^64 - 1
+pub fn reduce_limbs<const Q: u64>(hi: u64, lo: u64) -> u64 {
+    ((((hi as u128) << 64) | lo as u128) % Q as u128) as u64
+}
+
+/// Convert a vector of AssignedValue to a vector of two-limb values (hi, lo)
+///
+/// * Assumes that each element of AssignedValue can be represented in 16 bytes
+/// * Used to read coefficients that exceed u64, such as the coefficients of the product of two polynomials with 63-bit coefficients before reduction
+pub fn vec_assigned_to_vec_limbs<F: ScalarField>(vec: &[AssignedValue<F>]) -> Vec<(u64, u64)> {
+    let mut vec_limbs = Vec::new();
+
+    for assigned in vec {
+        let value_bytes_le = assigned.value().to_bytes_le();
+
+        let mut lo_bytes_le = [0u8; 8];
+        lo_bytes_le.copy_from_slice(&value_bytes_le[..8]);
+        let mut hi_bytes_le = [0u8; 8];
+        hi_bytes_le.copy_from_slice(&value_bytes_le[8..16]);
+
+        vec_limbs.push((u64::from_le_bytes(hi_bytes_le), u64::from_le_bytes(lo_bytes_le)));
+    }
+    vec_limbs
+}
+
+/// Returns true if every value in the range [0, bound] is guaranteed to be smaller than the prime p of the field F
+///
+/// * Checks that bound can be expressed in at most F::CAPACITY bits, namely bound < 2^CAPACITY <= p
+/// * Used to assert the overflow analysis of the chips at synthesis time
+pub fn fits_in_field<F: ScalarField>(bound: u128) -> bool {
+    fits_in_capacity(bound, F::CAPACITY)
+}
+
+/// Returns true if bound can be expressed in at most `capacity` bits
+///
+/// * Same as `fits_in_field` for a field of `capacity` bits of capacity
+pub fn fits_in_capacity(bound: u128, capacity: u32) -> bool {
+    128 - bound.leading_zeros() <= capacity
+}
+
+/// Returns true if `n` is a prime number
+///
+/// * Uses the Miller-Rabin test with the first 12 primes 2, 3, 5, ..., 37 as bases, which is deterministic for every n < 2^64
+/// * Declared as `const fn` so that it can be used to validate const generic parameters such as Q at compile time
+pub const fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+
+    // n is either one of the bases or coprime to all of them
+    let mut i = 0;
+    while i < BASES.len() {
+        if n % BASES[i] == 0 {
+            return n == BASES[i];
+        }
+        i += 1;
+    }
+
+    // n - 1 = d * 2^s with d odd
+    let mut d = n - 1;
+    let mut s = 0;
+    while d % 2 == 0 {
+        d /= 2;
+        s += 1;
+    }
+
+    let mut i = 0;
+    'bases: while i < BASES.len() {
+        let mut x = pow_mod(BASES[i], d, n);
+        i += 1;
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+
+        // n is a probable prime for this base if x^(2^r) = -1 mod n for some r < s
+        let mut r = 1;
+        while r < s {
+            x = (x as u128 * x as u128 % n as u128) as u64;
+            if x == n - 1 {
+                continue 'bases;
+            }
+            r += 1;
+        }
+
+        return false;
+    }
+
+    true
+}
+
+/// Computes base^exp mod modulus outside the circuit
+///
+/// * Intermediate products are computed on u128, therefore they never overflow
+/// * Declared as `const fn` so that constants such as the twiddle factors of the NTT can be computed at compile time
+pub const fn pow_mod(base: u64, exp: u64, modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let mut result = 1u128 % modulus;
+    let mut base = base as u128 % modulus;
+    let mut exp = exp;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    result as u64
+}
+
+/// Computes the inverse of a modulo q outside the circuit
+///
+/// * Assumes that q is prime and that a is not a multiple of q. The inverse is computed as a^(q-2) mod q (Fermat's little theorem)
+pub fn mod_inverse(a: u64, q: u64) -> u64 {
+    assert!(is_prime(q), "modular inverse requires a prime modulus");
+    assert!(a % q != 0, "zero has no modular inverse");
+
+    pow_mod(a, q - 2, q)
+}
+
+/// Computes outside the circuit the inverse of the polynomial a modulo x^K and Q with Newton iteration
+///
+/// * Returns b of degree K - 1 such that a * b = 1 mod (x^K, Q), as a vector of K coefficients [b_K-1, b_K-2, ..., b_1, b_0]
+/// * Starting from b = a_0^-1, each iteration b <- b * (2 - a * b) mod x^m doubles the number m of correct coefficients, therefore ceil(log2(K)) iterations are performed
+/// * Only the coefficients of a of degree smaller than K are used
+/// * Assumes that `a` is parsed as [a_n, a_n-1, ..., a_1, a_0] with coefficients in the range [0, Q-1]
+/// * Assumes that Q is prime and that the constant term a_0 is not zero, namely that it is a unit modulo Q
+pub fn inverse_mod_xk<const K: usize, const Q: u64>(a: &[u64]) -> Vec<u64> {
+    assert!(K > 0);
+
+    let q = Q as u128;
+
+    // Coefficients of a in increasing degree order, truncated to x^K
+    let a_low = a.iter().rev().take(K).map(|&c| c as u128).collect::<Vec<_>>();
+
+    // Product of x and y in increasing degree order, truncated to x^m
+    let mul_trunc = |x: &[u128], y: &[u128], m: usize| {
+        let mut z = vec![0u128; m];
+        for (i, xi) in x.iter().enumerate().take(m) {
+            for (j, yj) in y.iter().enumerate().take(m - i) {
+                z[i + j] = (z[i + j] + xi * yj) % q;
+            }
+        }
+        z
+    };
+
+    let mut b = vec![mod_inverse(a_low[0] as u64, Q) as u128];
+    let mut m = 1;
+
+    while m < K {
+        m = (2 * m).min(K);
+
+        // t = 2 - a * b mod x^m
+        let mut t = mul_trunc(&a_low, &b, m);
+        for c in t.iter_mut() {
+            *c = (q - *c) % q;
+        }
+        t[0] = (t[0] + 2) % q;
+
+        b = mul_trunc(&b, &t, m);
+    }
+
+    assert_eq!(b.len(), K);
+
+    b.iter().rev().map(|&c| c as u64).collect()
+}
+
+/// Finds a primitive `order`-th root of unity modulo q outside the circuit
+///
+/// * `order` must be a power of 2 dividing q - 1. In the negacyclic NTT of size N, `order` is 2N
+/// * Assumes that q is prime
+/// * psi is a primitive `order`-th root of unity if and only if psi^(order/2) = -1 mod q
+/// * Declared as `const fn` so that the root of the NTT can be computed at compile time
+pub const fn primitive_root_of_unity(order: u64, q: u64) -> u64 {
+    assert!(is_prime(q), "roots of unity require a prime modulus");
+    assert!(order.is_power_of_two() && order >= 2);
+    assert!((q - 1) % order == 0, "order must divide q - 1");
+
+    let mut g = 2;
+    while g < q {
+        let psi = pow_mod(g, (q - 1) / order, q);
+        if pow_mod(psi, order / 2, q) == q - 1 {
+            return psi;
+        }
+        g += 1;
+    }
+
+    panic!("no primitive root of unity of the given order modulo q");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random coefficients in the range [0, q - 1]
+    fn pseudo_random_poly(len: usize, q: u64, seed: u64) -> Vec<u64> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                // xorshift64
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state % q
+            })
+            .collect()
+    }
+
+    /// x^n + 1 parsed as [1, 0, ..., 0, 1]
+    fn cyclo(n: usize) -> Vec<u64> {
+        let mut cyclo = vec![0; n + 1];
+        cyclo[0] = 1;
+        cyclo[n] = 1;
+        cyclo
+    }
+
+    #[test]
+    fn is_prime_miller_rabin() {
+        let primes = [2, 3, 5, 37, 41, 97, 4637, 18014398509309953, (1 << 61) - 1, u64::MAX - 58];
+        for n in primes {
+            assert!(is_prime(n), "{} is prime", n);
+        }
+
+        // 561 is a Carmichael number, 3215031751 is a strong pseudoprime to the bases 2, 3, 5 and 7
+        // and 3825123056546413051 is a strong pseudoprime to every base up to 31
+        let composites = [0, 1, 4, 15, 561, 1373653, 3215031751, 3825123056546413051, u64::MAX];
+        for n in composites {
+            assert!(!is_prime(n), "{} is composite", n);
+        }
+    }
+
+    #[test]
+    fn div_by_cyclo_witness_batch_matches_serial() {
+        // Run with `--features parallel` to compare the parallel batch with the serial divisions
+        const N: usize = 64;
+        const Q: u64 = 18014398509309953;
+
+        let dividends = (1..=8)
+            .map(|seed| pseudo_random_poly(2 * N - 1, Q, seed))
+            .collect::<Vec<Vec<u64>>>();
+        let divisor = cyclo(N);
+
+        let batch = div_by_cyclo_witness_batch::<{ 2 * N - 2 }, N, Q>(&dividends, &divisor);
+        let serial = dividends
+            .iter()
+            .map(|dividend| div_by_cyclo_witness::<{ 2 * N - 2 }, N, Q>(dividend, &divisor))
+            .collect::<Vec<(Vec<u64>, Vec<u64>)>>();
+
+        assert_eq!(batch, serial);
+    }
+
+    /// Checks that dividend = quotient * divisor + remainder in Z_q[x], with the products computed on u128
+    fn assert_division(
+        dividend: &[u64],
+        divisor: &[u64],
+        quotient: &[u64],
+        remainder: &[u64],
+        q: u64,
+    ) {
+        let q = q as u128;
+        let mut recomposed = remainder.iter().map(|&r| r as u128).collect::<Vec<u128>>();
+        let offset = recomposed.len() - (quotient.len() + divisor.len() - 1);
+
+        for (i, &quotient_i) in quotient.iter().enumerate() {
+            for (j, &divisor_j) in divisor.iter().enumerate() {
+                let prod = quotient_i as u128 * divisor_j as u128 % q;
+                recomposed[offset + i + j] = (recomposed[offset + i + j] + prod) % q;
+            }
+        }
+
+        let dividend = dividend.iter().map(|&x| x as u128).collect::<Vec<u128>>();
+        assert_eq!(recomposed, dividend);
+    }
+
+    #[test]
+    fn div_euclid_overflowing_u64_intermediates() {
+        // 2^64 - 59 is the largest prime below 2^64
+        const N: usize = 8;
+        const Q: u64 = 18446744073709551557;
+
+        // The leading coefficients Q - 1 are subtracted from the zero coefficients of degree N to 2N - 2,
+        // therefore the intermediate values go down to -(Q - 1), which fits neither in a u64 nor in an i64
+        let mut extreme = vec![Q - 1; N - 1];
+        extreme.extend(vec![0; N]);
+
+        for dividend in [extreme, pseudo_random_poly(2 * N - 1, Q, 7)] {
+            let (quotient, remainder) =
+                div_by_cyclo_witness::<{ 2 * N - 2 }, N, Q>(&dividend, &cyclo(N));
+
+            assert_eq!(quotient.len(), N - 1);
+            assert!(remainder.iter().all(|&coeff| coeff < Q));
+            // The remainder has degree at most N - 1 and is padded with leading zeroes to degree 2N - 2
+            assert!(remainder[..N - 1].iter().all(|&coeff| coeff == 0));
+            assert_division(&dividend, &cyclo(N), &quotient, &remainder, Q);
+        }
+    }
+
+    #[test]
+    fn div_euclid_progress_reports() {
+        const Q: u64 = 12289;
+
+        // 255 steps report every percentage, 3 steps report 0, 33, 66 and 100, a zero dividend only reports 100
+        for (n, dividend, num_reports) in [
+            (256, pseudo_random_poly(2 * 256 - 1, Q, 3), 101),
+            (4, pseudo_random_poly(2 * 4 - 1, Q, 5), 4),
+            (4, vec![0; 2 * 4 - 1], 1),
+        ] {
+            let mut reports = Vec::new();
+            let with_progress = div_euclid_var_with_progress(
+                &dividend,
+                &cyclo(n),
+                2 * n - 2,
+                n,
+                Q,
+                |percentage| reports.push(percentage),
+            );
+
+            assert_eq!(
+                with_progress,
+                div_euclid_var(&dividend, &cyclo(n), 2 * n - 2, n, Q)
+            );
+            assert_eq!(reports.len(), num_reports);
+            assert!(reports.windows(2).all(|pair| pair[0] < pair[1]));
+            assert_eq!(reports.last(), Some(&100));
+        }
+    }
+}
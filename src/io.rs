@@ -0,0 +1,330 @@
+use crate::chips::utils::reduce_by_cyclo;
+use halo2_base::halo2_proofs::plonk::Assigned;
+use halo2_base::utils::ScalarField;
+use halo2_base::Context;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Write};
+use std::path::Path;
+
+/// Header of the witness dump format
+const WITNESS_HEADER: &str = "zk-fhe-witness v1";
+
+/// Dump the advice assignments of a context to a file
+///
+/// * The file starts with the line `zk-fhe-witness v1 <number of cells>`
+/// * Followed by one line per advice cell, in the order of assignment, containing the little endian representation of the cell value encoded in hex
+/// * Rational assignments are evaluated before being dumped, therefore the dump only contains field elements
+/// * The format is stable: dumping the same context twice produces the same file
+pub fn dump_witness<F: ScalarField>(ctx: &Context<F>, path: impl AsRef<Path>) -> Result<(), Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    writeln!(writer, "{} {}", WITNESS_HEADER, ctx.advice.len())?;
+
+    for cell in &ctx.advice {
+        let value = cell.evaluate();
+        let hex = value
+            .to_repr()
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        writeln!(writer, "{}", hex)?;
+    }
+
+    writer.flush()
+}
+
+/// Load the advice assignments dumped by `dump_witness` into a context
+///
+/// * The context must have been synthesized by the same circuit that produced the dump, namely it must contain the same number of advice cells
+/// * The value of each advice cell is overwritten by the value read from the file
+/// * Returns an error if the file is malformed or if the number of cells does not match
+pub fn load_witness<F: ScalarField>(
+    ctx: &mut Context<F>,
+    path: impl AsRef<Path>,
+) -> Result<(), Error> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut lines = reader.lines();
+
+    let header = lines
+        .next()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "missing witness header"))??;
+    let num_cells = header
+        .strip_prefix(WITNESS_HEADER)
+        .and_then(|len| len.trim().parse::<usize>().ok())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "invalid witness header"))?;
+
+    if num_cells != ctx.advice.len() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "expected {} advice cells, found {}",
+                ctx.advice.len(),
+                num_cells
+            ),
+        ));
+    }
+
+    let mut values = Vec::with_capacity(num_cells);
+
+    for line in lines {
+        let line = line?;
+        let mut repr = F::Repr::default();
+
+        if line.len() != 2 * repr.as_ref().len() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "invalid field element length",
+            ));
+        }
+
+        for (i, byte) in repr.as_mut().iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&line[2 * i..2 * i + 2], 16)
+                .map_err(|_| Error::new(ErrorKind::InvalidData, "invalid hex encoding"))?;
+        }
+
+        let value = Option::<F>::from(F::from_repr(repr))
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "non canonical field element"))?;
+        values.push(value);
+    }
+
+    if values.len() != num_cells {
+        return Err(Error::new(ErrorKind::InvalidData, "truncated witness file"));
+    }
+
+    for (cell, value) in ctx.advice.iter_mut().zip(values) {
+        *cell = Assigned::Trivial(value);
+    }
+
+    Ok(())
+}
+
+/// Parse a polynomial in the hex polynomial string format of Microsoft SEAL into a vector of coefficients
+///
+/// * This is the format produced by `seal::Plaintext::to_string()` and accepted by the `seal::Plaintext(const std::string &)` constructor (SEAL 3.x and 4.x)
+/// * The polynomial is written as a sum of terms separated by " + ", from the highest degree term to the constant term, e.g. `7FFx^3 + 1x^1 + 3`
+/// * Each coefficient is encoded in hex (upper or lower case), each exponent in decimal. Zero coefficients are omitted
+/// * Returns the coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * Returns an error if the string is malformed, if an exponent is greater than DEG or appears twice, or if a coefficient does not fit in a u64
+pub fn parse_seal_hex_poly<const DEG: usize>(poly: &str) -> Result<Vec<u64>, Error> {
+    let mut coeffs = vec![0u64; DEG + 1];
+    let mut seen = vec![false; DEG + 1];
+
+    let poly = poly.trim();
+
+    // The zero polynomial is written as "0"
+    if poly.is_empty() || poly == "0" {
+        return Ok(coeffs);
+    }
+
+    for term in poly.split(" + ") {
+        let (coeff, exponent) = match term.split_once("x^") {
+            Some((coeff, exponent)) => {
+                let exponent = exponent
+                    .parse::<usize>()
+                    .map_err(|_| Error::new(ErrorKind::InvalidData, "invalid SEAL exponent"))?;
+                (coeff, exponent)
+            }
+            None => (term, 0),
+        };
+
+        if exponent > DEG {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("SEAL term of degree {} exceeds degree {}", exponent, DEG),
+            ));
+        }
+        if seen[exponent] {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("SEAL term of degree {} appears twice", exponent),
+            ));
+        }
+
+        let coeff = u64::from_str_radix(coeff, 16)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "invalid SEAL coefficient"))?;
+
+        // coeffs[DEG - e] is the coefficient of x^e
+        coeffs[DEG - exponent] = coeff;
+        seen[exponent] = true;
+    }
+
+    Ok(coeffs)
+}
+
+/// Load a list of polynomials in the hex polynomial string format of Microsoft SEAL from a file, one polynomial per line
+///
+/// * Each non empty line is parsed with `parse_seal_hex_poly`. A ciphertext (c0, c1) is dumped as two lines
+/// * Returns the polynomials in the order of the lines
+pub fn load_seal_hex_polys<const DEG: usize>(
+    path: impl AsRef<Path>,
+) -> Result<Vec<Vec<u64>>, Error> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut polys = vec![];
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        polys.push(parse_seal_hex_poly::<DEG>(&line)?);
+    }
+
+    Ok(polys)
+}
+
+/// Compares the reduction by the cyclotomic polynomial x^N + 1 of the crate against polynomials reduced by Microsoft SEAL
+///
+/// * The file contains pairs of lines in the hex polynomial string format of SEAL: a product of degree DEG_DVD = 2N - 2 that is not reduced, followed by the same product reduced in Z_Q[x]/(x^N + 1) by SEAL
+/// * Each product is reduced with `utils::reduce_by_cyclo`, which mirrors the reduction performed by `poly_divide_by_cyclo`, and the remainder is compared to the SEAL polynomial
+/// * Returns the number of compared pairs
+/// * Returns an error if the file is malformed, if a SEAL polynomial has degree N or more, or if a remainder does not match
+pub fn check_seal_reduction<const N: usize, const DEG_DVD: usize, const Q: u64>(
+    path: impl AsRef<Path>,
+) -> Result<usize, Error> {
+    assert_eq!(DEG_DVD, 2 * N - 2);
+
+    let polys = load_seal_hex_polys::<DEG_DVD>(path)?;
+
+    if polys.len() % 2 != 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "expected pairs of unreduced and reduced SEAL polynomials",
+        ));
+    }
+
+    for (i, pair) in polys.chunks(2).enumerate() {
+        let (product, expected) = (&pair[0], &pair[1]);
+
+        // The SEAL polynomial lives in Z_Q[x]/(x^N + 1), therefore its coefficients of degree N or more must be zero
+        let (high, expected) = expected.split_at(DEG_DVD - (N - 1));
+        if high.iter().any(|&coeff| coeff != 0) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("reduced SEAL polynomial {} has degree at least {}", i, N),
+            ));
+        }
+
+        if reduce_by_cyclo::<N, Q>(product) != expected {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("reduction of SEAL polynomial {} does not match", i),
+            ));
+        }
+    }
+
+    Ok(polys.len() / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::poly_operations::poly_mul_equal_deg;
+    use crate::chips::test_utils::{load_poly, mock_run};
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+    use halo2_base::safe_types::RangeInstructions;
+    use std::env::temp_dir;
+    use std::fs::{read, remove_file};
+
+    /// Pairs of unreduced and reduced polynomials for N = 8 and Q = 12289
+    const SEAL_FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/seal_reduction.txt");
+
+    #[test]
+    fn parse_seal_hex_poly_format() {
+        assert_eq!(
+            parse_seal_hex_poly::<3>("7FFx^3 + 1x^1 + 3").unwrap(),
+            vec![0x7ff, 0, 1, 3]
+        );
+        assert_eq!(parse_seal_hex_poly::<3>("ax^2").unwrap(), vec![0, 10, 0, 0]);
+        assert_eq!(parse_seal_hex_poly::<3>("0").unwrap(), vec![0, 0, 0, 0]);
+
+        assert!(parse_seal_hex_poly::<3>("1x^4").is_err());
+        assert!(parse_seal_hex_poly::<3>("1x^2 + 2x^2").is_err());
+        assert!(parse_seal_hex_poly::<3>("Gx^2").is_err());
+    }
+
+    #[test]
+    fn seal_reduction_matches_fixture() {
+        assert_eq!(
+            check_seal_reduction::<8, 14, 12289>(SEAL_FIXTURE).unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn seal_reduction_reports_mismatch() {
+        let path = temp_dir().join(format!("zk-fhe-seal-{}.txt", std::process::id()));
+
+        // Add 1 to the constant term of the first reduced polynomial
+        let fixture = std::fs::read_to_string(SEAL_FIXTURE).unwrap();
+        let mut lines = fixture.lines().map(String::from).collect::<Vec<String>>();
+        let (head, constant) = lines[1].rsplit_once(" + ").unwrap();
+        let constant = u64::from_str_radix(constant, 16).unwrap() + 1;
+        lines[1] = format!("{} + {:X}", head, constant);
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        assert!(check_seal_reduction::<8, 14, 12289>(&path).is_err());
+
+        remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn witness_round_trip() {
+        let path = temp_dir().join(format!("zk-fhe-witness-{}.txt", std::process::id()));
+        let reloaded_path = temp_dir().join(format!(
+            "zk-fhe-witness-reloaded-{}.txt",
+            std::process::id()
+        ));
+
+        let (values, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[3, 1, 4, 1]);
+            let b = load_poly(ctx, &[5, 9, 2, 6]);
+            poly_mul_equal_deg::<3, Fr>(ctx, &a, &b, range.gate());
+
+            dump_witness(ctx, &path).unwrap();
+            ctx.advice
+                .iter()
+                .map(|cell| cell.evaluate())
+                .collect::<Vec<Fr>>()
+        });
+        assert!(satisfied);
+
+        // The same circuit synthesized on other inputs takes the dumped witness once it is reloaded
+        let (reloaded_values, satisfied) = mock_run::<Fr, _>(|ctx, range| {
+            let a = load_poly(ctx, &[0, 0, 0, 0]);
+            let b = load_poly(ctx, &[7, 7, 7, 7]);
+            poly_mul_equal_deg::<3, Fr>(ctx, &a, &b, range.gate());
+
+            load_witness(ctx, &path).unwrap();
+            dump_witness(ctx, &reloaded_path).unwrap();
+            ctx.advice
+                .iter()
+                .map(|cell| cell.evaluate())
+                .collect::<Vec<Fr>>()
+        });
+        assert!(satisfied);
+        assert_eq!(reloaded_values, values);
+        assert_eq!(read(&reloaded_path).unwrap(), read(&path).unwrap());
+
+        remove_file(&path).unwrap();
+        remove_file(&reloaded_path).unwrap();
+    }
+
+    #[test]
+    fn load_witness_rejects_other_circuit() {
+        let path = temp_dir().join(format!("zk-fhe-witness-other-{}.txt", std::process::id()));
+
+        mock_run::<Fr, _>(|ctx, _| {
+            load_poly(ctx, &[1, 2, 3]);
+            dump_witness(ctx, &path).unwrap();
+        });
+
+        // The dump has 3 advice cells, while this context has 4
+        mock_run::<Fr, _>(|ctx, _| {
+            load_poly(ctx, &[1, 2, 3, 4]);
+            assert!(load_witness(ctx, &path).is_err());
+        });
+
+        remove_file(&path).unwrap();
+    }
+}
@@ -1 +1,4 @@
 pub mod chips;
+pub mod io;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,180 @@
+use crate::chips::utils::div_by_cyclo_witness_var;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// Quotient and remainder of the division of a polynomial by a cyclotomic polynomial, as returned by `div_by_cyclo_json`
+///
+/// * The coefficients are serialized as decimal strings, since JavaScript numbers cannot represent integers above 2^53 exactly
+#[derive(Serialize, Deserialize)]
+struct DivisionOutput {
+    quotient: Vec<String>,
+    remainder: Vec<String>,
+}
+
+/// Parses a JSON array of coefficients given as decimal strings, e.g. `["1", "0", "18446744073709551615"]`
+fn parse_coeffs(json: &str, name: &str) -> Result<Vec<u64>, String> {
+    let coeffs: Vec<String> = serde_json::from_str(json).map_err(|e| format!("{}: {}", name, e))?;
+    coeffs
+        .iter()
+        .map(|coeff| {
+            coeff
+                .parse::<u64>()
+                .map_err(|e| format!("{}: invalid coefficient {:?}: {}", name, coeff, e))
+        })
+        .collect()
+}
+
+/// Checks the preconditions of `div_by_cyclo_witness_var`, so that invalid inputs are reported to JavaScript instead of panicking
+fn check_division_inputs(dividend: &[u64], divisor: &[u64], q: u64) -> Result<(), String> {
+    if q < 2 {
+        return Err("q must be at least 2".to_string());
+    }
+    if divisor.iter().all(|&x| x == 0) {
+        return Err("cannot divide by a zero polynomial".to_string());
+    }
+    if dividend.is_empty() || divisor.len() < 2 || dividend.len() <= divisor.len() {
+        return Err(
+            "the dividend must have a greater degree than the divisor, which must have degree at least 1"
+                .to_string(),
+        );
+    }
+    // The long division divides by the leading coefficient of the divisor on integers, therefore it must be 1 as for a cyclotomic polynomial
+    if divisor[0] != 1 {
+        return Err("the divisor must be monic".to_string());
+    }
+    if dividend.iter().chain(divisor.iter()).any(|&x| x >= q) {
+        return Err("the coefficients must be in the range [0, q - 1]".to_string());
+    }
+    Ok(())
+}
+
+/// Computes the witnesses of `poly_divide_by_cyclo` from JSON coefficient arrays, e.g. in the browser
+///
+/// * `dividend` and `divisor` are JSON arrays of coefficients [a_DEG, a_DEG-1, ..., a_1, a_0] where a_0 is the constant term
+/// * The coefficients are given as decimal strings, e.g. `["1", "0", "1"]`, so that values above 2^53 survive JavaScript
+/// * q is the modulus of the Ring. The coefficients of the dividend and divisor must be in the range [0, q - 1]
+/// * The divisor must be monic and non zero, and of degree at least 1 and smaller than the degree of the dividend
+/// * Returns a JSON object `{"quotient": [...], "remainder": [...]}` of decimal strings in the shape expected by `poly_divide_by_cyclo_with_witness`.
+///   The remainder is padded with leading zeroes to the degree of the dividend
+/// * Returns an error instead of panicking if the inputs are malformed or do not satisfy the preconditions above
+/// * Only the out-of-circuit arithmetic is exported. The circuit itself is synthesized natively
+#[wasm_bindgen]
+pub fn div_by_cyclo_json(dividend: &str, divisor: &str, q: u64) -> Result<String, JsValue> {
+    let dividend = parse_coeffs(dividend, "dividend").map_err(|e| JsValue::from_str(&e))?;
+    let divisor = parse_coeffs(divisor, "divisor").map_err(|e| JsValue::from_str(&e))?;
+
+    check_division_inputs(&dividend, &divisor, q).map_err(|e| JsValue::from_str(&e))?;
+
+    let (quotient, remainder) = div_by_cyclo_witness_var(
+        &dividend,
+        &divisor,
+        dividend.len() - 1,
+        divisor.len() - 1,
+        q,
+    );
+
+    serde_json::to_string(&DivisionOutput {
+        quotient: quotient.iter().map(|x| x.to_string()).collect(),
+        remainder: remainder.iter().map(|x| x.to_string()).collect(),
+    })
+    .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Compile check of the exported functions for the browser: this item is only built with `--target wasm32-unknown-unknown`,
+// where the `JsValue` errors are backed by JavaScript values instead of the native stubs of wasm-bindgen
+#[cfg(target_arch = "wasm32")]
+const _: fn(&str, &str, u64) -> Result<String, JsValue> = div_by_cyclo_json;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The error paths build a `JsValue`, which is only implemented on wasm32, therefore they are tested through the native helpers
+
+    #[test]
+    fn parse_coeffs_accepts_decimal_strings() {
+        assert_eq!(
+            parse_coeffs(r#"["1", "0", "18446744073709551615"]"#, "dividend"),
+            Ok(vec![1, 0, u64::MAX])
+        );
+        assert!(parse_coeffs(r#"[1, 0, 1]"#, "dividend").is_err());
+        assert!(parse_coeffs(r#"["1", "-1"]"#, "dividend").is_err());
+        assert!(parse_coeffs(r#"["18446744073709551616"]"#, "dividend").is_err());
+        assert!(parse_coeffs("not json", "divisor")
+            .unwrap_err()
+            .starts_with("divisor: "));
+    }
+
+    #[test]
+    fn check_division_inputs_reports_each_precondition() {
+        let cyclo = [1, 0, 0, 0, 1];
+        let dividend = [1, 0, 0, 0, 0, 0, 2];
+
+        assert_eq!(check_division_inputs(&dividend, &cyclo, 97), Ok(()));
+        assert_eq!(
+            check_division_inputs(&dividend, &cyclo, 1),
+            Err("q must be at least 2".to_string())
+        );
+        assert_eq!(
+            check_division_inputs(&dividend, &[0, 0, 0], 97),
+            Err("cannot divide by a zero polynomial".to_string())
+        );
+        assert_eq!(
+            check_division_inputs(&[], &cyclo, 97),
+            Err("the dividend must have a greater degree than the divisor, which must have degree at least 1".to_string())
+        );
+        assert_eq!(
+            check_division_inputs(&dividend, &[1], 97),
+            Err("the dividend must have a greater degree than the divisor, which must have degree at least 1".to_string())
+        );
+        assert_eq!(
+            check_division_inputs(&[1, 2, 3], &cyclo, 97),
+            Err("the dividend must have a greater degree than the divisor, which must have degree at least 1".to_string())
+        );
+        assert_eq!(
+            check_division_inputs(&dividend, &[2, 0, 0, 0, 1], 97),
+            Err("the divisor must be monic".to_string())
+        );
+        assert_eq!(
+            check_division_inputs(&[1, 0, 0, 0, 0, 0, 97], &cyclo, 97),
+            Err("the coefficients must be in the range [0, q - 1]".to_string())
+        );
+    }
+
+    fn divide(dividend: &str, divisor: &str, q: u64) -> (Vec<String>, Vec<String>) {
+        let output: DivisionOutput =
+            serde_json::from_str(&div_by_cyclo_json(dividend, divisor, q).unwrap()).unwrap();
+        (output.quotient, output.remainder)
+    }
+
+    #[test]
+    fn div_by_cyclo_json_outputs_padded_witnesses() {
+        // x^6 + 2 = x^2 * (x^4 + 1) - x^2 + 2
+        let (quotient, remainder) = divide(
+            r#"["1", "0", "0", "0", "0", "0", "2"]"#,
+            r#"["1", "0", "0", "0", "1"]"#,
+            97,
+        );
+        assert_eq!(quotient, vec!["1", "0", "0"]);
+        assert_eq!(remainder, vec!["0", "0", "0", "0", "96", "0", "2"]);
+
+        // x^5 + (q - 1) = x * (x^4 + 1) - x - 1, with coefficients above 2^53
+        let (quotient, remainder) = divide(
+            r#"["1", "0", "0", "0", "0", "18446744073709551556"]"#,
+            r#"["1", "0", "0", "0", "1"]"#,
+            18446744073709551557,
+        );
+        assert_eq!(quotient, vec!["1", "0"]);
+        assert_eq!(
+            remainder,
+            vec![
+                "0",
+                "0",
+                "0",
+                "0",
+                "18446744073709551556",
+                "18446744073709551556"
+            ]
+        );
+    }
+}
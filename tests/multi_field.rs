@@ -0,0 +1,144 @@
+//! Runs the core arithmetic chips over several prime fields, to catch any reliance on the modulus of a specific field in the overflow analysis
+//!
+//! * BN254 scalar field: 254 bits, the default field of the circuits
+//! * secp256k1 scalar field: 256 bits
+
+use halo2_base::gates::builder::{GateThreadBuilder, RangeCircuitBuilder};
+use halo2_base::halo2_proofs::dev::MockProver;
+use halo2_base::halo2_proofs::halo2curves::{bn256, secp256k1};
+use halo2_base::safe_types::{RangeChip, RangeInstructions};
+use halo2_base::utils::ScalarField;
+use halo2_base::{AssignedValue, Context};
+use std::env::set_var;
+use std::sync::Mutex;
+use zk_fhe::chips::params::PolyParams;
+use zk_fhe::chips::poly_operations::{
+    poly_add, poly_divide_by_cyclo, poly_mul_equal_deg, poly_reduce,
+};
+use zk_fhe::chips::utils::vec_assigned_to_vec_u64;
+
+const K: usize = 14;
+const LOOKUP_BITS: usize = 13;
+
+const N: usize = 4;
+const DEG: usize = N - 1;
+const DEG_DVD: usize = 2 * N - 2;
+const DEG_DVS: usize = N;
+// The Mersenne prime 2^61 - 1, such that (Q-1) * (Q-1) * N needs 124 bits
+const Q: u64 = (1 << 61) - 1;
+
+// The configuration of the mock circuits is passed through environment variables, which are shared by the tests running in parallel
+static MOCK_LOCK: Mutex<()> = Mutex::new(());
+
+/// Assigns the constraints of `f` in a mock circuit over F and returns its output together with true if all the constraints are satisfied
+fn mock_run<F: ScalarField, T>(f: impl FnOnce(&mut Context<F>, &RangeChip<F>) -> T) -> (T, bool) {
+    let _guard = MOCK_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+    set_var("LOOKUP_BITS", LOOKUP_BITS.to_string());
+    let mut builder = GateThreadBuilder::mock();
+    let range = RangeChip::<F>::default(LOOKUP_BITS);
+
+    let out = f(builder.main(0), &range);
+
+    builder.config(K, Some(20));
+    let circuit = RangeCircuitBuilder::mock(builder);
+    let satisfied = MockProver::<F>::run(K as u32, &circuit, vec![])
+        .unwrap()
+        .verify()
+        .is_ok();
+
+    (out, satisfied)
+}
+
+fn load_poly<F: ScalarField>(ctx: &mut Context<F>, coeffs: &[u64]) -> Vec<AssignedValue<F>> {
+    coeffs
+        .iter()
+        .map(|&coeff| ctx.load_witness(F::from(coeff)))
+        .collect()
+}
+
+/// Reference product of a and b in Z_Q[x] without reduction by the cyclotomic polynomial, with the coefficients reduced modulo Q
+fn mul_reference(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut out = vec![0u128; a.len() + b.len() - 1];
+    for (i, &a_i) in a.iter().enumerate() {
+        for (j, &b_j) in b.iter().enumerate() {
+            out[i + j] = (out[i + j] + a_i as u128 * b_j as u128) % Q as u128;
+        }
+    }
+    out.iter().map(|&coeff| coeff as u64).collect()
+}
+
+/// Reference reduction of a polynomial of degree 2N - 2 by x^N + 1 in Z_Q[x]
+fn cyclo_reference(a: &[u64]) -> Vec<u64> {
+    // a[i] is the coefficient of x^(DEG_DVD - i), and x^(N + e) = -x^e
+    let mut out = a[DEG_DVD - DEG..].to_vec();
+    for (i, &coeff) in a[..DEG_DVD - DEG].iter().enumerate() {
+        let exp = DEG_DVD - i - N;
+        out[DEG - exp] = ((out[DEG - exp] as u128 + Q as u128 - coeff as u128) % Q as u128) as u64;
+    }
+    out
+}
+
+/// Multiplies two polynomials of the ring R_Q = Z_Q[x]/(x^N + 1) with the core chips
+/// and checks the result against the reference arithmetic outside the circuit
+fn ring_mul_over_field<F: ScalarField>() {
+    let a = [Q - 1, 0, 1 << 60, 12345];
+    let b = [Q - 1, Q - 2, 3, (1 << 59) + 7];
+
+    // The parameters validated against F are the ones passed to the chips
+    let params = PolyParams::new::<F>(DEG, Q).unwrap();
+    assert!(params.matches::<DEG, Q>());
+    assert_eq!(params.field_capacity(), F::CAPACITY);
+
+    let expected_prod = mul_reference(&a, &b);
+    let expected_sum = a
+        .iter()
+        .zip(&b)
+        .map(|(&a_i, &b_i)| ((a_i as u128 + b_i as u128) % Q as u128) as u64)
+        .collect::<Vec<u64>>();
+
+    let ((sum, prod, remainder), satisfied) = mock_run::<F, _>(|ctx, range| {
+        let a = load_poly(ctx, &a);
+        let b = load_poly(ctx, &b);
+
+        // The coefficients of a + b are in the range [0, 2Q - 2], which needs 62 bits
+        let sum = poly_add::<DEG, F>(ctx, &a, &b, range.gate());
+        let sum = poly_reduce::<DEG, Q, F>(ctx, sum, range, 62);
+
+        let prod = poly_mul_equal_deg::<DEG, F>(ctx, &a, &b, range.gate());
+        let prod = poly_reduce::<DEG_DVD, Q, F>(ctx, prod, range, params.mul_num_bits());
+
+        let mut cyclo = vec![0; DEG_DVS + 1];
+        cyclo[0] = 1;
+        cyclo[DEG_DVS] = 1;
+        let cyclo = load_poly(ctx, &cyclo);
+        let remainder =
+            poly_divide_by_cyclo::<DEG_DVD, DEG_DVS, Q, F>(ctx, prod.clone(), cyclo, range);
+
+        (
+            vec_assigned_to_vec_u64(&sum),
+            vec_assigned_to_vec_u64(&prod),
+            vec_assigned_to_vec_u64(&remainder),
+        )
+    });
+
+    assert!(satisfied);
+    assert_eq!(sum, expected_sum);
+    assert_eq!(prod, expected_prod);
+    // The remainder is padded with leading zeroes to degree DEG_DVD
+    assert!(remainder[..DEG_DVD - DEG].iter().all(|&coeff| coeff == 0));
+    assert_eq!(
+        remainder[DEG_DVD - DEG..],
+        cyclo_reference(&expected_prod)[..]
+    );
+}
+
+#[test]
+fn ring_mul_bn254() {
+    ring_mul_over_field::<bn256::Fr>();
+}
+
+#[test]
+fn ring_mul_secp256k1() {
+    ring_mul_over_field::<secp256k1::Fq>();
+}